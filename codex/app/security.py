@@ -69,9 +69,12 @@ class CreditLimiter:
 
 SECONDS_PER_DAY = 86_400
 AUCTION_DAY_DISCRIMINATOR = hashlib.sha256(b"account:AuctionDay").digest()[:8]
+CONTENT_MANAGER_DISCRIMINATOR = hashlib.sha256(b"account:ContentManager").digest()[:8]
+AUTHORIZATION_EPOCH_DISCRIMINATOR = hashlib.sha256(b"account:AuthorizationEpoch").digest()[:8]
+DAY_CONTENT_DISCRIMINATOR = hashlib.sha256(b"account:DayContent").digest()[:8]
 DEFAULT_PUBKEY = str(PublicKey(bytes(32)))
 _winner_lock = threading.Lock()
-_winner_cache: dict[str, object] = {"day_index": None, "winner": None, "timestamp": 0.0}
+_winner_cache: dict[str, object] = {"day_index": None, "winner": None, "manager": None, "timestamp": 0.0}
 
 
 def _current_day_index() -> int:
@@ -83,6 +86,21 @@ def _auction_day_pda(program_id: PublicKey, day_index: int) -> PublicKey:
     return PublicKey.find_program_address(seed, program_id)[0]
 
 
+def _content_manager_pda(program_id: PublicKey, auction_day: PublicKey) -> PublicKey:
+    seed = [b"content_manager", bytes(auction_day)]
+    return PublicKey.find_program_address(seed, program_id)[0]
+
+
+def _day_content_pda(program_id: PublicKey, auction_day: PublicKey) -> PublicKey:
+    seed = [b"content", bytes(auction_day)]
+    return PublicKey.find_program_address(seed, program_id)[0]
+
+
+def _authorization_epoch_pda(program_id: PublicKey, owner: PublicKey) -> PublicKey:
+    seed = [b"authorization_epoch", bytes(owner)]
+    return PublicKey.find_program_address(seed, program_id)[0]
+
+
 def _decode_winner(data: bytes) -> str | None:
     if len(data) < 8 + 8 + 1 + 32:
         raise ValueError("auction day data too short")
@@ -96,14 +114,12 @@ def _decode_winner(data: bytes) -> str | None:
     return winner
 
 
-def _fetch_auction_day_winner(program_id: str, day_index: int, rpc_url: str) -> str | None:
-    program_key = PublicKey(program_id)
-    auction_day = _auction_day_pda(program_key, day_index)
+def _fetch_account_data(account: PublicKey, rpc_url: str) -> bytes | None:
     body = {
         "jsonrpc": "2.0",
         "id": 1,
         "method": "getAccountInfo",
-        "params": [str(auction_day), {"encoding": "base64"}],
+        "params": [str(account), {"encoding": "base64"}],
     }
     response = requests.post(rpc_url, json=body, timeout=10)
     response.raise_for_status()
@@ -114,22 +130,69 @@ def _fetch_auction_day_winner(program_id: str, day_index: int, rpc_url: str) ->
     data = value.get("data")
     if not data or not isinstance(data, list) or not data[0]:
         return None
-    decoded = base64.b64decode(data[0])
-    return _decode_winner(decoded)
+    return base64.b64decode(data[0])
+
+
+def _fetch_auction_day_winner(program_id: str, day_index: int, rpc_url: str) -> str | None:
+    program_key = PublicKey(program_id)
+    data = _fetch_account_data(_auction_day_pda(program_key, day_index), rpc_url)
+    if data is None:
+        return None
+    return _decode_winner(data)
 
 
-def _get_cached_winner(day_index: int, ttl_seconds: int) -> str | None:
+def _fetch_content_manager(program_id: str, day_index: int, winner: str, rpc_url: str) -> str | None:
+    # The grant only counts while its epoch matches the winner's current
+    # AuthorizationEpoch (missing account = epoch 0).
+    program_key = PublicKey(program_id)
+    auction_day = _auction_day_pda(program_key, day_index)
+    data = _fetch_account_data(_content_manager_pda(program_key, auction_day), rpc_url)
+    if data is None:
+        return None
+    if len(data) < 8 + 32 * 3 + 8 or data[:8] != CONTENT_MANAGER_DISCRIMINATOR:
+        raise ValueError("invalid content manager account")
+    if str(PublicKey(data[8 + 32 : 8 + 64])) != winner:
+        return None
+    manager = str(PublicKey(data[8 + 64 : 8 + 96]))
+    granted_epoch = int.from_bytes(data[8 + 96 : 8 + 104], "little")
+
+    epoch_data = _fetch_account_data(_authorization_epoch_pda(program_key, PublicKey(winner)), rpc_url)
+    current_epoch = 0
+    if epoch_data is not None:
+        if len(epoch_data) < 8 + 32 + 8 or epoch_data[:8] != AUTHORIZATION_EPOCH_DISCRIMINATOR:
+            raise ValueError("invalid authorization epoch account")
+        current_epoch = int.from_bytes(epoch_data[8 + 32 : 8 + 40], "little")
+    if granted_epoch != current_epoch or manager == DEFAULT_PUBKEY:
+        return None
+    return manager
+
+
+def _has_content_bond(program_id: str, day_index: int, winner: str, rpc_url: str) -> bool:
+    # The winner's DayContent must exist and not be slashed (bond posted via submit_content).
+    program_key = PublicKey(program_id)
+    auction_day = _auction_day_pda(program_key, day_index)
+    data = _fetch_account_data(_day_content_pda(program_key, auction_day), rpc_url)
+    if data is None:
+        return False
+    if len(data) < 8 + 32 * 3 + 8 + 8 + 1 or data[:8] != DAY_CONTENT_DISCRIMINATOR:
+        raise ValueError("invalid day content account")
+    slashed = data[8 + 96 + 16] != 0
+    return str(PublicKey(data[8 + 32 : 8 + 64])) == winner and not slashed
+
+
+def _get_cached_winner(day_index: int, ttl_seconds: int) -> tuple[str | None, str | None] | None:
     now = time.time()
     with _winner_lock:
         if _winner_cache["day_index"] == day_index and now - float(_winner_cache["timestamp"]) < ttl_seconds:
-            return _winner_cache["winner"]  # type: ignore[return-value]
+            return _winner_cache["winner"], _winner_cache["manager"]  # type: ignore[return-value]
     return None
 
 
-def _set_cached_winner(day_index: int, winner: str | None) -> None:
+def _set_cached_winner(day_index: int, winner: str | None, manager: str | None) -> None:
     with _winner_lock:
         _winner_cache["day_index"] = day_index
         _winner_cache["winner"] = winner
+        _winner_cache["manager"] = manager
         _winner_cache["timestamp"] = time.time()
 
 
@@ -142,14 +205,24 @@ def is_wallet_authorized(wallet: str) -> bool:
     if day_index < 0:
         return False
     ttl_seconds = int(os.environ.get("WINNER_CACHE_TTL_SECONDS", "30"))
-    winner = _get_cached_winner(day_index, ttl_seconds)
-    if winner is None:
+    cached = _get_cached_winner(day_index, ttl_seconds)
+    if cached is None or cached[0] is None:
         try:
             winner = _fetch_auction_day_winner(program_id, day_index, rpc_url)
+            manager = None
+            if winner is not None and os.environ.get("REQUIRE_CONTENT_BOND", "").lower() in ("1", "true"):
+                if not _has_content_bond(program_id, day_index, winner, rpc_url):
+                    winner = None
+            if winner is not None:
+                manager = _fetch_content_manager(program_id, day_index, winner, rpc_url)
         except (requests.RequestException, json.JSONDecodeError, ValueError) as exc:
             raise ValueError(f"winner lookup failed: {exc}") from exc
-        _set_cached_winner(day_index, winner)
-    return winner is not None and winner == wallet
+        _set_cached_winner(day_index, winner, manager)
+    else:
+        winner, manager = cached
+    if winner is None:
+        return False
+    return wallet == winner or wallet == manager
 
 
 def _hash_body(body_bytes: bytes) -> str:
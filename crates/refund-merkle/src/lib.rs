@@ -0,0 +1,152 @@
+//! Merkle trees over `(bidder, refund_amount)` pairs, shared by the auction
+//! program (`claim_refund_with_proof`) and the off-chain code that builds the
+//! root passed to `settle_day`.
+//!
+//! Leaves are `sha256(0x00 || bidder || amount_le)`. Inner nodes hash their two
+//! children in sorted order under `0x01`, so proofs carry no left/right bits.
+//! An odd node at the end of a layer moves up unchanged.
+
+use solana_sha256_hasher::hashv;
+
+pub type Hash = [u8; 32];
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(bidder: &[u8; 32], amount: u64) -> Hash {
+    hashv(&[LEAF_PREFIX, bidder, &amount.to_le_bytes()]).to_bytes()
+}
+
+pub fn node(a: &Hash, b: &Hash) -> Hash {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, low, high]).to_bytes()
+}
+
+pub fn verify(root: &Hash, leaf: Hash, proof: &[Hash]) -> bool {
+    let computed = proof.iter().fold(leaf, |hash, sibling| node(&hash, sibling));
+    computed == *root
+}
+
+pub struct RefundTree {
+    // `layers[0]` holds the leaves; the last layer holds the root.
+    layers: Vec<Vec<Hash>>,
+}
+
+impl RefundTree {
+    // Leaves keep the order of `refunds`; `proof(i)` refers to `refunds[i]`.
+    pub fn new(refunds: &[([u8; 32], u64)]) -> Self {
+        let mut layers = vec![refunds
+            .iter()
+            .map(|(bidder, amount)| leaf(bidder, *amount))
+            .collect::<Vec<_>>()];
+        while layers.last().is_some_and(|layer| layer.len() > 1) {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    // All zeroes for an empty tree, which `settle_day` treats as "no root".
+    pub fn root(&self) -> Hash {
+        self.layers
+            .last()
+            .and_then(|layer| layer.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, index: usize) -> Vec<Hash> {
+        let mut proof = Vec::new();
+        let mut index = index;
+        for layer in &self.layers[..self.layers.len().saturating_sub(1)] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refunds(count: usize) -> Vec<([u8; 32], u64)> {
+        (0..count)
+            .map(|i| ([i as u8 + 1; 32], 1_000 * (i as u64 + 1)))
+            .collect()
+    }
+
+    fn assert_round_trip(count: usize) {
+        let refunds = refunds(count);
+        let tree = RefundTree::new(&refunds);
+        let root = tree.root();
+        for (i, (bidder, amount)) in refunds.iter().enumerate() {
+            assert!(
+                verify(&root, leaf(bidder, *amount), &tree.proof(i)),
+                "leaf {i} of {count} failed to verify"
+            );
+        }
+    }
+
+    #[test]
+    fn single_leaf_is_its_own_root() {
+        let refunds = refunds(1);
+        let tree = RefundTree::new(&refunds);
+        assert_eq!(tree.root(), leaf(&refunds[0].0, refunds[0].1));
+        assert!(tree.proof(0).is_empty());
+        assert_round_trip(1);
+    }
+
+    #[test]
+    fn two_leaves_round_trip() {
+        assert_round_trip(2);
+    }
+
+    #[test]
+    fn odd_leaf_counts_round_trip() {
+        for count in [3, 5, 7, 9] {
+            assert_round_trip(count);
+        }
+    }
+
+    #[test]
+    fn even_leaf_counts_round_trip() {
+        for count in [4, 6, 8, 10] {
+            assert_round_trip(count);
+        }
+    }
+
+    #[test]
+    fn tampered_amount_fails() {
+        let refunds = refunds(5);
+        let tree = RefundTree::new(&refunds);
+        let root = tree.root();
+        for (i, (bidder, amount)) in refunds.iter().enumerate() {
+            assert!(!verify(&root, leaf(bidder, amount + 1), &tree.proof(i)));
+        }
+    }
+
+    #[test]
+    fn proof_for_another_bidder_fails() {
+        let refunds = refunds(4);
+        let tree = RefundTree::new(&refunds);
+        let (bidder, amount) = refunds[0];
+        assert!(!verify(&tree.root(), leaf(&bidder, amount), &tree.proof(1)));
+    }
+
+    #[test]
+    fn empty_tree_has_zero_root() {
+        assert_eq!(RefundTree::new(&[]).root(), [0u8; 32]);
+    }
+}
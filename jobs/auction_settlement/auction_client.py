@@ -4,7 +4,7 @@ from dataclasses import dataclass
 from typing import Iterable, List, Optional, Tuple
 
 from base58 import b58decode
-from borsh_construct import Bool, CStruct, I64, U8, U32, U64
+from borsh_construct import Bool, CStruct, I64, U8, U16, U32, U64
 from solana.publickey import PublicKey
 from solders.signature import Signature
 from solana.rpc.api import Client
@@ -15,10 +15,28 @@ from solana.keypair import Keypair
 
 SECONDS_PER_DAY = 86_400
 INIT_DAY_MAX_AHEAD_DAYS = 2
+CRANK_KEY_LEN = 16
 
 CONFIG_DISCRIMINATOR = hashlib.sha256(b"account:Config").digest()[:8]
 AUCTION_DAY_DISCRIMINATOR = hashlib.sha256(b"account:AuctionDay").digest()[:8]
 BID_RECEIPT_DISCRIMINATOR = hashlib.sha256(b"account:BidReceipt").digest()[:8]
+SCHEDULE_OVERRIDE_DISCRIMINATOR = hashlib.sha256(b"account:ScheduleOverride").digest()[:8]
+
+# `Config.recipient_splits` sits after every older field; Config only ever
+# grows at the end, so its offset (discriminator included) is fixed.
+CONFIG_RECIPIENT_SPLITS_OFFSET = 498
+MAX_RECIPIENT_SPLITS = 4
+# Same for `AuctionDay.receipt_tree`.
+AUCTION_DAY_RECEIPT_TREE_OFFSET = 1362
+
+# Programs `refund_batch` calls to mint participation cNFTs.
+BUBBLEGUM_PROGRAM_ID = PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY")
+SPL_NOOP_PROGRAM_ID = PublicKey("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV")
+SPL_ACCOUNT_COMPRESSION_PROGRAM_ID = PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK")
+
+# Programs `mint_trophy` uses for the day's trophy (deed) NFT.
+TOKEN_2022_PROGRAM_ID = PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb")
+ASSOCIATED_TOKEN_PROGRAM_ID = PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")
 
 
 CONFIG_LAYOUT = CStruct(
@@ -47,6 +65,21 @@ BID_RECEIPT_LAYOUT = CStruct(
     "bidder" / U8[32],
     "amount" / U64,
     "refunded" / Bool,
+    "last_bid_ts" / I64,
+    "first_bid_ts" / I64,
+    "refund_tip_lamports" / U64,
+)
+
+RECIPIENT_SPLITS_LAYOUT = CStruct(
+    "recipient_splits" / CStruct("recipient" / U8[32], "bps" / U16)[MAX_RECIPIENT_SPLITS],
+    "recipient_split_count" / U8,
+)
+
+SCHEDULE_OVERRIDE_LAYOUT = CStruct(
+    "day_index" / I64,
+    "closed" / Bool,
+    "updated_at" / I64,
+    "bump" / U8,
 )
 
 
@@ -56,6 +89,8 @@ class Config:
     loser_fee_lamports: int
     min_increment_lamports: int
     bump: int
+    # Split recipients in table order; `settle_day` takes them as remaining accounts.
+    recipient_splits: List[PublicKey]
 
 
 @dataclass
@@ -71,6 +106,8 @@ class AuctionDay:
     refund_pool_remaining: int
     fee_pool_remaining: int
     vault_bump: int
+    # None when the day mints no participation cNFTs.
+    receipt_tree: Optional[PublicKey] = None
 
 
 @dataclass
@@ -79,6 +116,7 @@ class BidReceipt:
     bidder: PublicKey
     amount: int
     refunded: bool
+    refund_tip_lamports: int
 
 
 def parse_keypair(raw: str) -> Keypair:
@@ -115,15 +153,107 @@ def pda_bid_receipt(
     )
 
 
+def pda_bid_index(
+    program_id: PublicKey, auction_day: PublicKey, ordinal: int
+) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address(
+        [b"bid_index", bytes(auction_day), ordinal.to_bytes(4, "little")], program_id
+    )
+
+
+def pda_schedule_override(program_id: PublicKey, day_index: int) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address(
+        [b"schedule_override", day_index.to_bytes(8, "little", signed=True)], program_id
+    )
+
+
+def pda_category_table(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"category_table"], program_id)
+
+
+def pda_param_history(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"param_history"], program_id)
+
+
+def pda_cranker_registry(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"cranker_registry"], program_id)
+
+
+def pda_cranker_bond(program_id: PublicKey, cranker: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"cranker_bond", bytes(cranker)], program_id)
+
+
+def pda_price_series(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"price_series"], program_id)
+
+
+def pda_global_stats(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"global_stats"], program_id)
+
+
+def pda_jackpot(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"jackpot"], program_id)
+
+
+def pda_protocol_treasury(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"protocol_treasury"], program_id)
+
+
+def pda_treasury(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"treasury"], program_id)
+
+
+def pda_cnft_authority(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"cnft_authority"], program_id)
+
+
+def pda_tree_config(merkle_tree: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([bytes(merkle_tree)], BUBBLEGUM_PROGRAM_ID)
+
+
+def pda_trophy_mint(program_id: PublicKey, auction_day: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"trophy", bytes(auction_day)], program_id)
+
+
+def associated_token_address(owner: PublicKey, mint: PublicKey, token_program: PublicKey) -> PublicKey:
+    address, _ = PublicKey.find_program_address(
+        [bytes(owner), bytes(token_program), bytes(mint)], ASSOCIATED_TOKEN_PROGRAM_ID
+    )
+    return address
+
+
+def pda_bidder_profile(program_id: PublicKey, wallet: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"bidder_profile", bytes(wallet)], program_id)
+
+
+def pda_event_authority(program_id: PublicKey) -> Tuple[PublicKey, int]:
+    return PublicKey.find_program_address([b"__event_authority"], program_id)
+
+
+def event_cpi_accounts(program_id: PublicKey) -> List[AccountMeta]:
+    # Instructions that emit events end with the event authority and the program
+    # itself, which `emit_cpi!` needs for its self-CPI.
+    event_authority, _ = pda_event_authority(program_id)
+    return [
+        AccountMeta(event_authority, is_signer=False, is_writable=False),
+        AccountMeta(program_id, is_signer=False, is_writable=False),
+    ]
+
+
 def decode_config(data: bytes) -> Config:
     if data[:8] != CONFIG_DISCRIMINATOR:
         raise ValueError("Invalid Config discriminator")
     parsed = CONFIG_LAYOUT.parse(data[8:])
+    splits = RECIPIENT_SPLITS_LAYOUT.parse(data[CONFIG_RECIPIENT_SPLITS_OFFSET:])
     return Config(
         recipient_pubkey=PublicKey(parsed.recipient_pubkey),
         loser_fee_lamports=parsed.loser_fee_lamports,
         min_increment_lamports=parsed.min_increment_lamports,
         bump=parsed.bump,
+        recipient_splits=[
+            PublicKey(split.recipient)
+            for split in splits.recipient_splits[: splits.recipient_split_count]
+        ],
     )
 
 
@@ -143,9 +273,19 @@ def decode_auction_day(data: bytes) -> AuctionDay:
         refund_pool_remaining=parsed.refund_pool_remaining,
         fee_pool_remaining=parsed.fee_pool_remaining,
         vault_bump=parsed.vault_bump,
+        receipt_tree=decode_optional_pubkey(
+            data[AUCTION_DAY_RECEIPT_TREE_OFFSET : AUCTION_DAY_RECEIPT_TREE_OFFSET + 32]
+        ),
     )
 
 
+def decode_optional_pubkey(raw: bytes) -> Optional[PublicKey]:
+    # Accounts created before the field existed are too short to hold it.
+    if len(raw) < 32 or raw == bytes(32):
+        return None
+    return PublicKey(raw)
+
+
 def decode_bid_receipt(data: bytes) -> BidReceipt:
     if data[:8] != BID_RECEIPT_DISCRIMINATOR:
         raise ValueError("Invalid BidReceipt discriminator")
@@ -155,6 +295,7 @@ def decode_bid_receipt(data: bytes) -> BidReceipt:
         bidder=PublicKey(parsed.bidder),
         amount=parsed.amount,
         refunded=parsed.refunded,
+        refund_tip_lamports=parsed.refund_tip_lamports,
     )
 
 
@@ -170,24 +311,40 @@ def encode_pubkey(pubkey: PublicKey) -> bytes:
     return bytes(pubkey)
 
 
-def encode_vec_pubkeys(pubkeys: Iterable[PublicKey]) -> bytes:
-    pubkeys = list(pubkeys)
-    data = encode_u32(len(pubkeys))
-    for key in pubkeys:
-        data += encode_pubkey(key)
-    return data
+def encode_option_crank_key(key: Optional[bytes]) -> bytes:
+    if key is None:
+        return b"\x00"
+    if len(key) != CRANK_KEY_LEN:
+        raise ValueError(f"Idempotency key must be {CRANK_KEY_LEN} bytes")
+    return b"\x01" + key
+
+
+def crank_key(*parts) -> bytes:
+    material = ":".join(str(part) for part in parts).encode()
+    return hashlib.sha256(material).digest()[:CRANK_KEY_LEN]
+
+
+def encode_vec_u8(values: Iterable[int]) -> bytes:
+    values = bytes(values)
+    return encode_u32(len(values)) + values
 
 
 def instruction_init_day(program_id: PublicKey, payer: PublicKey, day_index: int) -> TransactionInstruction:
     data = anchor_discriminator("init_day") + encode_i64(day_index)
+    config, _ = pda_config(program_id)
     auction_day, _ = pda_auction_day(program_id, day_index)
     vault, _ = pda_vault(program_id, auction_day)
+    schedule_override, _ = pda_schedule_override(program_id, day_index)
+    category_table, _ = pda_category_table(program_id)
     keys = [
         AccountMeta(payer, is_signer=True, is_writable=True),
+        AccountMeta(config, is_signer=False, is_writable=False),
         AccountMeta(auction_day, is_signer=False, is_writable=True),
         AccountMeta(vault, is_signer=False, is_writable=True),
         AccountMeta(PublicKey("11111111111111111111111111111111"), is_signer=False, is_writable=False),
-    ]
+        AccountMeta(schedule_override, is_signer=False, is_writable=False),
+        AccountMeta(category_table, is_signer=False, is_writable=False),
+    ] + event_cpi_accounts(program_id)
     return TransactionInstruction(program_id=program_id, data=data, keys=keys)
 
 
@@ -220,15 +377,140 @@ def instruction_settle_day(
     vault: PublicKey,
     recipient: PublicKey,
     day_index: int,
+    idempotency_key: Optional[bytes] = None,
+    winner: Optional[PublicKey] = None,
+    settler: Optional[PublicKey] = None,
+    winner_profile: Optional[PublicKey] = None,
+    jackpot: Optional[PublicKey] = None,
+    protocol_treasury: Optional[PublicKey] = None,
+    treasury: Optional[PublicKey] = None,
+    split_recipients: Iterable[PublicKey] = (),
 ) -> TransactionInstruction:
-    data = anchor_discriminator("settle_day") + encode_i64(day_index)
+    data = (
+        anchor_discriminator("settle_day")
+        + encode_i64(day_index)
+        + encode_option_crank_key(idempotency_key)
+        # No Merkle refund root: the nightly job refunds through `refund_batch`.
+        + b"\x00"
+    )
     keys = [
-        AccountMeta(config, is_signer=False, is_writable=False),
+        AccountMeta(config, is_signer=False, is_writable=True),
         AccountMeta(auction_day, is_signer=False, is_writable=True),
         AccountMeta(vault, is_signer=False, is_writable=True),
         AccountMeta(recipient, is_signer=False, is_writable=True),
         AccountMeta(PublicKey("11111111111111111111111111111111"), is_signer=False, is_writable=False),
     ]
+    # The program ID stands in for omitted optional accounts. The event
+    # accounts come last, so every optional account needs a slot.
+    placeholder = AccountMeta(program_id, is_signer=False, is_writable=False)
+    # No display hook; the schedule override PDA is always passed.
+    schedule_override, _ = pda_schedule_override(program_id, day_index)
+    keys += [placeholder, AccountMeta(schedule_override, is_signer=False, is_writable=False)]
+    if winner is not None:
+        # Settle the winner's receipt in the same instruction.
+        winner_receipt, _ = pda_bid_receipt(program_id, auction_day, winner)
+        keys += [
+            AccountMeta(winner_receipt, is_signer=False, is_writable=True),
+            AccountMeta(winner, is_signer=False, is_writable=True),
+        ]
+    else:
+        keys += [placeholder, placeholder]
+    # No refund-root authority; the settler collects the settle reward.
+    keys.append(placeholder)
+    keys.append(AccountMeta(settler, is_signer=True, is_writable=True) if settler else placeholder)
+    global_stats, _ = pda_global_stats(program_id)
+    keys.append(AccountMeta(global_stats, is_signer=False, is_writable=True))
+    keys.append(AccountMeta(winner_profile, is_signer=False, is_writable=True) if winner_profile else placeholder)
+    keys.append(AccountMeta(jackpot, is_signer=False, is_writable=True) if jackpot else placeholder)
+    keys.append(
+        AccountMeta(protocol_treasury, is_signer=False, is_writable=True) if protocol_treasury else placeholder
+    )
+    keys.append(AccountMeta(treasury, is_signer=False, is_writable=True) if treasury else placeholder)
+    keys += event_cpi_accounts(program_id)
+    keys += [AccountMeta(split, is_signer=False, is_writable=True) for split in split_recipients]
+    return TransactionInstruction(program_id=program_id, data=data, keys=keys)
+
+
+def instruction_publish_price(program_id: PublicKey, payer: PublicKey, day_index: int) -> TransactionInstruction:
+    data = anchor_discriminator("publish_price") + encode_i64(day_index)
+    auction_day, _ = pda_auction_day(program_id, day_index)
+    price_series, _ = pda_price_series(program_id)
+    keys = [
+        AccountMeta(payer, is_signer=True, is_writable=True),
+        AccountMeta(auction_day, is_signer=False, is_writable=False),
+        AccountMeta(price_series, is_signer=False, is_writable=True),
+        AccountMeta(PublicKey("11111111111111111111111111111111"), is_signer=False, is_writable=False),
+    ] + event_cpi_accounts(program_id)
+    return TransactionInstruction(program_id=program_id, data=data, keys=keys)
+
+
+def instruction_record_params(program_id: PublicKey, payer: PublicKey, day_index: int) -> TransactionInstruction:
+    data = anchor_discriminator("record_params") + encode_i64(day_index)
+    auction_day, _ = pda_auction_day(program_id, day_index)
+    param_history, _ = pda_param_history(program_id)
+    keys = [
+        AccountMeta(payer, is_signer=True, is_writable=True),
+        AccountMeta(auction_day, is_signer=False, is_writable=False),
+        AccountMeta(param_history, is_signer=False, is_writable=True),
+        AccountMeta(PublicKey("11111111111111111111111111111111"), is_signer=False, is_writable=False),
+    ] + event_cpi_accounts(program_id)
+    return TransactionInstruction(program_id=program_id, data=data, keys=keys)
+
+
+def instruction_mint_trophy(
+    program_id: PublicKey, payer: PublicKey, day_index: int, winner: PublicKey
+) -> TransactionInstruction:
+    data = anchor_discriminator("mint_trophy") + encode_i64(day_index)
+    auction_day, _ = pda_auction_day(program_id, day_index)
+    trophy_mint, _ = pda_trophy_mint(program_id, auction_day)
+    keys = [
+        AccountMeta(payer, is_signer=True, is_writable=True),
+        AccountMeta(auction_day, is_signer=False, is_writable=False),
+        AccountMeta(winner, is_signer=False, is_writable=False),
+        AccountMeta(trophy_mint, is_signer=False, is_writable=True),
+        AccountMeta(
+            associated_token_address(winner, trophy_mint, TOKEN_2022_PROGRAM_ID),
+            is_signer=False,
+            is_writable=True,
+        ),
+        AccountMeta(TOKEN_2022_PROGRAM_ID, is_signer=False, is_writable=False),
+        AccountMeta(ASSOCIATED_TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        AccountMeta(PublicKey("11111111111111111111111111111111"), is_signer=False, is_writable=False),
+    ] + event_cpi_accounts(program_id)
+    return TransactionInstruction(program_id=program_id, data=data, keys=keys)
+
+
+def instruction_refund_next(
+    program_id: PublicKey,
+    config: PublicKey,
+    auction_day: PublicKey,
+    vault: PublicKey,
+    cranker: PublicKey,
+    day_index: int,
+    first_ordinal: int,
+    bidders: List[PublicKey],
+    cranker_registry: Optional[PublicKey] = None,
+    cranker_bond: Optional[PublicKey] = None,
+) -> TransactionInstruction:
+    # `bidders` are the `BidIndex` entries starting at the day's `refund_cursor`.
+    data = anchor_discriminator("refund_next") + encode_i64(day_index)
+    keys = [
+        AccountMeta(config, is_signer=False, is_writable=False),
+        AccountMeta(auction_day, is_signer=False, is_writable=True),
+        AccountMeta(vault, is_signer=False, is_writable=True),
+        AccountMeta(cranker, is_signer=True, is_writable=True),
+        AccountMeta(PublicKey("11111111111111111111111111111111"), is_signer=False, is_writable=False),
+        # The program ID stands in for the registry in open mode and for the
+        # bond when none is required.
+        AccountMeta(cranker_registry or program_id, is_signer=False, is_writable=False),
+        AccountMeta(cranker_bond or program_id, is_signer=False, is_writable=False),
+    ] + event_cpi_accounts(program_id)
+    for offset, bidder in enumerate(bidders):
+        bid_index, _ = pda_bid_index(program_id, auction_day, first_ordinal + offset)
+        receipt, _ = pda_bid_receipt(program_id, auction_day, bidder)
+        keys.append(AccountMeta(bid_index, is_signer=False, is_writable=False))
+        keys.append(AccountMeta(receipt, is_signer=False, is_writable=True))
+        keys.append(AccountMeta(bidder, is_signer=False, is_writable=True))
     return TransactionInstruction(program_id=program_id, data=data, keys=keys)
 
 
@@ -240,17 +522,48 @@ def instruction_refund_batch(
     cranker: PublicKey,
     day_index: int,
     bidders: List[PublicKey],
+    idempotency_key: Optional[bytes] = None,
+    cranker_registry: Optional[PublicKey] = None,
+    cranker_bond: Optional[PublicKey] = None,
+    receipt_tree: Optional[PublicKey] = None,
 ) -> TransactionInstruction:
-    data = anchor_discriminator("refund_batch") + encode_i64(day_index) + encode_vec_pubkeys(bidders)
+    # Bumps are found off-chain so the program can skip the search per receipt.
+    receipts: List[PublicKey] = []
+    bumps: List[int] = []
+    for bidder in bidders:
+        bid_receipt, bump = pda_bid_receipt(program_id, auction_day, bidder)
+        receipts.append(bid_receipt)
+        bumps.append(bump)
+    data = (
+        anchor_discriminator("refund_batch")
+        + encode_i64(day_index)
+        + encode_vec_u8(bumps)
+        + encode_option_crank_key(idempotency_key)
+    )
     keys = [
         AccountMeta(config, is_signer=False, is_writable=False),
         AccountMeta(auction_day, is_signer=False, is_writable=True),
         AccountMeta(vault, is_signer=False, is_writable=True),
         AccountMeta(cranker, is_signer=True, is_writable=True),
         AccountMeta(PublicKey("11111111111111111111111111111111"), is_signer=False, is_writable=False),
+        # The program ID stands in for the registry in open mode and for the
+        # bond when none is required.
+        AccountMeta(cranker_registry or program_id, is_signer=False, is_writable=False),
+        AccountMeta(cranker_bond or program_id, is_signer=False, is_writable=False),
     ]
-    for bidder in bidders:
-        bid_receipt, _ = pda_bid_receipt(program_id, auction_day, bidder)
+    if receipt_tree is not None:
+        keys += [
+            AccountMeta(BUBBLEGUM_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pda_tree_config(receipt_tree)[0], is_signer=False, is_writable=True),
+            AccountMeta(receipt_tree, is_signer=False, is_writable=True),
+            AccountMeta(pda_cnft_authority(program_id)[0], is_signer=False, is_writable=False),
+            AccountMeta(SPL_NOOP_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, is_signer=False, is_writable=False),
+        ]
+    else:
+        keys += [AccountMeta(program_id, is_signer=False, is_writable=False)] * 6
+    keys += event_cpi_accounts(program_id)
+    for bidder, bid_receipt in zip(bidders, receipts):
         keys.append(AccountMeta(bid_receipt, is_signer=False, is_writable=True))
         keys.append(AccountMeta(bidder, is_signer=False, is_writable=True))
     return TransactionInstruction(program_id=program_id, data=data, keys=keys)
@@ -341,11 +654,21 @@ def fetch_auction_day(
     return decode_auction_day(data)
 
 
+def fetch_day_closed(client: Client, program_id: PublicKey, day_index: int) -> bool:
+    schedule_override, _ = pda_schedule_override(program_id, day_index)
+    data = fetch_account(client, schedule_override)
+    if not data:
+        return False
+    if data[:8] != SCHEDULE_OVERRIDE_DISCRIMINATOR:
+        raise ValueError("Invalid ScheduleOverride discriminator")
+    return bool(SCHEDULE_OVERRIDE_LAYOUT.parse(data[8:]).closed)
+
+
 def fetch_bid_receipts(
     client: Client, program_id: PublicKey, auction_day: PublicKey
 ) -> List[Tuple[PublicKey, BidReceipt]]:
     filters = [
-        81,
+        154,
         MemcmpOpts(offset=8, bytes=str(auction_day)),
     ]
     resp = client.get_program_accounts(
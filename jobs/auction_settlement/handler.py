@@ -18,15 +18,28 @@ from auction_client import (
     SECONDS_PER_DAY,
     AuctionDay,
     BidReceipt,
+    crank_key,
+    fetch_account,
     fetch_auction_day,
     fetch_bid_receipts,
     fetch_config,
+    fetch_day_closed,
     instruction_init_day,
+    instruction_mint_trophy,
+    instruction_publish_price,
+    instruction_record_params,
     instruction_refund_batch,
     instruction_settle_day,
     parse_keypair,
     pda_auction_day,
+    pda_bidder_profile,
     pda_config,
+    pda_cranker_bond,
+    pda_cranker_registry,
+    pda_jackpot,
+    pda_protocol_treasury,
+    pda_treasury,
+    pda_trophy_mint,
     pda_vault,
     send_transaction,
 )
@@ -76,7 +89,9 @@ def is_error(err: Exception, name: str) -> bool:
 
 
 def current_day_index() -> int:
-    return int(time.time()) // SECONDS_PER_DAY
+    # Must match Config.day_offset_seconds on-chain.
+    day_offset_seconds = int(os.environ.get("DAY_OFFSET_SECONDS", "0"))
+    return (int(time.time()) + day_offset_seconds) // SECONDS_PER_DAY
 
 
 def chunked(items: List[PublicKey], size: int) -> List[List[PublicKey]]:
@@ -143,6 +158,54 @@ def maybe_init_day(client: Client, program_id: PublicKey, signer, day_index: int
             log(f"init_day: RPC error for day {day_index}: {err}")
 
 
+def maybe_publish_price(client: Client, program_id: PublicKey, signer, day_index: int) -> None:
+    instruction = instruction_publish_price(program_id, signer.public_key, day_index)
+    try:
+        send_transaction(client, instruction, signer)
+        log(f"publish_price: published day {day_index}")
+    except RPCException as err:
+        code = extract_custom_error_code(err)
+        if code > 0:
+            log(f"publish_price: program error {code} for day {day_index}")
+        else:
+            log(f"publish_price: RPC error for day {day_index}: {err}")
+
+
+def maybe_record_params(client: Client, program_id: PublicKey, signer, day_index: int) -> None:
+    instruction = instruction_record_params(program_id, signer.public_key, day_index)
+    try:
+        send_transaction(client, instruction, signer)
+        log(f"record_params: recorded day {day_index}")
+    except RPCException as err:
+        code = extract_custom_error_code(err)
+        if code > 0:
+            log(f"record_params: program error {code} for day {day_index}")
+        else:
+            log(f"record_params: RPC error for day {day_index}: {err}")
+
+
+def maybe_mint_trophy(client: Client, program_id: PublicKey, signer, day_index: int) -> None:
+    # The trophy is the day's deed: `set_message` follows whoever holds it, so
+    # minting right after settlement lets the winner transfer or sell the slot.
+    auction_day = fetch_auction_day(client, program_id, day_index)
+    if not auction_day or not auction_day.finalized or auction_day.winner == PublicKey(bytes(32)):
+        return
+    auction_day_key, _ = pda_auction_day(program_id, day_index)
+    trophy_mint, _ = pda_trophy_mint(program_id, auction_day_key)
+    if fetch_account(client, trophy_mint):
+        return
+    instruction = instruction_mint_trophy(program_id, signer.public_key, day_index, auction_day.winner)
+    try:
+        send_transaction(client, instruction, signer)
+        log(f"mint_trophy: minted day {day_index}")
+    except RPCException as err:
+        code = extract_custom_error_code(err)
+        if code > 0:
+            log(f"mint_trophy: program error {code} for day {day_index}")
+        else:
+            log(f"mint_trophy: RPC error for day {day_index}: {err}")
+
+
 def settle_with_retry(
     client: Client,
     program_id: PublicKey,
@@ -157,6 +220,24 @@ def settle_with_retry(
     config = fetch_config(client, program_id)
     if not config:
         raise RuntimeError("Config account not found")
+    auction_day = fetch_auction_day(client, program_id, day_index)
+    winner = None
+    if auction_day and auction_day.winner != PublicKey(bytes(32)):
+        winner = auction_day.winner
+    # Passed even when it does not exist: streak bonus days require it.
+    winner_profile = None
+    if winner:
+        winner_profile, _ = pda_bidder_profile(program_id, winner)
+    # `fund_jackpot` creates the jackpot; without it there is nothing to roll or award.
+    jackpot, _ = pda_jackpot(program_id)
+    if not fetch_account(client, jackpot):
+        jackpot = None
+    # Always passed: the rake creates the system-owned PDA on first payment.
+    protocol_treasury, _ = pda_protocol_treasury(program_id)
+    # Required only while treasury custody is on, which needs `init_treasury` first.
+    treasury, _ = pda_treasury(program_id)
+    if not fetch_account(client, treasury):
+        treasury = None
 
     instruction = instruction_settle_day(
         program_id,
@@ -165,6 +246,14 @@ def settle_with_retry(
         vault_key,
         config.recipient_pubkey,
         day_index,
+        crank_key("settle_day", day_index),
+        winner,
+        signer.public_key,
+        winner_profile,
+        jackpot,
+        protocol_treasury,
+        treasury,
+        config.recipient_splits,
     )
 
     start = time.time()
@@ -215,6 +304,8 @@ def refund_losers(
         return
 
     receipts = fetch_bid_receipts(client, program_id, auction_day_key)
+    # Tipped receipts first: their refund tips go to whichever cranker refunds them.
+    receipts.sort(key=lambda item: item[1].refund_tip_lamports, reverse=True)
     losers: List[PublicKey] = []
     for _receipt_key, receipt in receipts:
         if receipt.refunded:
@@ -229,6 +320,14 @@ def refund_losers(
 
     config_key, _ = pda_config(program_id)
     vault_key, _ = pda_vault(program_id, auction_day_key)
+    # Only pass the registry and bond once they exist; permissioned mode and a
+    # minimum bond require them.
+    cranker_registry, _ = pda_cranker_registry(program_id)
+    if not fetch_account(client, cranker_registry):
+        cranker_registry = None
+    cranker_bond, _ = pda_cranker_bond(program_id, signer.public_key)
+    if not fetch_account(client, cranker_bond):
+        cranker_bond = None
     batches = chunked(losers, max_batch_size)
 
     start = time.time()
@@ -246,6 +345,10 @@ def refund_losers(
             signer.public_key,
             day_index,
             batch,
+            crank_key("refund_batch", day_index, *batch),
+            cranker_registry,
+            cranker_bond,
+            auction_day.receipt_tree,
         )
         try:
             send_transaction(client, instruction, signer)
@@ -273,9 +376,25 @@ def handler(event=None, context=None):
     target_day_index = current_day_index() - 1
     log(f"starting settlement for day {target_day_index}")
 
+    closed = fetch_day_closed(client, program_id, target_day_index)
+
     if not fetch_auction_day(client, program_id, target_day_index):
         maybe_init_day(client, program_id, signer, target_day_index)
 
+    if closed:
+        # Closed days take no bids; settling finalizes them as no-bid days.
+        log(f"day {target_day_index} closed by schedule override, finalizing without bids")
+        settle_with_retry(
+            client,
+            program_id,
+            signer,
+            target_day_index,
+            retry_window_seconds,
+            retry_interval_seconds,
+        )
+        maybe_trigger_codex_nuke()
+        return {"status": "closed", "day_index": target_day_index}
+
     settle_with_retry(
         client,
         program_id,
@@ -285,6 +404,10 @@ def handler(event=None, context=None):
         retry_interval_seconds,
     )
 
+    maybe_mint_trophy(client, program_id, signer, target_day_index)
+    maybe_publish_price(client, program_id, signer, target_day_index)
+    maybe_record_params(client, program_id, signer, target_day_index)
+
     refund_losers(
         client,
         program_id,
@@ -1,11 +1,218 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
-use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::system_program::{assign, transfer, Assign, Transfer};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::{self, state::Mint as MintState};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, MintTo, SetAuthority, Token2022, TokenAccount, TokenInterface,
+    TokenMetadataInitialize, TokenMetadataUpdateField, TransferChecked,
+};
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sha256_hasher::hashv;
 
 declare_id!("DtLQpjotSmrKAqk6Sqn16P6dSfKuiXawEyUEgmSmioW6");
 
 const SECONDS_PER_DAY: i64 = 86_400;
 const INIT_DAY_MAX_AHEAD_DAYS: i64 = 2;
+const CRANK_KEY_LEN: usize = 16;
+const CRANK_KEY_HISTORY: usize = 4;
+const BPS_DENOMINATOR: u64 = 10_000;
+const VAULT_VERSION_SYSTEM_OWNED: u8 = 0;
+const VAULT_VERSION_PROGRAM_OWNED: u8 = 1;
+const DEFAULT_DESTRUCTIVE_ACTION_DELAY_SECONDS: i64 = SECONDS_PER_DAY;
+const MIN_DESTRUCTIVE_ACTION_DELAY_SECONDS: i64 = 3_600;
+const DESTRUCTIVE_ACTION_WARNING_INTERVAL_SECONDS: i64 = 3_600;
+const DISPLAY_PAYLOAD_MAX_LEN: usize = 64;
+// Bytes of UTF-8 a `WinnerMessage` holds before it has to grow.
+const WINNER_MESSAGE_MAX_LEN: usize = 280;
+// Ceiling for `Config.winner_message_max_len`; keeps one realloc well under
+// the runtime's 10 KiB per-instruction growth limit.
+const WINNER_MESSAGE_HARD_MAX_LEN: usize = 4_096;
+const WINNER_MESSAGE_URI_MAX_LEN: usize = 200;
+const TOP_BIDDERS_LEN: usize = 3;
+// `DayMeta` field limits, in bytes of UTF-8.
+const DAY_META_TITLE_MAX_LEN: usize = 64;
+const DAY_META_DESCRIPTION_MAX_LEN: usize = 280;
+const DAY_META_IMAGE_URI_MAX_LEN: usize = 200;
+const PRICE_FLOOR_MAGIC: [u8; 8] = *b"AFLOOR01";
+const RANDOMNESS_MAGIC: [u8; 8] = *b"ARAND001";
+const VERIFY_WINNER_DOMAIN: &[u8] = b"daily-auction:verify_winner:";
+const CANDLE_SLOTS: usize = 8;
+const MAX_BUNDLE_DAYS: u8 = 7;
+// Bundles may reach at most this many days past the current one.
+const MAX_BUNDLE_HORIZON_DAYS: i64 = 14;
+// Category 0 is the default: days keep the plain `Config` snapshot.
+const CATEGORY_DEFAULT: u8 = 0;
+const MAX_CATEGORIES: usize = 8;
+const MAX_MODERATION_LEVEL: u8 = 3;
+// `Config.access_mode`: off, reject denylisted wallets, or additionally
+// require an allowlist entry.
+const ACCESS_MODE_OFF: u8 = 0;
+const ACCESS_MODE_DENYLIST: u8 = 1;
+const ACCESS_MODE_ALLOWLIST: u8 = 2;
+// `AccessEntry.status`; a missing entry reads as `ACCESS_NONE`.
+const ACCESS_NONE: u8 = 0;
+const ACCESS_ALLOWED: u8 = 1;
+const ACCESS_DENIED: u8 = 2;
+// `poke` raises `reminder_level` to 1, 2, 3 this many seconds before the
+// claim deadline, and to `CLAIM_REMINDER_EXPIRED` once it has passed.
+const CLAIM_REMINDER_LEADS: [i64; 3] = [12 * 3_600, 4 * 3_600, 3_600];
+const CLAIM_REMINDER_EXPIRED: u8 = 4;
+// Days of clearing-price history kept in the `PriceSeries` ring.
+const PRICE_SERIES_LEN: usize = 90;
+// `refund_batch` skips an undeliverable refund this many times before
+// escrowing it in the receipt as a voucher.
+const REFUND_DELIVERY_MAX_ATTEMPTS: u8 = 3;
+// Entries kept by the append-only `ParamHistory`.
+const PARAM_HISTORY_MAX_ENTRIES: usize = 64;
+// Approved crankers kept by the `CrankerRegistry`.
+const CRANKER_REGISTRY_MAX_ENTRIES: usize = 32;
+// A cranker bond stays slashable this long after unbonding is requested.
+const CRANKER_UNBOND_DELAY_SECONDS: i64 = 7 * SECONDS_PER_DAY;
+// Entries in `Config.recipient_splits`.
+const MAX_RECIPIENT_SPLITS: usize = 4;
+// Burned fees are sent here; the runtime destroys its lamports.
+const INCINERATOR: Pubkey = pubkey!("1nc1nerator11111111111111111111111111111111");
+// Pyth push oracle; owns the fixed-address `PriceUpdateV2` feed accounts.
+const PYTH_PUSH_ORACLE: Pubkey = pubkey!("pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT");
+const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+// Multi-currency days compare bids in micro-USD (10^-6 USD).
+const MICRO_USD_PER_CENT: u64 = 10_000;
+// Entries in `Config.quote_mints`.
+const MAX_QUOTE_MINTS: usize = 4;
+const TROPHY_SYMBOL: &str = "DAILY";
+// Bubblegum `mint_v1` and the programs it needs for participation receipts.
+const BUBBLEGUM_PROGRAM: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+const SPL_NOOP_PROGRAM: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+const SPL_ACCOUNT_COMPRESSION_PROGRAM: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+const BUBBLEGUM_MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+// Metaplex Token Metadata, read by hand for collection-gated bidding.
+const TOKEN_METADATA_PROGRAM: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+const METADATA_KEY_V1: u8 = 4;
+// Marks `BidderProfile` day fields that hold no day.
+const NO_DAY: i64 = i64::MIN;
+// `DayParams.features` bits: which optional mechanics a day ran with.
+const PARAM_FEATURE_SEALED: u64 = 1 << 0;
+const PARAM_FEATURE_PENNY: u64 = 1 << 1;
+const PARAM_FEATURE_CANDLE: u64 = 1 << 2;
+const PARAM_FEATURE_MULTI_WINNER: u64 = 1 << 3;
+const PARAM_FEATURE_SOFT_CLOSE: u64 = 1 << 4;
+const PARAM_FEATURE_PRIORITY_LANE: u64 = 1 << 5;
+// 1 << 6 was the removed masked-winner mode; left unused.
+const PARAM_FEATURE_EARLY_BIRD: u64 = 1 << 7;
+const PARAM_FEATURE_WITHDRAWALS: u64 = 1 << 8;
+const PARAM_FEATURE_MIN_BIDDERS: u64 = 1 << 9;
+const PARAM_FEATURE_REFUND_WINDOW: u64 = 1 << 10;
+const PARAM_FEATURE_PARTICIPATION_CAP: u64 = 1 << 11;
+const PARAM_FEATURE_WALLET_CAP: u64 = 1 << 12;
+const PARAM_FEATURE_BID_COOLDOWN: u64 = 1 << 13;
+// Whole play-money tokens minted per wallet per day by `faucet_drip`.
+#[cfg(feature = "devnet-faucet")]
+const FAUCET_DRIP_TOKENS: u64 = 2_000;
+
+// Semantic version of this build, taken from Cargo.toml.
+const PROGRAM_VERSION: [u16; 3] = [
+    parse_version_part(env!("CARGO_PKG_VERSION_MAJOR")),
+    parse_version_part(env!("CARGO_PKG_VERSION_MINOR")),
+    parse_version_part(env!("CARGO_PKG_VERSION_PATCH")),
+];
+
+// `ProgramInfo.capabilities` bits. Append only; never reuse a bit.
+const CAPABILITY_IDEMPOTENCY_KEYS: u64 = 1 << 0;
+const CAPABILITY_BPS_FEES: u64 = 1 << 1;
+const CAPABILITY_VAULT_MIGRATION: u64 = 1 << 2;
+const CAPABILITY_DESTRUCTIVE_ACTION_DELAY: u64 = 1 << 3;
+const CAPABILITY_DISPLAY_HOOK: u64 = 1 << 4;
+const CAPABILITY_BUY_NOW: u64 = 1 << 5;
+const CAPABILITY_SOFT_CLOSE: u64 = 1 << 6;
+const CAPABILITY_DAY_COMPLETE: u64 = 1 << 7;
+const CAPABILITY_PRIORITY_LANE: u64 = 1 << 8;
+// 1 << 9 was the removed masked-winner mode (`reveal_winner`).
+const CAPABILITY_PRICE_FLOOR: u64 = 1 << 10;
+const CAPABILITY_DAY_OFFSET: u64 = 1 << 11;
+const CAPABILITY_CONTENT_MANAGER: u64 = 1 << 12;
+const CAPABILITY_SCHEDULE_OVERRIDE: u64 = 1 << 13;
+const CAPABILITY_CLOSE_RECEIPTS: u64 = 1 << 14;
+const CAPABILITY_COMPUTE_LOG: u64 = 1 << 15;
+const CAPABILITY_SEALED_BIDS: u64 = 1 << 16;
+const CAPABILITY_BID_NETTING: u64 = 1 << 17;
+const CAPABILITY_SCHEDULED_BIDS: u64 = 1 << 18;
+const CAPABILITY_PENNY_AUCTION: u64 = 1 << 19;
+const CAPABILITY_FOLLOWERS: u64 = 1 << 20;
+const CAPABILITY_CANDLE_AUCTION: u64 = 1 << 21;
+const CAPABILITY_EARLY_BIRD_DISCOUNT: u64 = 1 << 22;
+const CAPABILITY_EXPOSURE_CAP: u64 = 1 << 23;
+const CAPABILITY_MULTI_WINNER: u64 = 1 << 24;
+const CAPABILITY_CONTENT_BOND: u64 = 1 << 25;
+const CAPABILITY_REFUND_TIP: u64 = 1 << 26;
+const CAPABILITY_VERIFY_WINNER: u64 = 1 << 27;
+const CAPABILITY_WITHDRAW_BID: u64 = 1 << 28;
+const CAPABILITY_PAUSE_SWITCHES: u64 = 1 << 29;
+const CAPABILITY_PROMO_BIDS: u64 = 1 << 30;
+const CAPABILITY_BUNDLE_BIDS: u64 = 1 << 31;
+const CAPABILITY_DAY_CATEGORIES: u64 = 1 << 32;
+const CAPABILITY_CLAIM_REMINDERS: u64 = 1 << 33;
+const CAPABILITY_MIN_BIDDERS: u64 = 1 << 34;
+const CAPABILITY_CANCEL_DAY: u64 = 1 << 35;
+const CAPABILITY_PRICE_SERIES: u64 = 1 << 36;
+const CAPABILITY_DEVNET_FAUCET: u64 = 1 << 37;
+const CAPABILITY_CLAIM_REFUND: u64 = 1 << 38;
+const CAPABILITY_SWEEP_UNCLAIMED: u64 = 1 << 39;
+const CAPABILITY_REFUND_VOUCHERS: u64 = 1 << 40;
+const CAPABILITY_PARTICIPATION_CAP: u64 = 1 << 41;
+const CAPABILITY_CLOSE_BID_RECEIPT: u64 = 1 << 42;
+const CAPABILITY_COUNTER_RAISE: u64 = 1 << 43;
+const CAPABILITY_CLOSE_DAY: u64 = 1 << 44;
+const CAPABILITY_PARAM_HISTORY: u64 = 1 << 45;
+const CAPABILITY_BID_INDEX: u64 = 1 << 46;
+const CAPABILITY_REFUND_CURSOR: u64 = 1 << 47;
+const CAPABILITY_REFUND_PROOFS: u64 = 1 << 48;
+const CAPABILITY_SETTLE_REWARD: u64 = 1 << 49;
+const CAPABILITY_CRANKER_REGISTRY: u64 = 1 << 50;
+const CAPABILITY_CRANKER_FEE_SHARE: u64 = 1 << 51;
+const CAPABILITY_CRANKER_BONDS: u64 = 1 << 52;
+const CAPABILITY_VAULT_SURPLUS: u64 = 1 << 53;
+const CAPABILITY_VAULT_RENT: u64 = 1 << 54;
+const CAPABILITY_STREAK_BONUS: u64 = 1 << 55;
+const CAPABILITY_REFERRALS: u64 = 1 << 56;
+const CAPABILITY_JACKPOT: u64 = 1 << 57;
+const CAPABILITY_RECIPIENT_SPLITS: u64 = 1 << 58;
+const CAPABILITY_FEE_BURN: u64 = 1 << 59;
+const CAPABILITY_PROTOCOL_RAKE: u64 = 1 << 60;
+const CAPABILITY_TREASURY: u64 = 1 << 61;
+const CAPABILITY_VESTING: u64 = 1 << 62;
+const CAPABILITY_TOKEN_BIDDING: u64 = 1 << 63;
+// `ProgramInfo.capabilities_ext` bits: capability `64 + n` is bit `n` here.
+const CAPABILITY_EXT_TOKEN_2022: u64 = 1 << 0;
+const CAPABILITY_EXT_WSOL_BIDS: u64 = 1 << 1;
+const CAPABILITY_EXT_USD_MINIMUMS: u64 = 1 << 2;
+const CAPABILITY_EXT_MULTI_CURRENCY: u64 = 1 << 3;
+const CAPABILITY_EXT_PRIZE_ESCROW: u64 = 1 << 4;
+const CAPABILITY_EXT_TROPHY_NFT: u64 = 1 << 5;
+const CAPABILITY_EXT_CNFT_RECEIPTS: u64 = 1 << 6;
+const CAPABILITY_EXT_WINNER_MESSAGE: u64 = 1 << 7;
+const CAPABILITY_EXT_MESSAGE_MODERATION: u64 = 1 << 8;
+const CAPABILITY_EXT_MESSAGE_URI: u64 = 1 << 9;
+const CAPABILITY_EXT_DAY_DEED: u64 = 1 << 10;
+const CAPABILITY_EXT_DAY_META: u64 = 1 << 11;
+const CAPABILITY_EXT_PRIZE_RECLAIM: u64 = 1 << 12;
+const CAPABILITY_EXT_BID_GATE: u64 = 1 << 13;
+const CAPABILITY_EXT_ACCESS_LIST: u64 = 1 << 14;
+const CAPABILITY_EXT_WALLET_BID_CAP: u64 = 1 << 15;
+const CAPABILITY_EXT_BID_COOLDOWN: u64 = 1 << 16;
+const CAPABILITY_EXT_REFERRAL_CLAIM_WINDOW: u64 = 1 << 17;
+
+// `Config.paused_instructions` kill switches (append-only bit positions).
+const PAUSE_BIDDING: u64 = 1 << 0;
+const PAUSE_SETTLEMENT: u64 = 1 << 1;
+const PAUSE_REFUNDS: u64 = 1 << 2;
+const PAUSE_CLAIMS: u64 = 1 << 3;
+const PAUSE_ALL: u64 = PAUSE_BIDDING | PAUSE_SETTLEMENT | PAUSE_REFUNDS | PAUSE_CLAIMS;
 
 #[program]
 pub mod auction {
@@ -22,11 +229,452 @@ pub mod auction {
         config.loser_fee_lamports = loser_fee_lamports;
         config.min_increment_lamports = min_increment_lamports;
         config.bump = ctx.bumps.config;
+        config.authority = ctx.accounts.payer.key();
+        config.min_increment_bps = 0;
+        config.loser_fee_bps = 0;
+        config.loser_fee_cap_lamports = 0;
+        config.destructive_action_delay_seconds = DEFAULT_DESTRUCTIVE_ACTION_DELAY_SECONDS;
+        config.min_opening_bid_lamports = min_increment_lamports;
+        config.bps_rounding = RoundingPolicy::Floor;
+        config.soft_close_window_seconds = 0;
+        config.soft_close_extension_seconds = 0;
+        config.priority_lane_seconds = 0;
+        config.floor_adapter_program = Pubkey::default();
+        config.floor_adapter_account = Pubkey::default();
+        config.floor_max_staleness_seconds = 0;
+        config.day_offset_seconds = 0;
+        config.receipt_rent_policy = ReceiptRentPolicy::Owner;
+        config.sealed_reveal_seconds = 0;
+        config.penny_tick_lamports = 0;
+        config.penny_bid_fee_lamports = 0;
+        config.penny_timer_seconds = 0;
+        config.candle_window_seconds = 0;
+        config.randomness_program = Pubkey::default();
+        config.randomness_account = Pubkey::default();
+        config.early_bird_window_seconds = 0;
+        config.early_bird_discount_bps = 0;
+        config.max_vault_exposure_lamports = 0;
+        config.open_exposure_lamports = 0;
+        config.winner_slots = 1;
+        config.content_bond_lamports = 0;
+        config.withdraw_cutoff_seconds = 0;
+        config.withdraw_penalty_bps = 0;
+        config.paused_instructions = 0;
+        config.promo_bid_max_lamports = 0;
+        config.min_bidders = 0;
+        config.refund_window_seconds = 0;
+        config.participation_cap_base_lamports = 0;
+        config.participation_cap_step_lamports = 0;
+        config.participation_veteran_days = 0;
+        config.day_rent_destination = recipient_pubkey;
+        config.settle_reward_lamports = 0;
+        config.permissioned_refunds = false;
+        config.cranker_fee_share_bps = BPS_DENOMINATOR as u16;
+        config.min_cranker_bond_lamports = 0;
+        config.surplus_to_winner = false;
+        config.streak_bonus_threshold = 0;
+        config.streak_bonus_bps = 0;
+        config.referral_fee_bps = 0;
+        config.jackpot_rollover_lamports = 0;
+        config.jackpot_fee_bps = 0;
+        config.recipient_splits = [RecipientSplit::default(); MAX_RECIPIENT_SPLITS];
+        config.recipient_split_count = 0;
+        config.burn_bps = 0;
+        config.total_burned_lamports = 0;
+        config.protocol_rake_bps = 0;
+        config.treasury_custody = false;
+        config.vesting_days = 0;
+        config.bid_mint = Pubkey::default();
+        config.usd_price_feed = Pubkey::default();
+        config.usd_max_staleness_seconds = 0;
+        config.min_increment_usd_cents = 0;
+        config.min_opening_bid_usd_cents = 0;
+        config.quote_mints = [QuoteMint::default(); MAX_QUOTE_MINTS];
+        config.quote_mint_count = 0;
+        config.receipt_tree = Pubkey::default();
+        config.winner_message_max_len = WINNER_MESSAGE_MAX_LEN as u16;
+        config.prize_claim_window_days = 0;
+        config.bid_gate = Pubkey::default();
+        config.bid_gate_collection = false;
+        config.access_mode = ACCESS_MODE_OFF;
+        config.max_bid_per_wallet_lamports = 0;
+        config.bid_cooldown_seconds = 0;
+        config.referral_claim_window_days = 0;
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        if let Some(recipient_pubkey) = update.recipient_pubkey {
+            config.recipient_pubkey = recipient_pubkey;
+        }
+        if let Some(loser_fee_lamports) = update.loser_fee_lamports {
+            config.loser_fee_lamports = loser_fee_lamports;
+        }
+        if let Some(min_increment_lamports) = update.min_increment_lamports {
+            config.min_increment_lamports = min_increment_lamports;
+        }
+        if let Some(min_increment_bps) = update.min_increment_bps {
+            require!(
+                u64::from(min_increment_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.min_increment_bps = min_increment_bps;
+        }
+        if let Some(loser_fee_bps) = update.loser_fee_bps {
+            require!(
+                u64::from(loser_fee_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.loser_fee_bps = loser_fee_bps;
+        }
+        if let Some(loser_fee_cap_lamports) = update.loser_fee_cap_lamports {
+            config.loser_fee_cap_lamports = loser_fee_cap_lamports;
+        }
+        if let Some(delay) = update.destructive_action_delay_seconds {
+            require!(
+                delay >= MIN_DESTRUCTIVE_ACTION_DELAY_SECONDS,
+                ErrorCode::DestructiveActionDelayTooShort
+            );
+            config.destructive_action_delay_seconds = delay;
+        }
+        if let Some(min_opening_bid_lamports) = update.min_opening_bid_lamports {
+            config.min_opening_bid_lamports = min_opening_bid_lamports;
+        }
+        if let Some(bps_rounding) = update.bps_rounding {
+            config.bps_rounding = bps_rounding;
+        }
+        if let Some(window) = update.soft_close_window_seconds {
+            require!(
+                (0..SECONDS_PER_DAY).contains(&window),
+                ErrorCode::InvalidSoftClose
+            );
+            config.soft_close_window_seconds = window;
+        }
+        if let Some(extension) = update.soft_close_extension_seconds {
+            require!(
+                (0..SECONDS_PER_DAY).contains(&extension),
+                ErrorCode::InvalidSoftClose
+            );
+            config.soft_close_extension_seconds = extension;
+        }
+        if let Some(priority_lane_seconds) = update.priority_lane_seconds {
+            require!(
+                (0..SECONDS_PER_DAY).contains(&priority_lane_seconds),
+                ErrorCode::InvalidPriorityLane
+            );
+            config.priority_lane_seconds = priority_lane_seconds;
+        }
+        if let Some(floor_adapter_program) = update.floor_adapter_program {
+            config.floor_adapter_program = floor_adapter_program;
+        }
+        if let Some(floor_adapter_account) = update.floor_adapter_account {
+            config.floor_adapter_account = floor_adapter_account;
+        }
+        if let Some(max_staleness) = update.floor_max_staleness_seconds {
+            require!(max_staleness >= 0, ErrorCode::InvalidPriceFloorAdapter);
+            config.floor_max_staleness_seconds = max_staleness;
+        }
+        if let Some(day_offset_seconds) = update.day_offset_seconds {
+            require!(
+                day_offset_seconds.abs() < SECONDS_PER_DAY,
+                ErrorCode::InvalidDayOffset
+            );
+            config.day_offset_seconds = day_offset_seconds;
+        }
+        if let Some(receipt_rent_policy) = update.receipt_rent_policy {
+            config.receipt_rent_policy = receipt_rent_policy;
+        }
+        if let Some(sealed_reveal_seconds) = update.sealed_reveal_seconds {
+            require!(
+                (0..=SECONDS_PER_DAY).contains(&sealed_reveal_seconds),
+                ErrorCode::InvalidSealedRevealWindow
+            );
+            config.sealed_reveal_seconds = sealed_reveal_seconds;
+        }
+        if let Some(penny_tick_lamports) = update.penny_tick_lamports {
+            config.penny_tick_lamports = penny_tick_lamports;
+        }
+        if let Some(penny_bid_fee_lamports) = update.penny_bid_fee_lamports {
+            config.penny_bid_fee_lamports = penny_bid_fee_lamports;
+        }
+        if let Some(penny_timer_seconds) = update.penny_timer_seconds {
+            config.penny_timer_seconds = penny_timer_seconds;
+        }
+        require!(
+            config.penny_tick_lamports == 0 || config.penny_timer_seconds > 0,
+            ErrorCode::InvalidPennyAuction
+        );
+        if let Some(candle_window_seconds) = update.candle_window_seconds {
+            require!(
+                (0..=SECONDS_PER_DAY).contains(&candle_window_seconds),
+                ErrorCode::InvalidCandleWindow
+            );
+            config.candle_window_seconds = candle_window_seconds;
+        }
+        if let Some(randomness_program) = update.randomness_program {
+            config.randomness_program = randomness_program;
+        }
+        if let Some(randomness_account) = update.randomness_account {
+            config.randomness_account = randomness_account;
+        }
+        if let Some(early_bird_window_seconds) = update.early_bird_window_seconds {
+            require!(
+                (0..=SECONDS_PER_DAY).contains(&early_bird_window_seconds),
+                ErrorCode::InvalidEarlyBirdWindow
+            );
+            config.early_bird_window_seconds = early_bird_window_seconds;
+        }
+        if let Some(early_bird_discount_bps) = update.early_bird_discount_bps {
+            require!(
+                u64::from(early_bird_discount_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.early_bird_discount_bps = early_bird_discount_bps;
+        }
+        if let Some(max_vault_exposure_lamports) = update.max_vault_exposure_lamports {
+            config.max_vault_exposure_lamports = max_vault_exposure_lamports;
+        }
+        if let Some(winner_slots) = update.winner_slots {
+            require!(
+                (1..=TOP_BIDDERS_LEN as u8).contains(&winner_slots),
+                ErrorCode::InvalidWinnerSlots
+            );
+            config.winner_slots = winner_slots;
+        }
+        // A candle cutoff re-prices only the single leader.
+        require!(
+            config.winner_slots <= 1 || config.candle_window_seconds == 0,
+            ErrorCode::InvalidWinnerSlots
+        );
+        if let Some(content_bond_lamports) = update.content_bond_lamports {
+            config.content_bond_lamports = content_bond_lamports;
+        }
+        if let Some(withdraw_cutoff_seconds) = update.withdraw_cutoff_seconds {
+            require!(
+                (0..=SECONDS_PER_DAY).contains(&withdraw_cutoff_seconds),
+                ErrorCode::InvalidWithdrawCutoff
+            );
+            config.withdraw_cutoff_seconds = withdraw_cutoff_seconds;
+        }
+        if let Some(withdraw_penalty_bps) = update.withdraw_penalty_bps {
+            require!(
+                u64::from(withdraw_penalty_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.withdraw_penalty_bps = withdraw_penalty_bps;
+        }
+        if let Some(paused_instructions) = update.paused_instructions {
+            require!(
+                paused_instructions & !PAUSE_ALL == 0,
+                ErrorCode::InvalidPauseSwitches
+            );
+            config.paused_instructions = paused_instructions;
+            emit_cpi!(PauseSwitchesUpdated {
+                paused_instructions,
+            });
+        }
+        if let Some(promo_bid_max_lamports) = update.promo_bid_max_lamports {
+            config.promo_bid_max_lamports = promo_bid_max_lamports;
+        }
+        if let Some(min_bidders) = update.min_bidders {
+            config.min_bidders = min_bidders;
+        }
+        if let Some(refund_window_seconds) = update.refund_window_seconds {
+            require!(refund_window_seconds >= 0, ErrorCode::InvalidRefundWindow);
+            config.refund_window_seconds = refund_window_seconds;
+        }
+        if let Some(participation_cap_base_lamports) = update.participation_cap_base_lamports {
+            config.participation_cap_base_lamports = participation_cap_base_lamports;
+        }
+        if let Some(participation_cap_step_lamports) = update.participation_cap_step_lamports {
+            config.participation_cap_step_lamports = participation_cap_step_lamports;
+        }
+        if let Some(participation_veteran_days) = update.participation_veteran_days {
+            config.participation_veteran_days = participation_veteran_days;
+        }
+        if let Some(day_rent_destination) = update.day_rent_destination {
+            config.day_rent_destination = day_rent_destination;
+        }
+        if let Some(settle_reward_lamports) = update.settle_reward_lamports {
+            config.settle_reward_lamports = settle_reward_lamports;
+        }
+        if let Some(permissioned_refunds) = update.permissioned_refunds {
+            config.permissioned_refunds = permissioned_refunds;
+        }
+        if let Some(cranker_fee_share_bps) = update.cranker_fee_share_bps {
+            require!(
+                u64::from(cranker_fee_share_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.cranker_fee_share_bps = cranker_fee_share_bps;
+        }
+        if let Some(min_cranker_bond_lamports) = update.min_cranker_bond_lamports {
+            config.min_cranker_bond_lamports = min_cranker_bond_lamports;
+        }
+        if let Some(surplus_to_winner) = update.surplus_to_winner {
+            config.surplus_to_winner = surplus_to_winner;
+        }
+        if let Some(streak_bonus_threshold) = update.streak_bonus_threshold {
+            config.streak_bonus_threshold = streak_bonus_threshold;
+        }
+        if let Some(streak_bonus_bps) = update.streak_bonus_bps {
+            require!(
+                u64::from(streak_bonus_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.streak_bonus_bps = streak_bonus_bps;
+        }
+        if let Some(referral_fee_bps) = update.referral_fee_bps {
+            require!(
+                u64::from(referral_fee_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.referral_fee_bps = referral_fee_bps;
+        }
+        if let Some(jackpot_rollover_lamports) = update.jackpot_rollover_lamports {
+            config.jackpot_rollover_lamports = jackpot_rollover_lamports;
+        }
+        if let Some(jackpot_fee_bps) = update.jackpot_fee_bps {
+            require!(
+                u64::from(jackpot_fee_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.jackpot_fee_bps = jackpot_fee_bps;
+        }
+        if let Some(recipient_splits) = update.recipient_splits.as_ref() {
+            require!(
+                recipient_splits.len() <= MAX_RECIPIENT_SPLITS,
+                ErrorCode::InvalidRecipientSplits
+            );
+            let mut total_bps: u64 = 0;
+            for split in recipient_splits {
+                require!(
+                    split.bps > 0 && split.recipient != Pubkey::default(),
+                    ErrorCode::InvalidRecipientSplits
+                );
+                total_bps += u64::from(split.bps);
+            }
+            require!(
+                total_bps <= BPS_DENOMINATOR,
+                ErrorCode::InvalidRecipientSplits
+            );
+            config.recipient_splits = [RecipientSplit::default(); MAX_RECIPIENT_SPLITS];
+            config.recipient_splits[..recipient_splits.len()].copy_from_slice(recipient_splits);
+            config.recipient_split_count = recipient_splits.len() as u8;
+        }
+        if let Some(burn_bps) = update.burn_bps {
+            require!(
+                u64::from(burn_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.burn_bps = burn_bps;
+        }
+        if let Some(protocol_rake_bps) = update.protocol_rake_bps {
+            require!(
+                u64::from(protocol_rake_bps) <= BPS_DENOMINATOR,
+                ErrorCode::InvalidBps
+            );
+            config.protocol_rake_bps = protocol_rake_bps;
+        }
+        if let Some(treasury_custody) = update.treasury_custody {
+            config.treasury_custody = treasury_custody;
+        }
+        if let Some(vesting_days) = update.vesting_days {
+            config.vesting_days = vesting_days;
+        }
+        if let Some(bid_mint) = update.bid_mint {
+            config.bid_mint = bid_mint;
+        }
+        if let Some(usd_price_feed) = update.usd_price_feed {
+            config.usd_price_feed = usd_price_feed;
+        }
+        if let Some(max_staleness) = update.usd_max_staleness_seconds {
+            require!(max_staleness >= 0, ErrorCode::InvalidUsdPriceFeed);
+            config.usd_max_staleness_seconds = max_staleness;
+        }
+        if let Some(min_increment_usd_cents) = update.min_increment_usd_cents {
+            config.min_increment_usd_cents = min_increment_usd_cents;
+        }
+        if let Some(min_opening_bid_usd_cents) = update.min_opening_bid_usd_cents {
+            config.min_opening_bid_usd_cents = min_opening_bid_usd_cents;
+        }
+        if let Some(quote_mints) = update.quote_mints.as_ref() {
+            require!(
+                quote_mints.len() <= MAX_QUOTE_MINTS,
+                ErrorCode::InvalidQuoteMints
+            );
+            for (i, quote) in quote_mints.iter().enumerate() {
+                require!(
+                    quote.mint != Pubkey::default()
+                        && quote.price_feed != Pubkey::default()
+                        && quote_mints[..i].iter().all(|other| other.mint != quote.mint),
+                    ErrorCode::InvalidQuoteMints
+                );
+            }
+            config.quote_mints = [QuoteMint::default(); MAX_QUOTE_MINTS];
+            config.quote_mints[..quote_mints.len()].copy_from_slice(quote_mints);
+            config.quote_mint_count = quote_mints.len() as u8;
+        }
+        if let Some(receipt_tree) = update.receipt_tree {
+            config.receipt_tree = receipt_tree;
+        }
+        if let Some(winner_message_max_len) = update.winner_message_max_len {
+            require!(
+                winner_message_max_len as usize <= WINNER_MESSAGE_HARD_MAX_LEN,
+                ErrorCode::WinnerMessageTooLong
+            );
+            config.winner_message_max_len = winner_message_max_len;
+        }
+        if let Some(prize_claim_window_days) = update.prize_claim_window_days {
+            config.prize_claim_window_days = prize_claim_window_days;
+        }
+        if let Some(bid_gate) = update.bid_gate {
+            config.bid_gate = bid_gate;
+        }
+        if let Some(bid_gate_collection) = update.bid_gate_collection {
+            config.bid_gate_collection = bid_gate_collection;
+        }
+        if let Some(access_mode) = update.access_mode {
+            require!(
+                matches!(
+                    access_mode,
+                    ACCESS_MODE_OFF | ACCESS_MODE_DENYLIST | ACCESS_MODE_ALLOWLIST
+                ),
+                ErrorCode::InvalidAccessMode
+            );
+            config.access_mode = access_mode;
+        }
+        if let Some(max_bid_per_wallet_lamports) = update.max_bid_per_wallet_lamports {
+            config.max_bid_per_wallet_lamports = max_bid_per_wallet_lamports;
+        }
+        if let Some(bid_cooldown_seconds) = update.bid_cooldown_seconds {
+            require!(bid_cooldown_seconds >= 0, ErrorCode::InvalidBidCooldown);
+            config.bid_cooldown_seconds = bid_cooldown_seconds;
+        }
+        if let Some(referral_claim_window_days) = update.referral_claim_window_days {
+            config.referral_claim_window_days = referral_claim_window_days;
+        }
+        // A day takes either one bid mint or the quote-mint whitelist.
+        require!(
+            config.quote_mint_count == 0 || config.bid_mint == Pubkey::default(),
+            ErrorCode::InvalidQuoteMints
+        );
+        if let Some(authority) = update.authority {
+            config.authority = authority;
+        }
+
+        emit_cpi!(ConfigUpdated {
+            authority: ctx.accounts.authority.key(),
+            update,
+            updated_at: Clock::get()?.unix_timestamp,
+        });
         Ok(())
     }
 
     pub fn init_day(ctx: Context<InitDay>, day_index: i64) -> Result<()> {
-        let current_day_index = current_day_index(&Clock::get()?);
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        let current_day_index = current_day_index(&Clock::get()?, &ctx.accounts.config);
         require!(
             day_index <= current_day_index.saturating_add(INIT_DAY_MAX_AHEAD_DAYS),
             ErrorCode::DayTooFarAhead
@@ -34,17 +682,20 @@ pub mod auction {
 
         let auction_day = &mut ctx.accounts.auction_day;
         if is_uninitialized_auction_day(auction_day) {
-            auction_day.day_index = day_index;
-            auction_day.finalized = false;
-            auction_day.winner = Pubkey::default();
-            auction_day.highest_bid = 0;
-            auction_day.bidder_count = 0;
-            auction_day.refund_count_total = 0;
-            auction_day.refund_count_completed = 0;
-            auction_day.total_bid_lamports = 0;
-            auction_day.refund_pool_remaining = 0;
-            auction_day.fee_pool_remaining = 0;
-            auction_day.vault_bump = ctx.bumps.vault;
+            initialize_auction_day(
+                auction_day,
+                day_index,
+                ctx.bumps.vault,
+                ctx.accounts.vault.lamports(),
+                &ctx.accounts.config,
+                &events,
+            )?;
+            apply_day_category(
+                auction_day,
+                &ctx.accounts.schedule_override,
+                ctx.accounts.category_table.as_deref(),
+                ctx.program_id,
+            )?;
         }
 
         require!(
@@ -56,67 +707,121 @@ pub mod auction {
     }
 
     pub fn place_bid(ctx: Context<PlaceBid>, day_index: i64, new_amount: u64) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        log_compute("place_bid", "start");
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
         require!(new_amount > 0, ErrorCode::InvalidBidAmount);
 
         let clock = Clock::get()?;
-        let current_day_index = current_day_index(&clock);
-        require!(day_index == current_day_index, ErrorCode::WrongDay);
 
         let auction_day = &mut ctx.accounts.auction_day;
         if is_uninitialized_auction_day(auction_day) {
-            auction_day.day_index = current_day_index;
-            auction_day.finalized = false;
-            auction_day.winner = Pubkey::default();
-            auction_day.highest_bid = 0;
-            auction_day.bidder_count = 0;
-            auction_day.refund_count_total = 0;
-            auction_day.refund_count_completed = 0;
-            auction_day.total_bid_lamports = 0;
-            auction_day.refund_pool_remaining = 0;
-            auction_day.fee_pool_remaining = 0;
-            auction_day.vault_bump = ctx.bumps.vault;
+            require!(
+                day_index == current_day_index(&clock, &ctx.accounts.config),
+                ErrorCode::WrongDay
+            );
+            initialize_auction_day(
+                auction_day,
+                day_index,
+                ctx.bumps.vault,
+                ctx.accounts.vault.lamports(),
+                &ctx.accounts.config,
+                &events,
+            )?;
+            apply_day_category(
+                auction_day,
+                &ctx.accounts.schedule_override,
+                ctx.accounts.category_table.as_deref(),
+                ctx.program_id,
+            )?;
         }
-
         require!(
             ctx.accounts.vault.owner == &system_program::ID,
             ErrorCode::InvalidVaultOwner
         );
+        require!(!auction_day.is_sealed(), ErrorCode::SealedBidDay);
+        require!(
+            !is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?,
+            ErrorCode::DayClosed
+        );
+        require!(auction_day.is_open_at(clock.unix_timestamp), ErrorCode::WrongDay);
+        apply_usd_minimums(
+            auction_day,
+            &ctx.accounts.config,
+            ctx.accounts.usd_price_feed.as_ref(),
+            clock.unix_timestamp,
+        )?;
+        require_bid_gate(
+            auction_day,
+            &ctx.accounts.bidder.key(),
+            ctx.accounts.gate_token_account.as_deref(),
+            ctx.accounts.gate_metadata.as_ref(),
+        )?;
+        require_access(
+            &ctx.accounts.config,
+            ctx.accounts.access_entry.as_ref(),
+            ctx.program_id,
+        )?;
+        log_compute("place_bid", "day_loaded");
 
-        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
-
-        let highest_bid = auction_day.highest_bid;
-        let min_increment = ctx.accounts.config.min_increment_lamports;
-        if highest_bid == 0 {
-            require!(new_amount >= min_increment, ErrorCode::BidTooLow);
-        } else {
-            let required = highest_bid
-                .checked_add(min_increment)
-                .ok_or(ErrorCode::MathOverflow)?;
-            require!(new_amount >= required, ErrorCode::BidTooLow);
+        let bidder_count_before = auction_day.bidder_count;
+        let opens_receipt = ctx.accounts.bid_receipt.bidder == Pubkey::default();
+        let delta = apply_bid(
+            auction_day,
+            &mut ctx.accounts.bid_receipt,
+            Bidder {
+                key: ctx.accounts.bidder.key(),
+                mint: Pubkey::default(),
+                stats: ctx.accounts.bidder_stats.as_deref_mut(),
+                vetted: true,
+            },
+            &ctx.accounts.config,
+            ctx.accounts.price_floor.as_ref(),
+            clock.unix_timestamp,
+            new_amount,
+            &events,
+        )?;
+        // The referrer is fixed by the bid that opens the receipt.
+        if let Some(referrer) = ctx.accounts.referrer.as_ref() {
+            if opens_receipt && referrer.key() != ctx.accounts.bidder.key() {
+                ctx.accounts.bid_receipt.referrer = referrer.key();
+            }
         }
-
-        let bid_receipt = &mut ctx.accounts.bid_receipt;
-        let is_new_receipt = bid_receipt.bidder == Pubkey::default();
-        if is_new_receipt {
-            bid_receipt.auction_day = auction_day.key();
-            bid_receipt.bidder = ctx.accounts.bidder.key();
-            bid_receipt.refunded = false;
-            auction_day.bidder_count = auction_day
-                .bidder_count
-                .checked_add(1)
-                .ok_or(ErrorCode::MathOverflow)?;
+        add_exposure(&mut ctx.accounts.config, auction_day, delta)?;
+        if auction_day.bidder_count > bidder_count_before {
+            record_bid_index(
+                auction_day,
+                ctx.accounts.bid_index.as_deref(),
+                ctx.accounts.bidder.key(),
+                &ctx.accounts.bidder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
         }
+        ctx.accounts.global_stats.record_bid(day_index, new_amount);
+        if let Some(profile) = ctx.accounts.bidder_profile.as_deref_mut() {
+            profile.record_bid(ctx.accounts.bidder.key(), day_index, delta)?;
+        }
+        log_compute("place_bid", "receipt_loaded");
 
-        require!(
-            bid_receipt.bidder == ctx.accounts.bidder.key(),
-            ErrorCode::BidderMismatch
-        );
-
-        let previous_amount = bid_receipt.amount;
-        require!(new_amount > previous_amount, ErrorCode::BidDecrease);
-        let delta = new_amount
-            .checked_sub(previous_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        // A wrapped-SOL account is unwrapped into the bidder's wallet first, so
+        // wallets holding only wSOL fund the bid through the same transfer.
+        if let Some(wsol_account) = ctx.accounts.wsol_account.as_ref() {
+            let token_program = ctx
+                .accounts
+                .token_program
+                .as_ref()
+                .ok_or(ErrorCode::TokenProgramRequired)?;
+            let cpi_ctx = CpiContext::new(
+                token_program.to_account_info(),
+                CloseAccount {
+                    account: wsol_account.to_account_info(),
+                    destination: ctx.accounts.bidder.to_account_info(),
+                    authority: ctx.accounts.bidder.to_account_info(),
+                },
+            );
+            token_interface::close_account(cpi_ctx)?;
+        }
 
         if delta > 0 {
             let cpi_ctx = CpiContext::new(
@@ -129,432 +834,10954 @@ pub mod auction {
             transfer(cpi_ctx, delta)?;
         }
 
-        bid_receipt.amount = new_amount;
-        auction_day.total_bid_lamports = auction_day
-            .total_bid_lamports
-            .checked_add(delta)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        if new_amount > auction_day.highest_bid {
-            auction_day.highest_bid = new_amount;
-            auction_day.winner = ctx.accounts.bidder.key();
-        }
-
+        log_compute("place_bid", "end");
         Ok(())
     }
 
-    pub fn settle_day(ctx: Context<SettleDay>, day_index: i64) -> Result<()> {
-        let current_day_index = current_day_index(&Clock::get()?);
-        let auction_day = &mut ctx.accounts.auction_day;
+    // Rolls a pending loser refund from a finalized day straight into a bid on
+    // the open day: only the difference moves between vaults, and the bidder
+    // collects their own loser fee as the refund cranker.
+    pub fn net_bid(
+        ctx: Context<NetBid>,
+        day_index: i64,
+        new_amount: u64,
+        refund_day_index: i64,
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        // Both a bid and a self-claimed refund.
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING | PAUSE_CLAIMS)?;
+        require!(new_amount > 0, ErrorCode::InvalidBidAmount);
+        let clock = Clock::get()?;
+        let bidder = ctx.accounts.bidder.key();
 
-        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
-        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
-        require!(day_index < current_day_index, ErrorCode::TooEarly);
+        let refund_day = &mut ctx.accounts.refund_auction_day;
+        require!(refund_day.day_index == refund_day_index, ErrorCode::DayMismatch);
+        require!(refund_day.finalized, ErrorCode::NotFinalized);
+        require_vault_owner(refund_day, &ctx.accounts.refund_vault)?;
+        let refund_receipt = &mut ctx.accounts.refund_receipt;
         require!(
-            ctx.accounts.vault.owner == &system_program::ID,
-            ErrorCode::InvalidVaultOwner
+            !refund_receipt.refunded && !refund_day.is_winner(&bidder),
+            ErrorCode::NoRefundToNet
         );
-
-        if auction_day.highest_bid == 0 {
-            auction_day.finalized = true;
-            auction_day.refund_pool_remaining = 0;
-            auction_day.fee_pool_remaining = 0;
-            auction_day.refund_count_total = 0;
-            auction_day.refund_count_completed = 0;
-            return Ok(());
-        }
-
-        let bidder_count = auction_day.bidder_count;
-        require!(bidder_count > 0, ErrorCode::BidderCountMismatch);
-
-        let loser_count = bidder_count
-            .checked_sub(1)
-            .ok_or(ErrorCode::MathOverflow)? as u64;
-        let loser_sum = auction_day
-            .total_bid_lamports
-            .checked_sub(auction_day.highest_bid)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let fee_pool = loser_count
-            .checked_mul(ctx.accounts.config.loser_fee_lamports)
-            .ok_or(ErrorCode::MathOverflow)?;
-        require!(loser_sum >= fee_pool, ErrorCode::FeePoolTooLarge);
-        let refund_pool = loser_sum
-            .checked_sub(fee_pool)
+        let refund_vault_lamports = vault_principal(refund_day, &ctx.accounts.refund_vault);
+        let (refund_amount, loser_fee) =
+            refund_loser_receipt(refund_day, refund_receipt, refund_vault_lamports, &events)?;
+        // Self-claimed: no cranker earned the refund tip.
+        pay_refund_tip(
+            &refund_receipt.to_account_info(),
+            refund_receipt,
+            &ctx.accounts.bidder.to_account_info(),
+        )?;
+        release_counter_raise(
+            &refund_receipt.to_account_info(),
+            refund_receipt,
+            &ctx.accounts.bidder.to_account_info(),
+        )?;
+        let credit = refund_amount
+            .checked_add(loser_fee)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        let vault_lamports = **ctx.accounts.vault.to_account_info().lamports.borrow();
+        let auction_day = &mut ctx.accounts.auction_day;
+        if is_uninitialized_auction_day(auction_day) {
+            require!(
+                day_index == current_day_index(&clock, &ctx.accounts.config),
+                ErrorCode::WrongDay
+            );
+            initialize_auction_day(
+                auction_day,
+                day_index,
+                ctx.bumps.vault,
+                ctx.accounts.vault.lamports(),
+                &ctx.accounts.config,
+                &events,
+            )?;
+            apply_day_category(
+                auction_day,
+                &ctx.accounts.schedule_override,
+                ctx.accounts.category_table.as_deref(),
+                ctx.program_id,
+            )?;
+        }
         require!(
-            vault_lamports >= auction_day.total_bid_lamports,
-            ErrorCode::InsufficientVaultLamports
+            ctx.accounts.vault.owner == &system_program::ID,
+            ErrorCode::InvalidVaultOwner
         );
-
-        let recipient = &ctx.accounts.recipient;
+        require!(!auction_day.is_sealed(), ErrorCode::SealedBidDay);
         require!(
-            recipient.key() == ctx.accounts.config.recipient_pubkey,
-            ErrorCode::RecipientMismatch
+            !is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?,
+            ErrorCode::DayClosed
         );
+        require!(auction_day.is_open_at(clock.unix_timestamp), ErrorCode::WrongDay);
 
-        let auction_day_key = auction_day.key();
-        let seeds: &[&[u8]] = &[b"vault", auction_day_key.as_ref(), &[auction_day.vault_bump]];
-        let signer_seeds: &[&[&[u8]]] = &[seeds];
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault.to_account_info(),
-                to: recipient.to_account_info(),
+        let bidder_count_before = auction_day.bidder_count;
+        let delta = apply_bid(
+            auction_day,
+            &mut ctx.accounts.bid_receipt,
+            Bidder {
+                key: bidder,
+                mint: Pubkey::default(),
+                stats: ctx.accounts.bidder_stats.as_deref_mut(),
+                vetted: false,
             },
-            signer_seeds,
-        );
-        transfer(cpi_ctx, auction_day.highest_bid)?;
+            &ctx.accounts.config,
+            ctx.accounts.price_floor.as_ref(),
+            clock.unix_timestamp,
+            new_amount,
+            &events,
+        )?;
+        add_exposure(&mut ctx.accounts.config, auction_day, delta)?;
+        if auction_day.bidder_count > bidder_count_before {
+            record_bid_index(
+                auction_day,
+                ctx.accounts.bid_index.as_deref(),
+                bidder,
+                &ctx.accounts.bidder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
+        }
 
-        auction_day.refund_pool_remaining = refund_pool;
-        auction_day.fee_pool_remaining = fee_pool;
-        auction_day.finalized = true;
-        auction_day.refund_count_total = bidder_count
-            .checked_sub(1)
-            .ok_or(ErrorCode::MathOverflow)?;
-        auction_day.refund_count_completed = 0;
+        let netted = credit.min(delta);
+        let system_program = ctx.accounts.system_program.to_account_info();
+        transfer_from_vault(
+            &ctx.accounts.refund_auction_day,
+            &ctx.accounts.refund_vault,
+            &ctx.accounts.vault,
+            &system_program,
+            netted,
+        )?;
+        transfer_from_vault(
+            &ctx.accounts.refund_auction_day,
+            &ctx.accounts.refund_vault,
+            &ctx.accounts.bidder,
+            &system_program,
+            credit - netted,
+        )?;
+        let shortfall = delta - netted;
+        if shortfall > 0 {
+            let cpi_ctx = CpiContext::new(
+                system_program,
+                Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            );
+            transfer(cpi_ctx, shortfall)?;
+        }
 
+        emit_cpi!(BidNetted {
+            bidder,
+            refund_day_index,
+            day_index,
+            refund_lamports: credit,
+            bid_delta: delta,
+            netted_lamports: netted,
+        });
         Ok(())
     }
 
-    pub fn refund_batch<'info>(
-        ctx: Context<'_, '_, '_, 'info, RefundBatch<'info>>,
+    // Pre-commits a bid of `amount` to be placed by any cranker during
+    // `[execute_at, execute_at + tolerance_seconds]`. The account escrows
+    // `amount + tip_lamports` on top of its rent.
+    pub fn schedule_bid(
+        ctx: Context<ScheduleBid>,
         day_index: i64,
-        bidders: Vec<Pubkey>,
+        amount: u64,
+        execute_at: i64,
+        tolerance_seconds: i64,
+        tip_lamports: u64,
+        only_if_not_leading: bool,
     ) -> Result<()> {
-        let auction_day = &mut ctx.accounts.auction_day;
-        require!(auction_day.finalized, ErrorCode::NotFinalized);
-        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(amount > 0, ErrorCode::InvalidBidAmount);
+        require!(tolerance_seconds >= 0, ErrorCode::InvalidScheduledBid);
+
+        let scheduled_bid = &mut ctx.accounts.scheduled_bid;
+        scheduled_bid.owner = ctx.accounts.bidder.key();
+        scheduled_bid.day_index = day_index;
+        scheduled_bid.amount = amount;
+        scheduled_bid.execute_at = execute_at;
+        scheduled_bid.tolerance_seconds = tolerance_seconds;
+        scheduled_bid.tip_lamports = tip_lamports;
+        scheduled_bid.only_if_not_leading = only_if_not_leading;
+        scheduled_bid.authorization_epoch =
+            current_authorization_epoch(&ctx.accounts.authorization_epoch);
+        scheduled_bid.bump = ctx.bumps.scheduled_bid;
+
+        let escrow = amount
+            .checked_add(tip_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: scheduled_bid.to_account_info(),
+            },
+        );
+        transfer(cpi_ctx, escrow)?;
+        Ok(())
+    }
+
+    // Permissionless. Places the owner's scheduled bid from escrow, pays the
+    // cranker the tip, and closes the schedule back to the owner. The cranker
+    // pays rent for the owner's receipt if it does not exist yet.
+    pub fn execute_scheduled_bid(ctx: Context<ExecuteScheduledBid>, day_index: i64) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let scheduled_bid = &ctx.accounts.scheduled_bid;
+        let owner = scheduled_bid.owner;
+        require!(
+            now >= scheduled_bid.execute_at
+                && now <= scheduled_bid.execute_at.saturating_add(scheduled_bid.tolerance_seconds),
+            ErrorCode::ScheduledBidOutsideWindow
+        );
+        require!(
+            authorization_epoch_of(&ctx.accounts.authorization_epoch, ctx.program_id)?
+                == scheduled_bid.authorization_epoch,
+            ErrorCode::AuthorizationRevoked
+        );
+
+        let auction_day = &mut ctx.accounts.auction_day;
+        if is_uninitialized_auction_day(auction_day) {
+            require!(
+                day_index == current_day_index(&clock, &ctx.accounts.config),
+                ErrorCode::WrongDay
+            );
+            initialize_auction_day(
+                auction_day,
+                day_index,
+                ctx.bumps.vault,
+                ctx.accounts.vault.lamports(),
+                &ctx.accounts.config,
+                &events,
+            )?;
+            apply_day_category(
+                auction_day,
+                &ctx.accounts.schedule_override,
+                ctx.accounts.category_table.as_deref(),
+                ctx.program_id,
+            )?;
+        }
         require!(
             ctx.accounts.vault.owner == &system_program::ID,
             ErrorCode::InvalidVaultOwner
         );
-        let auction_day_key = auction_day.key();
+        require!(!auction_day.is_sealed(), ErrorCode::SealedBidDay);
+        require!(
+            !is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?,
+            ErrorCode::DayClosed
+        );
+        require!(auction_day.is_open_at(now), ErrorCode::WrongDay);
+        if scheduled_bid.only_if_not_leading {
+            require!(auction_day.winner != owner, ErrorCode::ScheduledBidNotNeeded);
+        }
+
+        let amount = scheduled_bid.amount;
+        let tip_lamports = scheduled_bid.tip_lamports;
+        let bidder_count_before = auction_day.bidder_count;
+        let delta = apply_bid(
+            auction_day,
+            &mut ctx.accounts.bid_receipt,
+            Bidder {
+                key: owner,
+                mint: Pubkey::default(),
+                stats: ctx.accounts.bidder_stats.as_deref_mut(),
+                vetted: false,
+            },
+            &ctx.accounts.config,
+            ctx.accounts.price_floor.as_ref(),
+            now,
+            amount,
+            &events,
+        )?;
+        // Penny bid fees are not covered by the escrow.
+        require!(delta <= amount, ErrorCode::InsufficientDeposit);
+        add_exposure(&mut ctx.accounts.config, auction_day, delta)?;
+        if auction_day.bidder_count > bidder_count_before {
+            record_bid_index(
+                auction_day,
+                ctx.accounts.bid_index.as_deref(),
+                owner,
+                &ctx.accounts.cranker.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
+        }
+
+        let scheduled_bid_info = ctx.accounts.scheduled_bid.to_account_info();
+        scheduled_bid_info.sub_lamports(delta)?;
+        ctx.accounts.vault.add_lamports(delta)?;
+        scheduled_bid_info.sub_lamports(tip_lamports)?;
+        ctx.accounts.cranker.add_lamports(tip_lamports)?;
+
+        emit_cpi!(ScheduledBidExecuted {
+            owner,
+            day_index,
+            amount,
+            tip_lamports,
+            cranker: ctx.accounts.cranker.key(),
+        });
+        Ok(())
+    }
+
+    pub fn cancel_scheduled_bid(_ctx: Context<CancelScheduledBid>, _day_index: i64) -> Result<()> {
+        Ok(())
+    }
 
-        let expected_accounts = bidders.len().checked_mul(2).ok_or(ErrorCode::MathOverflow)?;
+    // Arms a one-shot counter-raise on the bidder's receipt: once outbid, any
+    // cranker may rebid `highest_bid + increment` (up to `max_bid`) for the
+    // tip. The receipt escrows `max_bid - amount + tip_lamports`; re-arming
+    // replaces the previous order.
+    pub fn set_counter_raise(
+        ctx: Context<SetCounterRaise>,
+        day_index: i64,
+        increment: u64,
+        max_bid: u64,
+        tip_lamports: u64,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
         require!(
-            ctx.remaining_accounts.len() == expected_accounts,
-            ErrorCode::InvalidRemainingAccounts
+            !auction_day.is_sealed() && !auction_day.is_penny(),
+            ErrorCode::CounterRaiseUnavailable
+        );
+        require!(auction_day.is_open_at(now), ErrorCode::WrongDay);
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        require!(
+            increment > 0 && bid_receipt.amount > 0 && max_bid > bid_receipt.amount,
+            ErrorCode::InvalidCounterRaise
         );
 
-        for (i, bidder_pubkey) in bidders.iter().enumerate() {
-            let bid_receipt_info = &ctx.remaining_accounts[i * 2];
-            let bidder_info = &ctx.remaining_accounts[i * 2 + 1];
+        let escrow = (max_bid - bid_receipt.amount)
+            .checked_add(tip_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let current = bid_receipt.counter_raise_escrow;
+        if escrow > current {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: bid_receipt.to_account_info(),
+                },
+            );
+            transfer(cpi_ctx, escrow - current)?;
+        } else if escrow < current {
+            bid_receipt.sub_lamports(current - escrow)?;
+            ctx.accounts.bidder.add_lamports(current - escrow)?;
+        }
+        bid_receipt.counter_raise_increment = increment;
+        bid_receipt.counter_raise_max_bid = max_bid;
+        bid_receipt.counter_raise_tip_lamports = tip_lamports;
+        bid_receipt.counter_raise_escrow = escrow;
+        bid_receipt.counter_raise_epoch =
+            current_authorization_epoch(&ctx.accounts.authorization_epoch);
+
+        emit_cpi!(CounterRaiseSet {
+            day_index,
+            bidder: bid_receipt.bidder,
+            increment,
+            max_bid,
+            tip_lamports,
+        });
+        Ok(())
+    }
+
+    // Disarms the counter-raise and returns its escrow. Allowed at any time so
+    // the escrow never gets stuck behind a closed day.
+    pub fn cancel_counter_raise(ctx: Context<CancelCounterRaise>, day_index: i64) -> Result<()> {
+        require!(
+            ctx.accounts.auction_day.day_index == day_index,
+            ErrorCode::DayMismatch
+        );
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        require!(bid_receipt.counter_raise_escrow > 0, ErrorCode::NoCounterRaise);
+        release_counter_raise(
+            &bid_receipt.to_account_info(),
+            bid_receipt,
+            &ctx.accounts.bidder.to_account_info(),
+        )?;
+        Ok(())
+    }
+
+    // Permissionless. Rebids for an outbid owner from the receipt's escrow,
+    // pays the cranker the tip, and returns the rest of the escrow.
+    pub fn execute_counter_raise(ctx: Context<ExecuteCounterRaise>, day_index: i64) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        let now = Clock::get()?.unix_timestamp;
+        let owner = ctx.accounts.owner.key();
+        let bid_receipt = &ctx.accounts.bid_receipt;
+        require!(bid_receipt.counter_raise_escrow > 0, ErrorCode::NoCounterRaise);
+        require!(
+            authorization_epoch_of(&ctx.accounts.authorization_epoch, ctx.program_id)?
+                == bid_receipt.counter_raise_epoch,
+            ErrorCode::AuthorizationRevoked
+        );
+
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            ctx.accounts.vault.owner == &system_program::ID,
+            ErrorCode::InvalidVaultOwner
+        );
+        require!(
+            !is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?,
+            ErrorCode::DayClosed
+        );
+        require!(auction_day.is_open_at(now), ErrorCode::WrongDay);
+        require!(
+            !auction_day.is_winner(&owner),
+            ErrorCode::CounterRaiseNotNeeded
+        );
+
+        let amount = auction_day
+            .highest_bid
+            .checked_add(bid_receipt.counter_raise_increment)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            amount <= bid_receipt.counter_raise_max_bid,
+            ErrorCode::CounterRaiseAboveMax
+        );
+        let tip_lamports = bid_receipt.counter_raise_tip_lamports;
+        let delta = apply_bid(
+            auction_day,
+            &mut ctx.accounts.bid_receipt,
+            Bidder {
+                key: owner,
+                mint: Pubkey::default(),
+                stats: ctx.accounts.bidder_stats.as_deref_mut(),
+                vetted: false,
+            },
+            &ctx.accounts.config,
+            ctx.accounts.price_floor.as_ref(),
+            now,
+            amount,
+            &events,
+        )?;
+        add_exposure(&mut ctx.accounts.config, auction_day, delta)?;
+
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        let bid_receipt_info = bid_receipt.to_account_info();
+        let escrow = bid_receipt.counter_raise_escrow;
+        let spent = delta
+            .checked_add(tip_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(spent <= escrow, ErrorCode::InsufficientDeposit);
+        bid_receipt_info.sub_lamports(delta)?;
+        ctx.accounts.vault.add_lamports(delta)?;
+        bid_receipt_info.sub_lamports(tip_lamports)?;
+        ctx.accounts.cranker.add_lamports(tip_lamports)?;
+        bid_receipt.counter_raise_escrow = escrow - spent;
+        release_counter_raise(&bid_receipt_info, bid_receipt, &ctx.accounts.owner)?;
+
+        emit_cpi!(CounterRaiseExecuted {
+            owner,
+            day_index,
+            amount,
+            tip_lamports,
+            cranker: ctx.accounts.cranker.key(),
+        });
+        Ok(())
+    }
 
+    // Sealed mode, phase one: escrow a deposit (an upper bound on the bid) and
+    // a hidden commitment. Repeat calls top up the deposit and replace the commitment.
+    pub fn commit_bid(
+        ctx: Context<CommitBid>,
+        day_index: i64,
+        commitment: [u8; 32],
+        deposit: u64,
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        let clock = Clock::get()?;
+
+        let auction_day = &mut ctx.accounts.auction_day;
+        if is_uninitialized_auction_day(auction_day) {
             require!(
-                bidder_info.key == bidder_pubkey,
-                ErrorCode::BidderMismatch
+                day_index == current_day_index(&clock, &ctx.accounts.config),
+                ErrorCode::WrongDay
             );
+            initialize_auction_day(
+                auction_day,
+                day_index,
+                ctx.bumps.vault,
+                ctx.accounts.vault.lamports(),
+                &ctx.accounts.config,
+                &events,
+            )?;
+            apply_day_category(
+                auction_day,
+                &ctx.accounts.schedule_override,
+                ctx.accounts.category_table.as_deref(),
+                ctx.program_id,
+            )?;
+        }
+        require!(auction_day.is_sealed(), ErrorCode::NotSealedBidDay);
+        require!(
+            auction_day.bid_mint == Pubkey::default() && !auction_day.is_multi_currency(),
+            ErrorCode::BidCurrencyMismatch
+        );
+        require!(!auction_day.is_gated(), ErrorCode::BidGateNotMet);
+        require!(
+            ctx.accounts.config.access_mode == ACCESS_MODE_OFF,
+            ErrorCode::AccessEntryRequired
+        );
+        require!(
+            auction_day.is_open_at(clock.unix_timestamp),
+            ErrorCode::WrongDay
+        );
+        require!(
+            ctx.accounts.vault.owner == &system_program::ID,
+            ErrorCode::InvalidVaultOwner
+        );
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(
+            !is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?,
+            ErrorCode::DayClosed
+        );
 
-            let (expected_receipt, _bump) = Pubkey::find_program_address(
-                &[
-                    b"bid_receipt",
-                    auction_day.key().as_ref(),
-                    bidder_pubkey.as_ref(),
-                ],
+        let bidder = ctx.accounts.bidder.key();
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        let is_new_receipt = bid_receipt.bidder == Pubkey::default();
+        if is_new_receipt {
+            bid_receipt.auction_day = auction_day.key();
+            bid_receipt.bidder = bidder;
+            bid_receipt.refunded = false;
+            bid_receipt.first_bid_ts = clock.unix_timestamp;
+            auction_day.bidder_count = auction_day
+                .bidder_count
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            record_bid_index(
+                auction_day,
+                ctx.accounts.bid_index.as_deref(),
+                bidder,
+                &ctx.accounts.bidder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
                 ctx.program_id,
+            )?;
+        }
+        require!(bid_receipt.bidder == bidder, ErrorCode::BidderMismatch);
+
+        let previous_amount = bid_receipt.amount;
+        let new_amount = previous_amount
+            .checked_add(deposit)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            new_amount >= auction_day.min_opening_bid_lamports,
+            ErrorCode::BidTooLow
+        );
+
+        let previous_fee = if is_new_receipt {
+            0
+        } else {
+            loser_fee_for(auction_day, previous_amount)?
+        };
+        auction_day.loser_fee_accrued = auction_day
+            .loser_fee_accrued
+            .checked_sub(previous_fee)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_add(loser_fee_for(auction_day, new_amount)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        add_exposure(&mut ctx.accounts.config, auction_day, deposit)?;
+
+        if deposit > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
             );
+            transfer(cpi_ctx, deposit)?;
+        }
+
+        bid_receipt.amount = new_amount;
+        bid_receipt.last_bid_ts = clock.unix_timestamp;
+        auction_day.total_bid_lamports = auction_day
+            .total_bid_lamports
+            .checked_add(deposit)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let sealed_bid = &mut ctx.accounts.sealed_bid;
+        sealed_bid.auction_day = auction_day.key();
+        sealed_bid.bidder = bidder;
+        sealed_bid.commitment = commitment;
+        sealed_bid.revealed = false;
+        sealed_bid.revealed_amount = 0;
+        sealed_bid.bump = ctx.bumps.sealed_bid;
+        Ok(())
+    }
+
+    // Sealed mode, phase two: disclose the amount during the reveal window.
+    // The unused part of the deposit is returned immediately, so afterwards
+    // the receipt holds exactly the bid and settlement/refunds are unchanged.
+    // Unrevealed deposits are refunded like any losing bid.
+    pub fn reveal_bid(
+        ctx: Context<RevealBid>,
+        day_index: i64,
+        amount: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.is_sealed(), ErrorCode::NotSealedBidDay);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(
+            now >= auction_day.end_ts && now < auction_day.settleable_at(),
+            ErrorCode::RevealWindowClosed
+        );
+
+        let bidder = ctx.accounts.bidder.key();
+        let sealed_bid = &mut ctx.accounts.sealed_bid;
+        require!(!sealed_bid.revealed, ErrorCode::BidAlreadyRevealed);
+        require!(
+            sealed_bid_commitment(&auction_day.key(), &bidder, amount, &salt)
+                == sealed_bid.commitment,
+            ErrorCode::SealedBidCommitmentMismatch
+        );
+
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        require!(
+            amount >= auction_day.min_opening_bid_lamports,
+            ErrorCode::BidTooLow
+        );
+        require!(amount <= bid_receipt.amount, ErrorCode::InsufficientDeposit);
+        require_within_participation_cap(
+            auction_day,
+            ctx.accounts.bidder_stats.as_deref(),
+            amount,
+        )?;
+        require_within_wallet_cap(auction_day, amount)?;
+
+        let excess = bid_receipt
+            .amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if excess > 0 {
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                &ctx.accounts.bidder,
+                &ctx.accounts.system_program,
+                excess,
+            )?;
+        }
+
+        auction_day.loser_fee_accrued = auction_day
+            .loser_fee_accrued
+            .checked_sub(loser_fee_for(auction_day, bid_receipt.amount)?)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_add(loser_fee_for(auction_day, amount)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.total_bid_lamports = auction_day
+            .total_bid_lamports
+            .checked_sub(excess)
+            .ok_or(ErrorCode::MathOverflow)?;
+        bid_receipt.amount = amount;
+
+        // Ties go to the earlier reveal.
+        let previous_leader = auction_day.winner;
+        let previous_high = auction_day.highest_bid;
+        auction_day.record_top_bid(bidder, amount);
+        if amount > auction_day.highest_bid {
+            auction_day.highest_bid = amount;
+            auction_day.winner = bidder;
+            auction_day.highest_bid_ts = now;
+        }
+        emit_bid_events(
+            auction_day,
+            bidder,
+            0,
+            amount,
+            (previous_leader, previous_high),
+            now,
+            &events,
+        )?;
+
+        sealed_bid.revealed = true;
+        sealed_bid.revealed_amount = amount;
+        Ok(())
+    }
+
+    pub fn settle_day<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleDay<'info>>,
+        day_index: i64,
+        idempotency_key: Option<[u8; CRANK_KEY_LEN]>,
+        refund_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        log_compute("settle_day", "start");
+        require_not_paused(&ctx.accounts.config, PAUSE_SETTLEMENT)?;
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        if is_crank_replay(auction_day, idempotency_key)? {
+            msg!("settle_day: idempotency key already processed");
+            return Ok(());
+        }
+
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        // Token days settle through `settle_day_token`, multi-currency days
+        // through `settle_day_multi`.
+        require!(
+            auction_day.bid_mint == Pubkey::default() && !auction_day.is_multi_currency(),
+            ErrorCode::BidCurrencyMismatch
+        );
+        // Closed days never take bids; they finalize as no-bid days below.
+        let closed = is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?;
+        // Leaving the jackpot out would skip the rollover and hand its fee
+        // share to the recipient, so it is required while either is set.
+        require!(
+            ctx.accounts.jackpot.is_some()
+                || (ctx.accounts.config.jackpot_fee_bps == 0
+                    && ctx.accounts.config.jackpot_rollover_lamports == 0),
+            ErrorCode::JackpotRequired
+        );
+        // A buy-now day can be settled as soon as bidding closes.
+        require!(
+            now >= auction_day.settleable_at() || auction_day.bidding_closed,
+            ErrorCode::TooEarly
+        );
+        require!(
+            ctx.accounts.vault.owner == &system_program::ID,
+            ErrorCode::InvalidVaultOwner
+        );
+
+        // The root fixes every proof-claimed refund, so only the authority may attest it.
+        if refund_root.is_some() {
             require!(
-                bid_receipt_info.key == &expected_receipt,
-                ErrorCode::BidReceiptMismatch
+                ctx.accounts.authority.is_some(),
+                ErrorCode::RefundRootUnauthorized
             );
+        }
+        auction_day.refund_root = refund_root.unwrap_or_default();
+
+        // Lamports sent straight to the vault PDA belong to no bid; pass them
+        // on instead of stranding them there.
+        let owed = auction_day
+            .total_bid_lamports
+            .checked_add(auction_day.penny_fees_collected)
+            .and_then(|total| total.checked_add(auction_day.withdraw_penalties))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let surplus = ctx
+            .accounts
+            .vault
+            .lamports()
+            .saturating_sub(owed)
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        if surplus > 0 {
+            let to_winner = auction_day.surplus_to_winner
+                && auction_day.highest_bid > 0
+                && auction_day.bidder_count >= auction_day.min_bidders;
+            let destination = if to_winner {
+                ctx.accounts
+                    .winner
+                    .as_ref()
+                    .ok_or(ErrorCode::WinnerAccountRequired)?
+                    .to_account_info()
+            } else {
+                require!(
+                    ctx.accounts.recipient.key() == ctx.accounts.config.recipient_pubkey,
+                    ErrorCode::RecipientMismatch
+                );
+                ctx.accounts.recipient.to_account_info()
+            };
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                &destination,
+                &ctx.accounts.system_program,
+                surplus,
+            )?;
+            emit_cpi!(VaultSurplusSwept {
+                day_index,
+                destination: destination.key(),
+                lamports: surplus,
+            });
+        }
+
+        let display_payload = ctx
+            .accounts
+            .display_hook
+            .as_ref()
+            .map(|hook| hook.payload.clone())
+            .unwrap_or_default();
+
+        release_exposure(&mut ctx.accounts.config, auction_day);
+
+        if auction_day.highest_bid == 0 {
+            auction_day.finalized = true;
+            auction_day.refund_pool_remaining = 0;
+            auction_day.fee_pool_remaining = 0;
+            auction_day.refund_count_total = 0;
+            auction_day.refund_count_completed = 0;
+            if closed {
+                msg!("settle_day: day {} closed by schedule override", day_index);
+            } else if let Some(jackpot) = ctx.accounts.jackpot.as_deref_mut() {
+                let lamports = jackpot.roll_over(ctx.accounts.config.jackpot_rollover_lamports)?;
+                if lamports > 0 {
+                    emit_cpi!(JackpotRolledOver {
+                        day_index,
+                        lamports,
+                        pot_lamports: jackpot.pot_lamports,
+                    });
+                }
+            }
+            emit_cpi!(DaySettled {
+                day_index,
+                winner: auction_day.winner,
+                winning_bid: 0,
+                bidder_count: auction_day.bidder_count,
+                refund_pool: 0,
+                fee_pool: 0,
+                display_payload,
+                settled_at: now,
+                seq: auction_day.seq,
+            });
+            return Ok(());
+        }
+
+        let bidder_count = auction_day.bidder_count;
+        require!(bidder_count > 0, ErrorCode::BidderCountMismatch);
+        if bidder_count < auction_day.min_bidders {
+            let vault_lamports = vault_principal(auction_day, &ctx.accounts.vault);
             require!(
-                bid_receipt_info.owner == ctx.program_id,
-                ErrorCode::BidReceiptOwnerMismatch
+                vault_lamports >= auction_day.total_bid_lamports,
+                ErrorCode::InsufficientVaultLamports
             );
+            cancel_auction_day(auction_day);
+            start_refund_window(auction_day, now)?;
+            if let Some(jackpot) = ctx.accounts.jackpot.as_deref_mut() {
+                let lamports = jackpot.roll_over(ctx.accounts.config.jackpot_rollover_lamports)?;
+                if lamports > 0 {
+                    emit_cpi!(JackpotRolledOver {
+                        day_index,
+                        lamports,
+                        pot_lamports: jackpot.pot_lamports,
+                    });
+                }
+            }
+            emit_cpi!(DayCancelled {
+                day_index,
+                bidder_count,
+                refund_pool: auction_day.refund_pool_remaining,
+            });
+            return Ok(());
+        }
+        require!(
+            !auction_day.is_candle() || auction_day.candle_resolved,
+            ErrorCode::CandleNotResolved
+        );
 
-            let mut bid_receipt = {
-                let mut data_slice: &[u8] = &bid_receipt_info.data.borrow();
-                BidReceipt::try_deserialize(&mut data_slice)?
-            };
+        let (winning_total, winner_count, fee_pool, refund_pool) = settlement_pools(auction_day)?;
+        // Settling is permissionless; a signing settler earns the day's reward
+        // out of the fee pool, so settlement never waits on the nightly job.
+        let settle_reward = if ctx.accounts.settler.is_some() {
+            auction_day.settle_reward_lamports.min(fee_pool)
+        } else {
+            0
+        };
+        let fee_pool = fee_pool - settle_reward;
+
+        log_compute("settle_day", "pools_computed");
+
+        // Penny bid fees and withdrawal penalties are non-refundable and go to
+        // the recipient with the winning bid.
+        let retained_fees = auction_day
+            .penny_fees_collected
+            .checked_add(auction_day.withdraw_penalties)
+            .ok_or(ErrorCode::MathOverflow)?;
+        // A winner hitting a streak threshold gets part of the winning bid back.
+        let streak = record_winner_profile(
+            auction_day,
+            ctx.accounts.winner_profile.as_ref(),
+            ctx.program_id,
+        )?;
+        let streak_bonus = if auction_day.streak_bonus_threshold > 0
+            && streak > 0
+            && streak % auction_day.streak_bonus_threshold == 0
+        {
+            apply_bps(
+                auction_day.highest_bid,
+                auction_day.streak_bonus_bps,
+                RoundingPolicy::Floor,
+            )?
+        } else {
+            0
+        };
+        // A share of the retained fees feeds the jackpot reserve that quiet
+        // days roll into the pot.
+        let jackpot_fee = if ctx.accounts.jackpot.is_some() {
+            apply_bps(
+                retained_fees,
+                ctx.accounts.config.jackpot_fee_bps,
+                RoundingPolicy::Floor,
+            )?
+        } else {
+            0
+        };
+        // The protocol's cut of the winning bid, kept apart from the recipient's.
+        let protocol_rake = apply_bps(
+            winning_total,
+            ctx.accounts.config.protocol_rake_bps,
+            RoundingPolicy::Floor,
+        )?
+        .min(winning_total - streak_bonus);
+        let recipient_amount = winning_total
+            .checked_add(retained_fees)
+            .and_then(|total| total.checked_sub(streak_bonus))
+            .and_then(|total| total.checked_sub(jackpot_fee))
+            .and_then(|total| total.checked_sub(protocol_rake))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let vault_lamports = vault_principal(auction_day, &ctx.accounts.vault);
+        require!(
+            vault_lamports
+                >= auction_day
+                    .total_bid_lamports
+                    .checked_add(retained_fees)
+                    .ok_or(ErrorCode::MathOverflow)?,
+            ErrorCode::InsufficientVaultLamports
+        );
+
+        let recipient = &ctx.accounts.recipient;
+        require!(
+            recipient.key() == ctx.accounts.config.recipient_pubkey,
+            ErrorCode::RecipientMismatch
+        );
+
+        // Split recipients are passed as remaining accounts in table order;
+        // `recipient` keeps the rest, including rounding dust.
+        let split_count = usize::from(ctx.accounts.config.recipient_split_count);
+        require!(
+            ctx.remaining_accounts.len() >= split_count,
+            ErrorCode::RecipientMismatch
+        );
+        let mut split_total: u64 = 0;
+        for (split, split_recipient) in ctx.accounts.config.recipient_splits[..split_count]
+            .iter()
+            .zip(ctx.remaining_accounts)
+        {
+            require!(
+                split_recipient.key() == split.recipient,
+                ErrorCode::RecipientMismatch
+            );
+            let lamports = apply_bps(recipient_amount, split.bps, RoundingPolicy::Floor)?;
+            if lamports == 0 {
+                continue;
+            }
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                split_recipient,
+                &ctx.accounts.system_program,
+                lamports,
+            )?;
+            split_total += lamports;
+            emit_cpi!(RecipientSplitPaid {
+                day_index,
+                recipient: split.recipient,
+                lamports,
+            });
+        }
+        let recipient_share = recipient_amount - split_total;
+        if auction_day.vesting_days > 0 {
+            // The share stays in the vault and `claim_vested` releases it
+            // linearly over the vesting period.
+            auction_day.vesting_total_lamports = recipient_share;
+            auction_day.vesting_claimed_lamports = 0;
+            auction_day.vesting_start_ts = now;
+        } else if ctx.accounts.config.treasury_custody {
+            // Proceeds wait in the `Treasury` for `withdraw_treasury`.
+            let treasury = ctx
+                .accounts
+                .treasury
+                .as_mut()
+                .ok_or(ErrorCode::TreasuryRequired)?;
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                &treasury.to_account_info(),
+                &ctx.accounts.system_program,
+                recipient_share,
+            )?;
+            treasury.total_deposited_lamports = treasury
+                .total_deposited_lamports
+                .checked_add(recipient_share)
+                .ok_or(ErrorCode::MathOverflow)?;
+            emit_cpi!(TreasuryDeposited {
+                day_index,
+                lamports: recipient_share,
+            });
+        } else {
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                recipient,
+                &ctx.accounts.system_program,
+                recipient_share,
+            )?;
+        }
+        if streak_bonus > 0 {
+            let winner = ctx
+                .accounts
+                .winner
+                .as_ref()
+                .ok_or(ErrorCode::WinnerAccountRequired)?;
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                winner,
+                &ctx.accounts.system_program,
+                streak_bonus,
+            )?;
+            emit_cpi!(StreakBonusPaid {
+                day_index,
+                winner: winner.key(),
+                streak,
+                lamports: streak_bonus,
+            });
+        }
+        if protocol_rake > 0 {
+            let protocol_treasury = ctx
+                .accounts
+                .protocol_treasury
+                .as_ref()
+                .ok_or(ErrorCode::ProtocolTreasuryRequired)?;
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                protocol_treasury,
+                &ctx.accounts.system_program,
+                protocol_rake,
+            )?;
+            emit_cpi!(ProtocolRakeTaken {
+                day_index,
+                lamports: protocol_rake,
+            });
+        }
+        if let Some(jackpot) = ctx.accounts.jackpot.as_mut() {
+            if jackpot_fee > 0 {
+                transfer_from_vault(
+                    auction_day,
+                    &ctx.accounts.vault,
+                    &jackpot.to_account_info(),
+                    &ctx.accounts.system_program,
+                    jackpot_fee,
+                )?;
+                jackpot.reserve_lamports = jackpot
+                    .reserve_lamports
+                    .checked_add(jackpot_fee)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+            // The pot built up by quiet days goes to this day's winner.
+            let pot = jackpot.pot_lamports;
+            if pot > 0 {
+                let winner = ctx
+                    .accounts
+                    .winner
+                    .as_ref()
+                    .ok_or(ErrorCode::WinnerAccountRequired)?;
+                jackpot.pot_lamports = 0;
+                jackpot.to_account_info().sub_lamports(pot)?;
+                winner.add_lamports(pot)?;
+                emit_cpi!(JackpotAwarded {
+                    day_index,
+                    winner: winner.key(),
+                    lamports: pot,
+                });
+            }
+        }
+        if let Some(settler) = ctx.accounts.settler.as_ref() {
+            if settle_reward > 0 {
+                transfer_from_vault(
+                    auction_day,
+                    &ctx.accounts.vault,
+                    &settler.to_account_info(),
+                    &ctx.accounts.system_program,
+                    settle_reward,
+                )?;
+                emit_cpi!(SettleRewardPaid {
+                    day_index,
+                    settler: settler.key(),
+                    lamports: settle_reward,
+                });
+            }
+        }
+
+        // Settles the winner's receipt here so `refund_batch` never has to
+        // visit it. The account constraints pin it to `auction_day.winner`.
+        if let Some(winner_receipt) = ctx.accounts.winner_receipt.as_mut() {
+            let winner = ctx
+                .accounts
+                .winner
+                .as_ref()
+                .ok_or(ErrorCode::WinnerAccountRequired)?;
+            let winner_receipt_info = winner_receipt.to_account_info();
+            winner_receipt.refunded = true;
+            pay_refund_tip(&winner_receipt_info, winner_receipt, winner)?;
+            release_counter_raise(&winner_receipt_info, winner_receipt, winner)?;
+        }
+
+        auction_day.refund_pool_remaining = refund_pool;
+        auction_day.fee_pool_remaining = fee_pool;
+        auction_day.finalized = true;
+        auction_day.refund_count_total = bidder_count
+            .checked_sub(winner_count)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.refund_count_completed = 0;
+        // The winner claims the slot by submitting content before it leaves display.
+        auction_day.claim_deadline_ts = content_display_end(auction_day);
+        start_refund_window(auction_day, now)?;
+        // `place_bid` creates the stats account; until then there is nothing to update.
+        let global_stats = &ctx.accounts.global_stats;
+        if global_stats.owner == ctx.program_id && !global_stats.data_is_empty() {
+            let mut stats = GlobalStats::try_deserialize(&mut &global_stats.data.borrow()[..])?;
+            stats.record_settlement(
+                auction_day,
+                winning_total,
+                fee_pool + settle_reward + retained_fees,
+                refund_pool,
+            )?;
+            let mut data = global_stats.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            stats.try_serialize(&mut writer)?;
+        }
+
+        emit_cpi!(DaySettled {
+            day_index,
+            winner: auction_day.winner,
+            winning_bid: auction_day.highest_bid,
+            bidder_count,
+            refund_pool,
+            fee_pool,
+            display_payload,
+            settled_at: now,
+            seq: auction_day.seq,
+        });
+        if auction_day.is_multi_winner() {
+            emit_cpi!(WinnersSettled {
+                day_index,
+                winners: auction_day.top_bidders[..winner_count as usize].to_vec(),
+                winning_bids: auction_day.top_bids[..winner_count as usize].to_vec(),
+                winning_total,
+            });
+        }
+
+        log_compute("settle_day", "end");
+        Ok(())
+    }
+
+    // Permissionless: records a settled day's clearing price in the shared
+    // `PriceSeries`. Republishing the same day is a no-op.
+    pub fn publish_price(ctx: Context<PublishPrice>, day_index: i64) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        let price = clearing_price(auction_day).ok_or(ErrorCode::NoClearingPrice)?;
+
+        let price_series = &mut ctx.accounts.price_series;
+        price_series.bump = ctx.bumps.price_series;
+        let point = &mut price_series.points[price_series_slot(day_index)];
+        if point.price > 0 && point.day_index == day_index {
+            msg!("publish_price: day {} already published", day_index);
+            return Ok(());
+        }
+        // Never let a late publication overwrite a newer day sharing the slot.
+        require!(
+            point.price == 0 || point.day_index < day_index,
+            ErrorCode::StalePricePoint
+        );
+        *point = PricePoint { day_index, price };
+        price_series.latest_day_index = price_series.latest_day_index.max(day_index);
+
+        emit_cpi!(PricePublished { day_index, price });
+        Ok(())
+    }
+
+    // Permissionless: records the parameters an initialized day snapshotted in
+    // the append-only `ParamHistory`. Days must be recorded in order; a day
+    // matching the latest entry only extends its range.
+    pub fn record_params(ctx: Context<RecordParams>, day_index: i64) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        let params = DayParams::of(auction_day);
+
+        let param_history = &mut ctx.accounts.param_history;
+        param_history.bump = ctx.bumps.param_history;
+        if let Some(latest) = param_history.entries.last_mut() {
+            if day_index <= latest.last_day_index {
+                msg!("record_params: day {} already covered", day_index);
+                return Ok(());
+            }
+            if latest.params == params {
+                latest.last_day_index = day_index;
+                return Ok(());
+            }
+        }
+        require!(
+            param_history.entries.len() < PARAM_HISTORY_MAX_ENTRIES,
+            ErrorCode::ParamHistoryFull
+        );
+        param_history.entries.push(ParamSet {
+            first_day_index: day_index,
+            last_day_index: day_index,
+            params,
+        });
+
+        emit_cpi!(ParamsActivated {
+            entry_index: (param_history.entries.len() - 1) as u32,
+            first_day_index: day_index,
+            params,
+        });
+        Ok(())
+    }
+
+    // Once the day's refund window has passed, anyone may move whatever the
+    // refunds left behind to the recipient. Unrefunded receipts forfeit their
+    // claim and the day takes no further refunds. A day whose refunds are all
+    // done may be swept early to collect its treasury fees.
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_SETTLEMENT)?;
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(!auction_day.swept, ErrorCode::RefundWindowClosed);
+        require!(
+            (auction_day.refund_deadline_ts > 0 && now >= auction_day.refund_deadline_ts)
+                || auction_day.refund_count_completed == auction_day.refund_count_total,
+            ErrorCode::RefundWindowOpen
+        );
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        require!(
+            ctx.accounts.recipient.key() == ctx.accounts.config.recipient_pubkey,
+            ErrorCode::RecipientMismatch
+        );
+
+        let mut unclaimed = auction_day
+            .refund_pool_remaining
+            .checked_add(auction_day.fee_pool_remaining)
+            .and_then(|total| total.checked_add(auction_day.treasury_fees_accrued))
+            .ok_or(ErrorCode::MathOverflow)?;
+        // Cancelled days kept their retained fees in the vault; they go too.
+        if auction_day.cancelled {
+            unclaimed = unclaimed
+                .checked_add(auction_day.penny_fees_collected)
+                .and_then(|total| total.checked_add(auction_day.withdraw_penalties))
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        // Referral fees stay behind for `claim_referral_fee` until their own
+        // deadline; `sweep_referral_fees` collects them after that.
+        let unclaimed = unclaimed.min(
+            vault_principal(auction_day, &ctx.accounts.vault)
+                .saturating_sub(auction_day.referral_fees_accrued),
+        );
+
+        // `burn_bps` of the treasury's loser fees is burned instead of swept.
+        let burned = apply_bps(
+            auction_day.treasury_fees_accrued,
+            ctx.accounts.config.burn_bps,
+            RoundingPolicy::Floor,
+        )?
+        .min(unclaimed);
+        if burned > 0 {
+            let incinerator = ctx
+                .accounts
+                .incinerator
+                .as_ref()
+                .ok_or(ErrorCode::IncineratorRequired)?;
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                incinerator,
+                &ctx.accounts.system_program,
+                burned,
+            )?;
+            let config = &mut ctx.accounts.config;
+            config.total_burned_lamports = config
+                .total_burned_lamports
+                .checked_add(burned)
+                .ok_or(ErrorCode::MathOverflow)?;
+            emit_cpi!(FeesBurned {
+                day_index,
+                lamports: burned,
+                total_burned_lamports: config.total_burned_lamports,
+            });
+        }
+        let unclaimed = unclaimed - burned;
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &ctx.accounts.recipient,
+            &ctx.accounts.system_program,
+            unclaimed,
+        )?;
+        let treasury_fees = auction_day.treasury_fees_accrued;
+        auction_day.refund_pool_remaining = 0;
+        auction_day.fee_pool_remaining = 0;
+        auction_day.treasury_fees_accrued = 0;
+        auction_day.swept = true;
+
+        emit_cpi!(UnclaimedSwept {
+            day_index,
+            lamports: unclaimed,
+            treasury_fees,
+            refunds_forfeited: auction_day
+                .refund_count_total
+                .saturating_sub(auction_day.refund_count_completed),
+        });
+        Ok(())
+    }
+
+    // Permissionless. Once every refund is done and every receipt and promo
+    // bid is closed, the day's accounts are dead weight: close the AuctionDay
+    // and the (rent-only) vault to `config.day_rent_destination`.
+    pub fn close_day(ctx: Context<CloseDay>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_SETTLEMENT)?;
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            auction_day.swept
+                || auction_day.refund_count_completed == auction_day.refund_count_total,
+            ErrorCode::RefundsIncomplete
+        );
+        require!(
+            auction_day.receipts_closed >= auction_day.bidder_count
+                && auction_day.promo_bids_reclaimed >= auction_day.promo_bid_count,
+            ErrorCode::ReceiptsOutstanding
+        );
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let vault_lamports = vault_info.lamports();
+        require!(
+            vault_lamports <= Rent::get()?.minimum_balance(vault_info.data_len()),
+            ErrorCode::VaultNotEmpty
+        );
+
+        let rent_destination = ctx.accounts.rent_destination.to_account_info();
+        if auction_day.vault_version == VAULT_VERSION_PROGRAM_OWNED {
+            close_program_account(&vault_info, &rent_destination)?;
+        } else {
+            // The day is done, so the rent reserve goes too.
+            move_vault_lamports(
+                auction_day,
+                &vault_info,
+                &rent_destination,
+                &ctx.accounts.system_program,
+                vault_lamports,
+            )?;
+        }
+
+        emit_cpi!(DayAccountsClosed {
+            day_index,
+            rent_destination: rent_destination.key(),
+            lamports: vault_lamports.saturating_add(auction_day.to_account_info().lamports()),
+        });
+        Ok(())
+    }
+
+    // Remaining accounts are `(bid_receipt, bidder)` pairs; the bidder account
+    // itself names whose receipt it is. `receipt_bumps[i]` is the bump of pair
+    // `i`'s receipt PDA, so each receipt is checked with
+    // `create_program_address` instead of a bump search.
+    pub fn refund_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefundBatch<'info>>,
+        day_index: i64,
+        receipt_bumps: Vec<u8>,
+        idempotency_key: Option<[u8; CRANK_KEY_LEN]>,
+    ) -> Result<()> {
+        log_compute("refund_batch", "start");
+        require_not_paused(&ctx.accounts.config, PAUSE_REFUNDS)?;
+        require_registered_cranker(
+            &ctx.accounts.config,
+            ctx.accounts.cranker_registry.as_deref(),
+            &ctx.accounts.cranker.key(),
+        )?;
+        require_cranker_bond(&ctx.accounts.config, ctx.accounts.cranker_bond.as_deref())?;
+        let auction_day = &mut ctx.accounts.auction_day;
+        if is_crank_replay(auction_day, idempotency_key)? {
+            msg!("refund_batch: idempotency key already processed");
+            return Ok(());
+        }
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2),
+            ErrorCode::InvalidRemainingAccounts
+        );
+        require!(
+            receipt_bumps.len() == ctx.remaining_accounts.len() / 2,
+            ErrorCode::InvalidReceiptBumps
+        );
+
+        let receipt_minter = if auction_day.receipt_tree == Pubkey::default() {
+            None
+        } else {
+            let (
+                Some(bubblegum_program),
+                Some(tree_config),
+                Some(merkle_tree),
+                Some(cnft_authority),
+                Some(log_wrapper),
+                Some(compression_program),
+            ) = (
+                ctx.accounts.bubblegum_program.as_ref(),
+                ctx.accounts.tree_config.as_ref(),
+                ctx.accounts.merkle_tree.as_ref(),
+                ctx.accounts.cnft_authority.as_ref(),
+                ctx.accounts.log_wrapper.as_ref(),
+                ctx.accounts.compression_program.as_ref(),
+            )
+            else {
+                return err!(ErrorCode::ReceiptTreeAccountsRequired);
+            };
+            Some(ReceiptMinter {
+                bubblegum_program: bubblegum_program.to_account_info(),
+                tree_config: tree_config.to_account_info(),
+                merkle_tree: merkle_tree.to_account_info(),
+                authority: cnft_authority.to_account_info(),
+                authority_bump: ctx.bumps.cnft_authority.unwrap_or_default(),
+                log_wrapper: log_wrapper.to_account_info(),
+                compression_program: compression_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            })
+        };
+        let refund_ctx = RefundCtx {
+            instruction: "refund_batch",
+            day_index,
+            vault: ctx.accounts.vault.to_account_info(),
+            cranker: ctx.accounts.cranker.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: Rent::get()?,
+            events: EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        };
+        let completed_at_start = auction_day.refund_count_completed;
+        for (pair, receipt_bump) in ctx.remaining_accounts.chunks(2).zip(receipt_bumps) {
+            let (bid_receipt_info, bidder_info) = (&pair[0], &pair[1]);
+            let completed_before = auction_day.refund_count_completed;
+            refund_receipt(
+                &refund_ctx,
+                auction_day,
+                bidder_info.key,
+                Some(receipt_bump),
+                bid_receipt_info,
+                bidder_info,
+                ctx.program_id,
+            )?;
+            // Only a loser refund paid in this call earns the memento.
+            if let Some(minter) = receipt_minter.as_ref() {
+                if auction_day.refund_count_completed > completed_before {
+                    minter.mint(bidder_info, day_index)?;
+                }
+            }
+        }
+        record_refund_cranker(
+            &ctx.accounts.config,
+            auction_day,
+            &ctx.accounts.cranker.key(),
+            completed_at_start,
+        );
+
+        log_compute("refund_batch", "end");
+        Ok(())
+    }
+
+    // Permissionless cursor crank over the day's `BidIndex` list. Remaining
+    // accounts are `(bid_index, bid_receipt, bidder)` triples for the ordinals
+    // starting at `refund_cursor`. Every bid path indexes the receipts it
+    // opens, so the list covers the whole day. The cursor only moves past
+    // settled receipts, so crankers need no coordination and no bidder list
+    // argument.
+    pub fn refund_next<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefundNext<'info>>,
+        day_index: i64,
+    ) -> Result<()> {
+        log_compute("refund_next", "start");
+        require_not_paused(&ctx.accounts.config, PAUSE_REFUNDS)?;
+        require_registered_cranker(
+            &ctx.accounts.config,
+            ctx.accounts.cranker_registry.as_deref(),
+            &ctx.accounts.cranker.key(),
+        )?;
+        require_cranker_bond(&ctx.accounts.config, ctx.accounts.cranker_bond.as_deref())?;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(3),
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let refund_ctx = RefundCtx {
+            instruction: "refund_next",
+            day_index,
+            vault: ctx.accounts.vault.to_account_info(),
+            cranker: ctx.accounts.cranker.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: Rent::get()?,
+            events: EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        };
+        let auction_day_key = auction_day.key();
+        let start = auction_day.refund_cursor;
+        let completed_at_start = auction_day.refund_count_completed;
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            let (bid_index_info, bid_receipt_info, bidder_info) =
+                (&accounts[0], &accounts[1], &accounts[2]);
+            let ordinal = auction_day.refund_cursor;
+            require!(
+                ordinal < auction_day.bid_index_count,
+                ErrorCode::RefundCursorExhausted
+            );
+            let (expected_index, _bump) = Pubkey::find_program_address(
+                &[b"bid_index", auction_day_key.as_ref(), &ordinal.to_le_bytes()],
+                ctx.program_id,
+            );
+            require!(
+                bid_index_info.key == &expected_index && bid_index_info.owner == ctx.program_id,
+                ErrorCode::BidIndexMismatch
+            );
+            let bid_index = {
+                let mut data_slice: &[u8] = &bid_index_info.data.borrow();
+                BidIndex::try_deserialize(&mut data_slice)?
+            };
+
+            // Withdrawn and closed receipts have nothing left to refund.
+            let settled = if bid_receipt_info.owner != ctx.program_id {
+                let (expected_receipt, _bump) = Pubkey::find_program_address(
+                    &[b"bid_receipt", auction_day_key.as_ref(), bid_index.bidder.as_ref()],
+                    ctx.program_id,
+                );
+                require!(
+                    bid_receipt_info.key == &expected_receipt,
+                    ErrorCode::BidReceiptMismatch
+                );
+                true
+            } else {
+                refund_receipt(
+                    &refund_ctx,
+                    auction_day,
+                    &bid_index.bidder,
+                    None,
+                    bid_receipt_info,
+                    bidder_info,
+                    ctx.program_id,
+                )?
+            };
+            if !settled {
+                break;
+            }
+            auction_day.refund_cursor = ordinal + 1;
+        }
+        record_refund_cranker(
+            &ctx.accounts.config,
+            auction_day,
+            &ctx.accounts.cranker.key(),
+            completed_at_start,
+        );
+
+        msg!("refund_next: cursor {} -> {}", start, auction_day.refund_cursor);
+        log_compute("refund_next", "end");
+        Ok(())
+    }
+
+    // Self-service alternative to `refund_batch` for a losing bidder. No cranker
+    // did the work, so the bidder also keeps the loser fee and any refund tip.
+    pub fn claim_refund(ctx: Context<ClaimRefund>, day_index: i64) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_CLAIMS)?;
+        let bidder = ctx.accounts.bidder.key();
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        require!(!bid_receipt.refunded, ErrorCode::ReceiptAlreadyRefunded);
+        require!(
+            !auction_day.is_winner(&bidder),
+            ErrorCode::NoRefundToClaim
+        );
+
+        let vault_lamports = vault_principal(auction_day, &ctx.accounts.vault);
+        let (refund_amount, loser_fee) =
+            refund_loser_receipt(auction_day, bid_receipt, vault_lamports, &events)?;
+        pay_refund_tip(
+            &bid_receipt.to_account_info(),
+            bid_receipt,
+            &ctx.accounts.bidder.to_account_info(),
+        )?;
+        release_counter_raise(
+            &bid_receipt.to_account_info(),
+            bid_receipt,
+            &ctx.accounts.bidder.to_account_info(),
+        )?;
+        let credit = refund_amount
+            .checked_add(loser_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &ctx.accounts.bidder,
+            &ctx.accounts.system_program,
+            credit,
+        )?;
+        msg!("claim_refund: day {} refunded {}", day_index, credit);
+        Ok(())
+    }
+
+    // Permissionless refund against the Merkle root attested at settlement.
+    // The leaf fixes the bidder's refund; the caller earns the loser fee and
+    // any refund tip as the cranker would.
+    pub fn claim_refund_with_proof(
+        ctx: Context<ClaimRefundWithProof>,
+        day_index: i64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_REFUNDS)?;
+        let bidder = ctx.accounts.bidder.key();
+        // The caller earns the loser fee, so third-party callers count as crankers.
+        let third_party = ctx.accounts.caller.key() != bidder;
+        if third_party {
+            require_registered_cranker(
+                &ctx.accounts.config,
+                ctx.accounts.cranker_registry.as_deref(),
+                &ctx.accounts.caller.key(),
+            )?;
+            require_cranker_bond(&ctx.accounts.config, ctx.accounts.cranker_bond.as_deref())?;
+        }
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(!auction_day.swept, ErrorCode::RefundWindowClosed);
+        require!(auction_day.refund_root != [0; 32], ErrorCode::NoRefundRoot);
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        require!(
+            refund_merkle::verify(
+                &auction_day.refund_root,
+                refund_merkle::leaf(&bidder.to_bytes(), amount),
+                &proof,
+            ),
+            ErrorCode::InvalidRefundProof
+        );
+
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        require!(!bid_receipt.refunded, ErrorCode::ReceiptAlreadyRefunded);
+        require!(
+            !auction_day.is_winner(&bidder),
+            ErrorCode::NoRefundToClaim
+        );
+        // Same fee split as `refund_loser_receipt`: the early-bird discount is
+        // paid to the bidder out of the fee pool, so `amount` includes it.
+        let full_fee = if auction_day.cancelled {
+            0
+        } else {
+            loser_fee_for(auction_day, bid_receipt.amount)?
+        };
+        let discount = early_bird_discount(auction_day, bid_receipt.first_bid_ts, full_fee)?;
+        // A bad root can underpay a bidder but never pay out more than the bid.
+        require!(
+            amount
+                .checked_add(full_fee - discount)
+                .is_some_and(|total| total <= bid_receipt.amount),
+            ErrorCode::InvalidRefundProof
+        );
+        // The settle reward may have left the fee pool short; the caller absorbs it.
+        let fee_share = full_fee.min(auction_day.fee_pool_remaining);
+        let discount = discount.min(fee_share);
+        let loser_fee = fee_share - discount;
+        let pool_refund = amount
+            .checked_sub(discount)
+            .ok_or(ErrorCode::InvalidRefundProof)?;
+        require!(
+            auction_day.refund_pool_remaining >= pool_refund,
+            ErrorCode::InsufficientVaultLamports
+        );
+        auction_day.refund_pool_remaining -= pool_refund;
+        auction_day.fee_pool_remaining -= fee_share;
+        let referral_fee = take_referral_fee(auction_day, bid_receipt, loser_fee)?;
+        let cranker_fee = take_cranker_fee(auction_day, loser_fee - referral_fee)?;
+        let completed_before = auction_day.refund_count_completed;
+        auction_day.refund_count_completed = completed_before
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        bid_receipt.refunded = true;
+        if third_party {
+            record_refund_cranker(
+                &ctx.accounts.config,
+                auction_day,
+                &ctx.accounts.caller.key(),
+                completed_before,
+            );
+        }
+
+        let bid_receipt_info = bid_receipt.to_account_info();
+        let caller = ctx.accounts.caller.to_account_info();
+        pay_refund_tip(&bid_receipt_info, bid_receipt, &caller)?;
+        release_counter_raise(&bid_receipt_info, bid_receipt, &ctx.accounts.bidder)?;
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &ctx.accounts.bidder,
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &caller,
+            &ctx.accounts.system_program,
+            cranker_fee,
+        )?;
+
+        emit_cpi!(RefundProofClaimed {
+            day_index,
+            bidder,
+            amount,
+            loser_fee,
+        });
+        let seq = auction_day.next_seq()?;
+        emit_cpi!(RefundIssued {
+            day_index,
+            bidder,
+            refund_lamports: amount,
+            loser_fee,
+            refunded_at: Clock::get()?.unix_timestamp,
+            seq,
+        });
+        Ok(())
+    }
+
+    // Pays out a refund voucher escrowed by `refund_batch` to any destination
+    // the bidder picks, e.g. a fresh wallet when the original one is unusable.
+    pub fn redeem_voucher(ctx: Context<RedeemVoucher>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_CLAIMS)?;
+        require!(
+            ctx.accounts.auction_day.day_index == day_index,
+            ErrorCode::DayMismatch
+        );
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        let lamports = bid_receipt.voucher_lamports;
+        require!(lamports > 0, ErrorCode::NoVoucher);
+        bid_receipt.voucher_lamports = 0;
+        bid_receipt.sub_lamports(lamports)?;
+        ctx.accounts.destination.add_lamports(lamports)?;
+
+        emit_cpi!(RefundVoucherRedeemed {
+            day_index,
+            bidder: bid_receipt.bidder,
+            destination: ctx.accounts.destination.key(),
+            lamports,
+        });
+        Ok(())
+    }
+
+    // Lets a bidder who is not winning leave an open day before the withdrawal
+    // cutoff. The penalty stays in the vault for the recipient; the rest of the
+    // bid and the receipt (rent and any refund tip) go back to the bidder.
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_CLAIMS)?;
+        let now = Clock::get()?.unix_timestamp;
+        let bidder = ctx.accounts.bidder.key();
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(!auction_day.bidding_closed, ErrorCode::BiddingClosed);
+        require!(
+            auction_day.withdraw_cutoff_seconds > 0,
+            ErrorCode::WithdrawalsDisabled
+        );
+        // Sealed amounts are hidden and candle slots may still name the bidder.
+        require!(
+            !auction_day.is_sealed() && !auction_day.is_candle(),
+            ErrorCode::WithdrawalsDisabled
+        );
+        require!(
+            now < auction_day
+                .bidding_end_ts()
+                .saturating_sub(auction_day.withdraw_cutoff_seconds),
+            ErrorCode::WithdrawCutoffPassed
+        );
+        require!(
+            !auction_day.is_winner(&bidder),
+            ErrorCode::LeaderCannotWithdraw
+        );
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+
+        let amount = ctx.accounts.bid_receipt.amount;
+        let penalty = apply_bps(amount, auction_day.withdraw_penalty_bps, auction_day.bps_rounding)?;
+        let refund = amount.checked_sub(penalty).ok_or(ErrorCode::MathOverflow)?;
+
+        auction_day.loser_fee_accrued = auction_day
+            .loser_fee_accrued
+            .checked_sub(loser_fee_for(auction_day, amount)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.total_bid_lamports = auction_day
+            .total_bid_lamports
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.bidder_count = auction_day
+            .bidder_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.withdraw_penalties = auction_day
+            .withdraw_penalties
+            .checked_add(penalty)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.remove_top_bid(&bidder);
+
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &ctx.accounts.bidder.to_account_info(),
+            &ctx.accounts.system_program,
+            refund,
+        )?;
+        remove_exposure(&mut ctx.accounts.config, auction_day, refund);
+
+        let seq = ctx.accounts.auction_day.next_seq()?;
+        emit_cpi!(BidWithdrawn {
+            day_index,
+            bidder,
+            amount,
+            penalty,
+            seq,
+        });
+        Ok(())
+    }
+
+    // Places the same bid on each day of `[first_day_index, first_day_index +
+    // day_count)`, creating the days, vaults, and receipts as needed. Future
+    // days accept these advance bids before they open; every other bid rule
+    // applies per day. Remaining accounts: (auction_day, vault, bid_receipt,
+    // schedule_override, bid_index) per day, in order. Days closed by the
+    // schedule are skipped.
+    pub fn place_bundle_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceBundleBid<'info>>,
+        first_day_index: i64,
+        day_count: u8,
+        new_amount: u64,
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        require!(new_amount > 0, ErrorCode::InvalidBidAmount);
+        require!(
+            (1..=MAX_BUNDLE_DAYS).contains(&day_count),
+            ErrorCode::InvalidBundle
+        );
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let current_day = current_day_index(&clock, &ctx.accounts.config);
+        let last_day_index = first_day_index
+            .checked_add(i64::from(day_count) - 1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            first_day_index >= current_day
+                && last_day_index <= current_day + MAX_BUNDLE_HORIZON_DAYS,
+            ErrorCode::InvalidBundle
+        );
+        require!(
+            ctx.remaining_accounts.len() == usize::from(day_count) * 5,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let program_id = ctx.program_id;
+        let bidder = ctx.accounts.bidder.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        for (offset, accounts) in ctx.remaining_accounts.chunks(5).enumerate() {
+            let day_index = first_day_index + offset as i64;
+            let [auction_day_info, vault_info, receipt_info, schedule_override_info, bid_index_info] =
+                accounts
+            else {
+                return err!(ErrorCode::InvalidRemainingAccounts);
+            };
+
+            let day_seed = day_index.to_le_bytes();
+            let (expected_schedule_override, _) =
+                Pubkey::find_program_address(&[b"schedule_override", &day_seed], program_id);
+            require_keys_eq!(
+                schedule_override_info.key(),
+                expected_schedule_override,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            if is_day_closed(schedule_override_info, program_id)? {
+                msg!("place_bundle_bid: day {} closed by schedule override", day_index);
+                continue;
+            }
+
+            let (expected_day, day_bump) =
+                Pubkey::find_program_address(&[b"auction_day", &day_seed], program_id);
+            require_keys_eq!(
+                auction_day_info.key(),
+                expected_day,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            let (expected_vault, vault_bump) =
+                Pubkey::find_program_address(&[b"vault", expected_day.as_ref()], program_id);
+            require_keys_eq!(
+                vault_info.key(),
+                expected_vault,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            let (expected_receipt, receipt_bump) = Pubkey::find_program_address(
+                &[b"bid_receipt", expected_day.as_ref(), bidder.key.as_ref()],
+                program_id,
+            );
+            require_keys_eq!(
+                receipt_info.key(),
+                expected_receipt,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            require!(
+                vault_info.owner == &system_program::ID,
+                ErrorCode::InvalidVaultOwner
+            );
+
+            ensure_program_account::<AuctionDay>(
+                auction_day_info,
+                &bidder,
+                &system_program,
+                AuctionDay::SPACE,
+                &[b"auction_day", &day_seed, &[day_bump]],
+                program_id,
+            )?;
+            ensure_program_account::<BidReceipt>(
+                receipt_info,
+                &bidder,
+                &system_program,
+                BidReceipt::SPACE,
+                &[
+                    b"bid_receipt",
+                    expected_day.as_ref(),
+                    bidder.key.as_ref(),
+                    &[receipt_bump],
+                ],
+                program_id,
+            )?;
+            let mut auction_day: Account<AuctionDay> = Account::try_from(auction_day_info)?;
+            let mut bid_receipt: Account<BidReceipt> = Account::try_from(receipt_info)?;
+
+            if is_uninitialized_auction_day(&auction_day) {
+                initialize_auction_day(
+                    &mut auction_day,
+                    day_index,
+                    vault_bump,
+                    vault_info.lamports(),
+                    &ctx.accounts.config,
+                    &events,
+                )?;
+                apply_day_category(
+                    &mut auction_day,
+                    schedule_override_info,
+                    ctx.accounts.category_table.as_deref(),
+                    program_id,
+                )?;
+            }
+            require!(!auction_day.is_sealed(), ErrorCode::SealedBidDay);
+            require!(
+                auction_day.is_open_at(now) || now < auction_day.start_ts,
+                ErrorCode::WrongDay
+            );
+
+            let bidder_count_before = auction_day.bidder_count;
+            let delta = apply_bid(
+                &mut auction_day,
+                &mut bid_receipt,
+                Bidder {
+                    key: bidder.key(),
+                    mint: Pubkey::default(),
+                    stats: ctx.accounts.bidder_stats.as_deref_mut(),
+                    vetted: false,
+                },
+                &ctx.accounts.config,
+                ctx.accounts.price_floor.as_ref(),
+                now,
+                new_amount,
+                &events,
+            )?;
+            add_exposure(&mut ctx.accounts.config, &mut auction_day, delta)?;
+            if auction_day.bidder_count > bidder_count_before {
+                record_bid_index(
+                    &mut auction_day,
+                    Some(bid_index_info),
+                    bidder.key(),
+                    &bidder,
+                    &system_program,
+                    program_id,
+                )?;
+            }
+            if delta > 0 {
+                let cpi_ctx = CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: bidder.clone(),
+                        to: vault_info.clone(),
+                    },
+                );
+                transfer(cpi_ctx, delta)?;
+            }
+
+            auction_day.exit(program_id)?;
+            bid_receipt.exit(program_id)?;
+        }
+
+        emit_cpi!(BundleBidPlaced {
+            bidder: bidder.key(),
+            first_day_index,
+            day_count,
+            amount: new_amount,
+        });
+        Ok(())
+    }
+
+    // Promotional "try a bid free" tier: a notional bid up to
+    // `promo_bid_max_lamports` that never touches the vault and can never win.
+    // The promo pool reimburses the bidder for the PromoBid rent in the same
+    // transaction, so the bidder only pays the transaction fee.
+    pub fn place_promo_bid(ctx: Context<PlacePromoBid>, day_index: i64, amount: u64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        let max_lamports = ctx.accounts.config.promo_bid_max_lamports;
+        require!(max_lamports > 0, ErrorCode::PromoBidsDisabled);
+        require!(
+            amount > 0 && amount <= max_lamports,
+            ErrorCode::InvalidPromoBid
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.is_open_at(now), ErrorCode::WrongDay);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(!auction_day.bidding_closed, ErrorCode::BiddingClosed);
+        require!(!auction_day.is_gated(), ErrorCode::BidGateNotMet);
+        require!(
+            ctx.accounts.config.access_mode == ACCESS_MODE_OFF,
+            ErrorCode::AccessEntryRequired
+        );
+
+        let rent = Rent::get()?;
+        let reimbursement = rent.minimum_balance(PromoBid::SPACE);
+        require!(
+            ctx.accounts.promo_pool.lamports() >= reimbursement + rent.minimum_balance(0),
+            ErrorCode::PromoPoolEmpty
+        );
+        transfer_from_system_pda(
+            &ctx.accounts.promo_pool,
+            b"promo_pool",
+            ctx.bumps.promo_pool,
+            &ctx.accounts.bidder.to_account_info(),
+            &ctx.accounts.system_program,
+            reimbursement,
+        )?;
+
+        let promo_bid = &mut ctx.accounts.promo_bid;
+        promo_bid.auction_day = auction_day.key();
+        promo_bid.bidder = ctx.accounts.bidder.key();
+        promo_bid.amount = amount;
+        promo_bid.placed_at = now;
+        promo_bid.bump = ctx.bumps.promo_bid;
+        auction_day.promo_bid_count = auction_day
+            .promo_bid_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let seq = auction_day.next_seq()?;
+        emit_cpi!(PromoBidPlaced {
+            day_index,
+            bidder: promo_bid.bidder,
+            amount,
+            promo_bid_count: auction_day.promo_bid_count,
+            seq,
+        });
+        Ok(())
+    }
+
+    pub fn fund_promo_pool(ctx: Context<FundPromoPool>, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.promo_pool.to_account_info(),
+            },
+        );
+        transfer(cpi_ctx, amount)
+    }
+
+    // Test instances only: mints a wallet the daily drip of the play-money
+    // `bid_mint`, so testers can bid through the normal token-bid paths. The
+    // authority co-signs every drip, so only wallets it vouches for get any.
+    #[cfg(feature = "devnet-faucet")]
+    pub fn faucet_drip(ctx: Context<FaucetDrip>) -> Result<()> {
+        let day_index = current_day_index(&Clock::get()?, &ctx.accounts.config);
+        let claim = &mut ctx.accounts.faucet_claim;
+        require!(
+            claim.wallet == Pubkey::default() || claim.last_claim_day < day_index,
+            ErrorCode::FaucetAlreadyClaimed
+        );
+        require!(
+            ctx.accounts
+                .bid_mint
+                .mint_authority
+                .contains(&ctx.accounts.faucet.key()),
+            ErrorCode::FaucetMintAuthority
+        );
+        claim.wallet = ctx.accounts.wallet.key();
+        claim.last_claim_day = day_index;
+        claim.bump = ctx.bumps.faucet_claim;
+
+        let amount = 10u64
+            .checked_pow(u32::from(ctx.accounts.bid_mint.decimals))
+            .and_then(|unit| unit.checked_mul(FAUCET_DRIP_TOKENS))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let seeds: &[&[u8]] = &[b"faucet", &[ctx.bumps.faucet]];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.bid_mint.to_account_info(),
+                    to: ctx.accounts.wallet_token_account.to_account_info(),
+                    authority: ctx.accounts.faucet.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        emit_cpi!(FaucetDripped {
+            wallet: claim.wallet,
+            day_index,
+            mint: ctx.accounts.bid_mint.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw_protocol_treasury(
+        ctx: Context<WithdrawProtocolTreasury>,
+        amount: u64,
+    ) -> Result<()> {
+        transfer_from_system_pda(
+            &ctx.accounts.protocol_treasury,
+            b"protocol_treasury",
+            ctx.bumps.protocol_treasury,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )
+    }
+
+    pub fn withdraw_promo_pool(ctx: Context<WithdrawPromoPool>, amount: u64) -> Result<()> {
+        transfer_from_system_pda(
+            &ctx.accounts.promo_pool,
+            b"promo_pool",
+            ctx.bumps.promo_pool,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program,
+            amount,
+        )
+    }
+
+    // Returns a settled day's PromoBid rent to the promo pool (permissionless).
+    pub fn reclaim_promo_bid(
+        ctx: Context<ReclaimPromoBid>,
+        day_index: i64,
+        _bidder: Pubkey,
+    ) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        auction_day.promo_bids_reclaimed = auction_day.promo_bids_reclaimed.saturating_add(1);
+        Ok(())
+    }
+
+    // Pre-registers (or changes) a tip escrowed on the bidder's receipt, paid
+    // to whichever cranker refunds the receipt first.
+    pub fn set_refund_tip(
+        ctx: Context<SetRefundTip>,
+        day_index: i64,
+        tip_lamports: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.auction_day.day_index == day_index,
+            ErrorCode::DayMismatch
+        );
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        require!(!bid_receipt.refunded, ErrorCode::ReceiptAlreadyRefunded);
+
+        let current = bid_receipt.refund_tip_lamports;
+        if tip_lamports > current {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bidder.to_account_info(),
+                    to: bid_receipt.to_account_info(),
+                },
+            );
+            transfer(cpi_ctx, tip_lamports - current)?;
+        } else if tip_lamports < current {
+            bid_receipt.sub_lamports(current - tip_lamports)?;
+            ctx.accounts.bidder.add_lamports(current - tip_lamports)?;
+        }
+        bid_receipt.refund_tip_lamports = tip_lamports;
+
+        emit_cpi!(RefundTipSet {
+            day_index,
+            bidder: bid_receipt.bidder,
+            tip_lamports,
+        });
+        Ok(())
+    }
+
+    // Bidder-side counterpart of `close_receipts_batch`: once the receipt is
+    // refunded (or belongs to a settled winner), its rent goes back to the bidder.
+    pub fn close_bid_receipt(ctx: Context<CloseBidReceipt>, day_index: i64) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        let bid_receipt = &ctx.accounts.bid_receipt;
+        require!(
+            bid_receipt.refunded
+                || auction_day.swept
+                || auction_day.is_winner(&ctx.accounts.bidder.key()),
+            ErrorCode::ReceiptNotRefunded
+        );
+        require!(bid_receipt.voucher_lamports == 0, ErrorCode::VoucherOutstanding);
+        auction_day.receipts_closed = auction_day.receipts_closed.saturating_add(1);
+        Ok(())
+    }
+
+    pub fn close_receipts_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseReceiptsBatch<'info>>,
+        day_index: i64,
+        receipts: Vec<Pubkey>,
+    ) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+
+        // Remaining accounts: (bid_receipt, rent_destination) per receipt.
+        let expected_accounts = receipts.len().checked_mul(2).ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            ctx.remaining_accounts.len() == expected_accounts,
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let policy = ctx.accounts.config.receipt_rent_policy;
+        let mut rent_lamports: u64 = 0;
+        for (i, receipt_pubkey) in receipts.iter().enumerate() {
+            let bid_receipt_info = &ctx.remaining_accounts[i * 2];
+            let destination_info = &ctx.remaining_accounts[i * 2 + 1];
+
+            require!(
+                bid_receipt_info.key == receipt_pubkey,
+                ErrorCode::BidReceiptMismatch
+            );
+            require!(
+                bid_receipt_info.owner == ctx.program_id,
+                ErrorCode::BidReceiptOwnerMismatch
+            );
+            let bid_receipt = {
+                let mut data_slice: &[u8] = &bid_receipt_info.data.borrow();
+                BidReceipt::try_deserialize(&mut data_slice)?
+            };
+            require!(
+                bid_receipt.auction_day == auction_day.key(),
+                ErrorCode::BidReceiptMismatch
+            );
+            // Swept days forfeited their unclaimed refunds, so any receipt can go.
+            require!(
+                bid_receipt.refunded || auction_day.swept,
+                ErrorCode::ReceiptNotRefunded
+            );
+            require!(bid_receipt.voucher_lamports == 0, ErrorCode::VoucherOutstanding);
+            require!(
+                bid_receipt.counter_raise_escrow == 0,
+                ErrorCode::CounterRaiseOutstanding
+            );
+
+            let expected_destination = match policy {
+                ReceiptRentPolicy::Owner => bid_receipt.bidder,
+                ReceiptRentPolicy::Payer => ctx.accounts.cranker.key(),
+                ReceiptRentPolicy::Treasury => ctx.accounts.config.recipient_pubkey,
+            };
+            require_keys_eq!(
+                destination_info.key(),
+                expected_destination,
+                ErrorCode::RentDestinationMismatch
+            );
+
+            rent_lamports = rent_lamports
+                .checked_add(bid_receipt_info.lamports())
+                .ok_or(ErrorCode::MathOverflow)?;
+            close_program_account(bid_receipt_info, destination_info)?;
+        }
+        auction_day.receipts_closed = auction_day
+            .receipts_closed
+            .saturating_add(receipts.len() as u32);
+
+        emit_cpi!(ReceiptsClosed {
+            day_index,
+            count: receipts.len() as u32,
+            rent_lamports,
+            policy,
+        });
+        Ok(())
+    }
+
+    pub fn revoke_all_authorizations(ctx: Context<RevokeAllAuthorizations>) -> Result<()> {
+        let authorization_epoch = &mut ctx.accounts.authorization_epoch;
+        authorization_epoch.owner = ctx.accounts.owner.key();
+        authorization_epoch.bump = ctx.bumps.authorization_epoch;
+        authorization_epoch.epoch = authorization_epoch
+            .epoch
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        authorization_epoch.revoked_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "revoke_all_authorizations: owner={} epoch={}",
+            authorization_epoch.owner,
+            authorization_epoch.epoch
+        );
+        Ok(())
+    }
+
+    pub fn migrate_vault(ctx: Context<MigrateVault>, day_index: i64) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        // Open days still route bids through `init_if_needed` on the system-owned vault.
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            auction_day.vault_version == VAULT_VERSION_SYSTEM_OWNED,
+            ErrorCode::VaultAlreadyMigrated
+        );
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+
+        let outstanding = auction_day
+            .refund_pool_remaining
+            .checked_add(auction_day.fee_pool_remaining)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let vault_lamports = ctx.accounts.vault.lamports();
+        require!(
+            vault_lamports >= outstanding,
+            ErrorCode::InsufficientVaultLamports
+        );
+
+        // The vault keeps its address and bump; only the owner changes, so the
+        // balance moves with it and no receipts need to be rewritten.
+        let auction_day_key = auction_day.key();
+        let seeds: &[&[u8]] = &[b"vault", auction_day_key.as_ref(), &[auction_day.vault_bump]];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Assign {
+                account_to_assign: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        assign(cpi_ctx, ctx.program_id)?;
+        auction_day.vault_version = VAULT_VERSION_PROGRAM_OWNED;
+
+        emit_cpi!(VaultMigrated {
+            day_index,
+            vault: ctx.accounts.vault.key(),
+            vault_bump: auction_day.vault_bump,
+            lamports: vault_lamports,
+            outstanding_lamports: outstanding,
+            from_version: VAULT_VERSION_SYSTEM_OWNED,
+            to_version: VAULT_VERSION_PROGRAM_OWNED,
+        });
+        Ok(())
+    }
+
+    pub fn set_display_payload(
+        ctx: Context<SetDisplayPayload>,
+        day_index: i64,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        require!(
+            payload.len() <= DISPLAY_PAYLOAD_MAX_LEN,
+            ErrorCode::DisplayPayloadTooLong
+        );
+        let display_hook = &mut ctx.accounts.display_hook;
+        display_hook.day_index = day_index;
+        display_hook.payload = payload;
+        display_hook.bump = ctx.bumps.display_hook;
+        Ok(())
+    }
+
+    pub fn set_schedule_override(
+        ctx: Context<SetScheduleOverride>,
+        day_index: i64,
+        closed: bool,
+    ) -> Result<()> {
+        // Closing a day that already has bids would strand them without settlement.
+        let auction_day_info = ctx.accounts.auction_day.to_account_info();
+        if closed && auction_day_info.owner == ctx.program_id && !auction_day_info.data_is_empty() {
+            let auction_day = AuctionDay::try_deserialize(&mut &auction_day_info.data.borrow()[..])?;
+            require!(auction_day.bidder_count == 0, ErrorCode::DayHasBids);
+        }
+
+        let schedule_override = &mut ctx.accounts.schedule_override;
+        schedule_override.day_index = day_index;
+        schedule_override.closed = closed;
+        schedule_override.updated_at = Clock::get()?.unix_timestamp;
+        schedule_override.bump = ctx.bumps.schedule_override;
+        msg!("set_schedule_override: day {} closed={}", day_index, closed);
+        Ok(())
+    }
+
+    // Describes what a day auctions (which charity, which slot). Like the
+    // schedule override it can be written before `init_day`, and it locks once
+    // the day takes its first bid so bidders know what they are bidding on.
+    pub fn set_day_meta(
+        ctx: Context<SetDayMeta>,
+        day_index: i64,
+        title: String,
+        description: String,
+        image_uri: String,
+    ) -> Result<()> {
+        require!(
+            title.len() <= DAY_META_TITLE_MAX_LEN
+                && description.len() <= DAY_META_DESCRIPTION_MAX_LEN
+                && image_uri.len() <= DAY_META_IMAGE_URI_MAX_LEN,
+            ErrorCode::DayMetaTooLong
+        );
+        let auction_day_info = ctx.accounts.auction_day.to_account_info();
+        if auction_day_info.owner == ctx.program_id && !auction_day_info.data_is_empty() {
+            let auction_day = AuctionDay::try_deserialize(&mut &auction_day_info.data.borrow()[..])?;
+            require!(auction_day.bidder_count == 0, ErrorCode::DayHasBids);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let day_meta = &mut ctx.accounts.day_meta;
+        day_meta.day_index = day_index;
+        day_meta.title = title;
+        day_meta.description = description;
+        day_meta.image_uri = image_uri;
+        day_meta.updated_at = now;
+        day_meta.bump = ctx.bumps.day_meta;
+
+        emit_cpi!(DayMetaSet {
+            day_index,
+            title: day_meta.title.clone(),
+            description: day_meta.description.clone(),
+            image_uri: day_meta.image_uri.clone(),
+            updated_at: now,
+        });
+        Ok(())
+    }
+
+    // Allowlists or denylists a wallet, or clears it with `ACCESS_NONE`. Takes
+    // effect on the wallet's next bid while `Config.access_mode` is on.
+    pub fn set_access_entry(
+        ctx: Context<SetAccessEntry>,
+        wallet: Pubkey,
+        status: u8,
+    ) -> Result<()> {
+        require!(
+            matches!(status, ACCESS_NONE | ACCESS_ALLOWED | ACCESS_DENIED),
+            ErrorCode::InvalidAccessMode
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let access_entry = &mut ctx.accounts.access_entry;
+        access_entry.wallet = wallet;
+        access_entry.status = status;
+        access_entry.updated_at = now;
+        access_entry.bump = ctx.bumps.access_entry;
+
+        emit_cpi!(AccessEntrySet {
+            wallet,
+            status,
+            updated_at: now,
+        });
+        Ok(())
+    }
+
+    // Writes the rules for one operator-defined category. Zeroed fields keep
+    // the `Config` default; see `apply_day_category`.
+    pub fn set_category_rules(
+        ctx: Context<SetCategoryRules>,
+        category: u8,
+        rules: CategoryRules,
+    ) -> Result<()> {
+        require!(
+            category != CATEGORY_DEFAULT && usize::from(category) < MAX_CATEGORIES,
+            ErrorCode::InvalidCategory
+        );
+        require!(
+            u64::from(rules.loser_fee_bps) <= BPS_DENOMINATOR,
+            ErrorCode::InvalidBps
+        );
+        require!(
+            rules.moderation_level <= MAX_MODERATION_LEVEL,
+            ErrorCode::InvalidCategory
+        );
+
+        let category_table = &mut ctx.accounts.category_table;
+        category_table.rules[usize::from(category)] = rules;
+        category_table.updated_at = Clock::get()?.unix_timestamp;
+        category_table.bump = ctx.bumps.category_table;
+        msg!("set_category_rules: category {}", category);
+        Ok(())
+    }
+
+    // Adds or removes an approved refund cranker. The registry only gates
+    // refunds while `Config.permissioned_refunds` is set.
+    pub fn set_cranker(ctx: Context<SetCranker>, cranker: Pubkey, approved: bool) -> Result<()> {
+        let cranker_registry = &mut ctx.accounts.cranker_registry;
+        cranker_registry.bump = ctx.bumps.cranker_registry;
+        let position = cranker_registry.crankers.iter().position(|key| *key == cranker);
+        match (position, approved) {
+            (None, true) => {
+                require!(
+                    cranker_registry.crankers.len() < CRANKER_REGISTRY_MAX_ENTRIES,
+                    ErrorCode::CrankerRegistryFull
+                );
+                cranker_registry.crankers.push(cranker);
+            }
+            (Some(index), false) => {
+                cranker_registry.crankers.swap_remove(index);
+            }
+            _ => {}
+        }
+        msg!("set_cranker: {} approved={}", cranker, approved);
+        Ok(())
+    }
+
+    // Assigns a category to a day before it is initialized; the day resolves
+    // the category's rules once, when it is created.
+    pub fn set_day_category(ctx: Context<SetDayCategory>, day_index: i64, category: u8) -> Result<()> {
+        require!(usize::from(category) < MAX_CATEGORIES, ErrorCode::InvalidCategory);
+        let auction_day_info = ctx.accounts.auction_day.to_account_info();
+        require!(
+            auction_day_info.owner != ctx.program_id || auction_day_info.data_is_empty(),
+            ErrorCode::DayAlreadyInitialized
+        );
+
+        let schedule_override = &mut ctx.accounts.schedule_override;
+        schedule_override.day_index = day_index;
+        schedule_override.category = category;
+        schedule_override.updated_at = Clock::get()?.unix_timestamp;
+        schedule_override.bump = ctx.bumps.schedule_override;
+        msg!("set_day_category: day {} category={}", day_index, category);
+        Ok(())
+    }
+
+    // Incident escape hatch: cancels a day that has not been settled, open or
+    // not. Bidding stops, nothing is paid out, and `refund_batch` returns every
+    // bid in full.
+    pub fn cancel_day(ctx: Context<CancelDay>, day_index: i64) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        let vault_lamports = vault_principal(auction_day, &ctx.accounts.vault);
+        require!(
+            vault_lamports >= auction_day.total_bid_lamports,
+            ErrorCode::InsufficientVaultLamports
+        );
+
+        release_exposure(&mut ctx.accounts.config, auction_day);
+        cancel_auction_day(auction_day);
+        start_refund_window(auction_day, Clock::get()?.unix_timestamp)?;
+        emit_cpi!(DayCancelled {
+            day_index,
+            bidder_count: auction_day.bidder_count,
+            refund_pool: auction_day.refund_pool_remaining,
+        });
+        Ok(())
+    }
+
+    pub fn set_buy_now_price(
+        ctx: Context<SetBuyNowPrice>,
+        day_index: i64,
+        buy_now_lamports: u64,
+    ) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(!auction_day.bidding_closed, ErrorCode::BiddingClosed);
+        require!(
+            buy_now_lamports == 0 || buy_now_lamports > auction_day.highest_bid,
+            ErrorCode::InvalidBuyNowPrice
+        );
+        auction_day.buy_now_lamports = buy_now_lamports;
+        Ok(())
+    }
+
+    // Points one day's participation cNFTs at a different tree, or turns them
+    // off with Pubkey::default(), until the day settles.
+    pub fn set_receipt_tree(
+        ctx: Context<SetReceiptTree>,
+        day_index: i64,
+        receipt_tree: Pubkey,
+    ) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        auction_day.receipt_tree = receipt_tree;
+        Ok(())
+    }
+
+    pub fn certify_day_complete(ctx: Context<CertifyDayComplete>, day_index: i64) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            auction_day.refund_count_completed >= auction_day.refund_count_total,
+            ErrorCode::RefundsIncomplete
+        );
+
+        let day_complete = &mut ctx.accounts.day_complete;
+        day_complete.auction_day = auction_day.key();
+        day_complete.day_index = day_index;
+        day_complete.completed_at = Clock::get()?.unix_timestamp;
+        day_complete.winner = auction_day.winner;
+        day_complete.winning_bid = auction_day.highest_bid;
+        day_complete.refund_count = auction_day.refund_count_completed;
+        day_complete.refund_pool_remaining = auction_day.refund_pool_remaining;
+        day_complete.fee_pool_remaining = auction_day.fee_pool_remaining;
+        day_complete.bump = ctx.bumps.day_complete;
+        Ok(())
+    }
+
+    // Standard ownership handshake for off-chain perks: the transaction must
+    // carry, immediately before this instruction, an Ed25519 program check of
+    // the winner's signature over
+    // `"daily-auction:verify_winner:" || auction_day_pubkey || challenge`.
+    pub fn verify_winner(
+        ctx: Context<VerifyWinner>,
+        day_index: i64,
+        challenge: [u8; 32],
+    ) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let current_index = load_current_index_checked(&instructions)?;
+        require!(current_index > 0, ErrorCode::InvalidSignatureInstruction);
+        let signature_ix = load_instruction_at_checked(current_index as usize - 1, &instructions)?;
+        require_keys_eq!(
+            signature_ix.program_id,
+            solana_sdk_ids::ed25519_program::ID,
+            ErrorCode::InvalidSignatureInstruction
+        );
+        let (winner, message) = ed25519_single_signature(&signature_ix.data)?;
+
+        let auction_day_key = auction_day.key();
+        let expected = [VERIFY_WINNER_DOMAIN, auction_day_key.as_ref(), &challenge].concat();
+        require!(message == expected.as_slice(), ErrorCode::InvalidSignatureInstruction);
+        require!(
+            auction_day.is_winner(&winner),
+            ErrorCode::Unauthorized
+        );
+
+        emit_cpi!(WinnerVerified {
+            day_index,
+            winner,
+            challenge,
+            verified_at: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    pub fn propose_destructive_action(
+        ctx: Context<ProposeDestructiveAction>,
+        action: DestructiveAction,
+        target: Pubkey,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pending = &mut ctx.accounts.pending_action;
+        pending.action = action;
+        pending.target = target;
+        pending.proposer = ctx.accounts.authority.key();
+        pending.proposed_at = now;
+        pending.executable_at = now
+            .checked_add(ctx.accounts.config.destructive_action_delay_seconds)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pending.last_warned_at = now;
+        pending.bump = ctx.bumps.pending_action;
+
+        emit_cpi!(DestructiveActionProposed {
+            action,
+            target,
+            proposer: pending.proposer,
+            executable_at: pending.executable_at,
+        });
+        Ok(())
+    }
+
+    pub fn cancel_destructive_action(
+        ctx: Context<CancelDestructiveAction>,
+        target: Pubkey,
+    ) -> Result<()> {
+        let pending = &ctx.accounts.pending_action;
+        emit_cpi!(DestructiveActionCancelled {
+            action: pending.action,
+            target,
+            cancelled_by: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    // Permissionless warning crank for a pending destructive action. It only
+    // fires during the waiting period, at most once per warning interval, so
+    // operators keep hearing about the action without indexers being spammed.
+    pub fn warn_destructive_action(
+        ctx: Context<WarnDestructiveAction>,
+        target: Pubkey,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pending = &mut ctx.accounts.pending_action;
+        require!(now < pending.executable_at, ErrorCode::DestructiveActionReady);
+        require!(
+            now >= pending
+                .last_warned_at
+                .saturating_add(DESTRUCTIVE_ACTION_WARNING_INTERVAL_SECONDS),
+            ErrorCode::DestructiveActionWarningNotDue
+        );
+        pending.last_warned_at = now;
+
+        emit_cpi!(DestructiveActionPending {
+            action: pending.action,
+            target,
+            executable_at: pending.executable_at,
+            seconds_remaining: pending.executable_at - now,
+        });
+        Ok(())
+    }
+
+    // Authority only, after a `ResetCounters` proposal for the `GlobalStats`
+    // account has waited out its delay. Zeroes every lifetime total.
+    pub fn reset_global_stats(ctx: Context<ResetGlobalStats>) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        ctx.accounts.pending_action.require_ready(
+            DestructiveAction::ResetCounters,
+            ctx.accounts.global_stats.key(),
+            Clock::get()?.unix_timestamp,
+            &events,
+        )?;
+        ctx.accounts.global_stats.reset();
+        Ok(())
+    }
+
+    // Authority only, after a `CloseStatsAccount` proposal for the
+    // `GlobalStats` account has waited out its delay. The rent goes to the
+    // authority; the next `place_bid` recreates the account from zero.
+    pub fn close_global_stats(ctx: Context<CloseGlobalStats>) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        ctx.accounts.pending_action.require_ready(
+            DestructiveAction::CloseStatsAccount,
+            ctx.accounts.global_stats.key(),
+            Clock::get()?.unix_timestamp,
+            &events,
+        )?;
+        Ok(())
+    }
+
+    pub fn sync_program_info(ctx: Context<SyncProgramInfo>) -> Result<()> {
+        let program_info = &mut ctx.accounts.program_info;
+        let previous_version = program_info.version;
+        program_info.version = PROGRAM_VERSION;
+        program_info.capabilities = program_capabilities();
+        program_info.capabilities_ext = program_capabilities_ext();
+        program_info.updated_at = Clock::get()?.unix_timestamp;
+        program_info.updated_by = ctx.accounts.authority.key();
+        program_info.bump = ctx.bumps.program_info;
+
+        emit_cpi!(ProgramInfoUpdated {
+            previous_version,
+            version: program_info.version,
+            capabilities: program_info.capabilities,
+            capabilities_ext: program_info.capabilities_ext,
+        });
+        Ok(())
+    }
+
+    // Consumes the day's randomness to pick the candle cutoff slot and makes
+    // that slot's leader the winner at their bid as of the cutoff. Any part of
+    // the winner's escrow bid after the cutoff is returned here; everyone else
+    // is refunded normally after `settle_day`.
+    pub fn resolve_candle(ctx: Context<ResolveCandle>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_SETTLEMENT)?;
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.is_candle(), ErrorCode::NotCandleDay);
+        require!(!auction_day.candle_resolved, ErrorCode::CandleAlreadyResolved);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(now >= auction_day.end_ts, ErrorCode::TooEarly);
+        require!(auction_day.highest_bid > 0, ErrorCode::InvalidBidAmount);
+
+        require_keys_eq!(
+            ctx.accounts.randomness.key(),
+            config.randomness_account,
+            ErrorCode::RandomnessMismatch
+        );
+        let randomness =
+            RandomnessFeed::try_from_account(&ctx.accounts.randomness, &config.randomness_program)?;
+        // Bound to this day and produced after bidding closed, so nobody could
+        // know the cutoff while bids were open.
+        require!(
+            randomness.auction_day == auction_day.key()
+                && randomness.produced_at >= auction_day.end_ts,
+            ErrorCode::RandomnessMismatch
+        );
+
+        let slot = auction_day.candle_slot_for(&randomness.value);
+        let winner = auction_day.candle_leaders[slot];
+        let winning_bid = auction_day.candle_bids[slot];
+        require_keys_eq!(
+            ctx.accounts.winner.key(),
+            winner,
+            ErrorCode::CandleWinnerMismatch
+        );
+
+        let winner_receipt = &mut ctx.accounts.winner_receipt;
+        let excess = winner_receipt
+            .amount
+            .checked_sub(winning_bid)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if excess > 0 {
+            transfer_from_vault(
+                auction_day,
+                &ctx.accounts.vault,
+                &ctx.accounts.winner,
+                &ctx.accounts.system_program,
+                excess,
+            )?;
+            auction_day.loser_fee_accrued = auction_day
+                .loser_fee_accrued
+                .checked_sub(loser_fee_for(auction_day, winner_receipt.amount)?)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_add(loser_fee_for(auction_day, winning_bid)?)
+                .ok_or(ErrorCode::MathOverflow)?;
+            auction_day.total_bid_lamports = auction_day
+                .total_bid_lamports
+                .checked_sub(excess)
+                .ok_or(ErrorCode::MathOverflow)?;
+            winner_receipt.amount = winning_bid;
+        }
+
+        let cutoff_ts = auction_day.candle_slot_end(slot);
+        auction_day.winner = winner;
+        auction_day.highest_bid = winning_bid;
+        auction_day.candle_resolved = true;
+        auction_day.candle_cutoff_ts = cutoff_ts;
+
+        emit_cpi!(CandleResolved {
+            day_index,
+            cutoff_ts,
+            winner,
+            winning_bid,
+        });
+        Ok(())
+    }
+
+    pub fn follow_day(ctx: Context<FollowDay>, day_index: i64) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        auction_day.follower_count = auction_day
+            .follower_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let follower = &mut ctx.accounts.follower;
+        follower.auction_day = auction_day.key();
+        follower.user = ctx.accounts.user.key();
+        follower.followed_at = Clock::get()?.unix_timestamp;
+        follower.bump = ctx.bumps.follower;
+
+        emit_cpi!(DayFollowed {
+            day_index,
+            user: follower.user,
+            follower_count: auction_day.follower_count,
+        });
+        Ok(())
+    }
+
+    pub fn unfollow_day(ctx: Context<UnfollowDay>, day_index: i64) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        auction_day.follower_count = auction_day.follower_count.saturating_sub(1);
+        Ok(())
+    }
+
+    pub fn set_content_manager(
+        ctx: Context<SetContentManager>,
+        day_index: i64,
+        manager: Pubkey,
+    ) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            auction_day.winner == ctx.accounts.winner.key(),
+            ErrorCode::Unauthorized
+        );
+
+        let content_manager = &mut ctx.accounts.content_manager;
+        content_manager.auction_day = auction_day.key();
+        content_manager.winner = ctx.accounts.winner.key();
+        content_manager.manager = manager;
+        content_manager.authorization_epoch =
+            current_authorization_epoch(&ctx.accounts.authorization_epoch);
+        content_manager.granted_at = Clock::get()?.unix_timestamp;
+        content_manager.bump = ctx.bumps.content_manager;
+
+        emit_cpi!(ContentManagerSet {
+            day_index,
+            winner: content_manager.winner,
+            manager,
+            authorization_epoch: content_manager.authorization_epoch,
+        });
+        Ok(())
+    }
+
+    // Permissionless reminder crank for a winner who has not submitted content.
+    // Each reminder level is emitted at most once, so repeated pokes cannot spam
+    // notification consumers.
+    pub fn poke(ctx: Context<Poke>, day_index: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        let content_info = ctx.accounts.content.to_account_info();
+        require!(
+            auction_day.claim_deadline_ts > 0
+                && (content_info.owner != ctx.program_id || content_info.data_is_empty()),
+            ErrorCode::NoClaimObligation
+        );
+
+        let level = claim_reminder_level(auction_day.claim_deadline_ts, now);
+        require!(level > auction_day.reminder_level, ErrorCode::ReminderNotDue);
+        auction_day.reminder_level = level;
+
+        emit_cpi!(ClaimReminder {
+            day_index,
+            winner: auction_day.winner,
+            claim_deadline_ts: auction_day.claim_deadline_ts,
+            reminder_level: level,
+        });
+        Ok(())
+    }
+
+    // Records the hash of the winner's content. The first submission escrows
+    // `Config.content_bond_lamports` in the content PDA; later edits keep the
+    // same bond at stake until the display period ends.
+    pub fn submit_content(
+        ctx: Context<SubmitContent>,
+        day_index: i64,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            auction_day.winner == ctx.accounts.winner.key(),
+            ErrorCode::Unauthorized
+        );
+        require!(
+            now < content_display_end(auction_day),
+            ErrorCode::DisplayPeriodOver
+        );
+
+        let content = &mut ctx.accounts.content;
+        require!(!content.slashed, ErrorCode::ContentBondSlashed);
+        if content.winner == Pubkey::default() {
+            let bond_lamports = ctx.accounts.config.content_bond_lamports;
+            if bond_lamports > 0 {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.winner.to_account_info(),
+                        to: content.to_account_info(),
+                    },
+                );
+                transfer(cpi_ctx, bond_lamports)?;
+            }
+            content.auction_day = auction_day.key();
+            content.winner = ctx.accounts.winner.key();
+            content.bond_lamports = bond_lamports;
+            content.bump = ctx.bumps.content;
+        }
+        content.content_hash = content_hash;
+        content.updated_at = now;
+
+        emit_cpi!(ContentSubmitted {
+            day_index,
+            winner: content.winner,
+            content_hash,
+            bond_lamports: content.bond_lamports,
+        });
+        Ok(())
+    }
+
+    // Once the display period is over, anyone may close an unslashed content
+    // PDA; the winner gets the bond back along with the rent.
+    pub fn release_content_bond(ctx: Context<ReleaseContentBond>, day_index: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            now >= content_display_end(auction_day),
+            ErrorCode::DisplayPeriodNotOver
+        );
+        let content = &ctx.accounts.content;
+        require!(!content.slashed, ErrorCode::ContentBondSlashed);
+
+        emit_cpi!(ContentBondSettled {
+            day_index,
+            winner: content.winner,
+            bond_lamports: content.bond_lamports,
+            slashed: false,
+        });
+        Ok(())
+    }
+
+    // Upholds a moderation flag during the display period: the bond goes to
+    // the recipient and the winner can no longer submit content for the day.
+    pub fn slash_content_bond(ctx: Context<SlashContentBond>, day_index: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            now < content_display_end(auction_day),
+            ErrorCode::DisplayPeriodOver
+        );
+        let recipient = &ctx.accounts.recipient;
+        require!(
+            recipient.key() == ctx.accounts.config.recipient_pubkey,
+            ErrorCode::RecipientMismatch
+        );
+
+        let content = &mut ctx.accounts.content;
+        require!(!content.slashed, ErrorCode::ContentBondSlashed);
+        let bond_lamports = content.bond_lamports;
+        content.to_account_info().sub_lamports(bond_lamports)?;
+        recipient.add_lamports(bond_lamports)?;
+        content.bond_lamports = 0;
+        content.slashed = true;
+
+        emit_cpi!(ContentBondSettled {
+            day_index,
+            winner: content.winner,
+            bond_lamports,
+            slashed: true,
+        });
+        Ok(())
+    }
+
+    // Once the display period is over, anyone may close a slashed content PDA;
+    // its bond is already gone, so the rent goes to the recipient.
+    pub fn close_slashed_content(ctx: Context<CloseSlashedContent>, day_index: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            now >= content_display_end(auction_day),
+            ErrorCode::DisplayPeriodNotOver
+        );
+        require!(ctx.accounts.content.slashed, ErrorCode::ContentBondNotSlashed);
+        Ok(())
+    }
+
+    // Adds to the signer's cranker bond, creating it on first use. Bonding
+    // again cancels a pending unbond.
+    pub fn bond_cranker(ctx: Context<BondCranker>, lamports: u64) -> Result<()> {
+        require!(lamports > 0, ErrorCode::InvalidBidAmount);
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.cranker.to_account_info(),
+                to: ctx.accounts.cranker_bond.to_account_info(),
+            },
+        );
+        transfer(cpi_ctx, lamports)?;
+
+        let cranker_bond = &mut ctx.accounts.cranker_bond;
+        cranker_bond.cranker = ctx.accounts.cranker.key();
+        cranker_bond.bonded_lamports = cranker_bond
+            .bonded_lamports
+            .checked_add(lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        cranker_bond.unbond_requested_at = 0;
+        cranker_bond.bump = ctx.bumps.cranker_bond;
+
+        emit_cpi!(CrankerBonded {
+            cranker: cranker_bond.cranker,
+            lamports,
+            bonded_lamports: cranker_bond.bonded_lamports,
+        });
+        Ok(())
+    }
+
+    // Starts the unbonding delay. The bond no longer qualifies the cranker
+    // for refunds but stays slashable until `withdraw_cranker_bond`.
+    pub fn request_unbond(ctx: Context<RequestUnbond>) -> Result<()> {
+        ctx.accounts.cranker_bond.unbond_requested_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    // Closes the bond back to the cranker once the unbonding delay is over.
+    pub fn withdraw_cranker_bond(ctx: Context<WithdrawCrankerBond>) -> Result<()> {
+        let cranker_bond = &ctx.accounts.cranker_bond;
+        require!(
+            cranker_bond.unbond_requested_at > 0
+                && Clock::get()?.unix_timestamp
+                    >= cranker_bond.unbond_requested_at + CRANKER_UNBOND_DELAY_SECONDS,
+            ErrorCode::UnbondPending
+        );
+        msg!(
+            "withdraw_cranker_bond: {} withdrew {}",
+            cranker_bond.cranker,
+            cranker_bond.bonded_lamports
+        );
+        Ok(())
+    }
+
+    // Permissionless health check for monitors: fails with
+    // `DayInvariantViolated` if a finalized day's books don't balance.
+    pub fn verify_day(ctx: Context<VerifyDay>, day_index: i64) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        check_day_invariants(auction_day, vault_principal(auction_day, &ctx.accounts.vault))
+    }
+
+    // Slashes the bond of the day's last refund cranker while the day fails
+    // `verify_day`. The slashed lamports go into the day's vault to cover
+    // the shortfall.
+    pub fn slash_cranker(ctx: Context<SlashCranker>, day_index: i64, lamports: u64) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            check_day_invariants(auction_day, vault_principal(auction_day, &ctx.accounts.vault))
+                .is_err(),
+            ErrorCode::DayInvariantsHold
+        );
+
+        let cranker_bond = &mut ctx.accounts.cranker_bond;
+        let slashed = lamports.min(cranker_bond.bonded_lamports);
+        cranker_bond.sub_lamports(slashed)?;
+        ctx.accounts.vault.add_lamports(slashed)?;
+        cranker_bond.bonded_lamports -= slashed;
+
+        emit_cpi!(CrankerSlashed {
+            day_index,
+            cranker: cranker_bond.cranker,
+            lamports: slashed,
+        });
+        Ok(())
+    }
+
+    // Permissionless: tops the vault's rent reserve up to the rent-exempt
+    // minimum, for vaults that were first funded by a bid rather than created
+    // with the day.
+    pub fn fund_vault_rent(ctx: Context<FundVaultRent>, day_index: i64) -> Result<()> {
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        let lamports = Rent::get()?
+            .minimum_balance(0)
+            .saturating_sub(auction_day.vault_rent_lamports);
+        if lamports == 0 {
+            msg!("fund_vault_rent: day {} vault rent already funded", day_index);
+            return Ok(());
+        }
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        );
+        transfer(cpi_ctx, lamports)?;
+        auction_day.vault_rent_lamports = auction_day
+            .vault_rent_lamports
+            .checked_add(lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit_cpi!(VaultRentFunded {
+            day_index,
+            payer: ctx.accounts.payer.key(),
+            lamports,
+        });
+        Ok(())
+    }
+
+    // Permissionless: pays the recipient whatever of a vesting day's escrowed
+    // proceeds has vested and not yet been claimed.
+    pub fn claim_vested(ctx: Context<ClaimVested>, day_index: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        require!(
+            ctx.accounts.recipient.key() == ctx.accounts.config.recipient_pubkey,
+            ErrorCode::RecipientMismatch
+        );
+        let lamports = auction_day
+            .vested_lamports(now)?
+            .saturating_sub(auction_day.vesting_claimed_lamports);
+        require!(lamports > 0, ErrorCode::NothingVested);
+        auction_day.vesting_claimed_lamports += lamports;
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &ctx.accounts.recipient,
+            &ctx.accounts.system_program,
+            lamports,
+        )?;
+
+        emit_cpi!(VestedClaimed {
+            day_index,
+            lamports,
+            claimed_lamports: auction_day.vesting_claimed_lamports,
+            total_lamports: auction_day.vesting_total_lamports,
+        });
+        Ok(())
+    }
+
+    // Pays a referrer their cut of one referred receipt's loser fee. Fees left
+    // unclaimed at `sweep_unclaimed` go to the recipient with the rest.
+    pub fn claim_referral_fee(
+        ctx: Context<ClaimReferralFee>,
+        day_index: i64,
+        bidder: Pubkey,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            !ctx.accounts.config.referral_window_lapsed(auction_day, now),
+            ErrorCode::ReferralClaimWindowClosed
+        );
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        let lamports = bid_receipt.referral_fee_lamports;
+        require!(lamports > 0, ErrorCode::NoReferralFee);
+        bid_receipt.referral_fee_lamports = 0;
+        auction_day.referral_fees_accrued = auction_day
+            .referral_fees_accrued
+            .checked_sub(lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &ctx.accounts.referrer,
+            &ctx.accounts.system_program,
+            lamports,
+        )?;
+
+        emit_cpi!(ReferralFeeClaimed {
+            day_index,
+            referrer: ctx.accounts.referrer.key(),
+            bidder,
+            lamports,
+        });
+        Ok(())
+    }
+
+    // Permissionless. Once a day's referral claim window has passed, pays the
+    // referral fees nobody claimed to the recipient.
+    pub fn sweep_referral_fees(ctx: Context<SweepReferralFees>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_SETTLEMENT)?;
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            ctx.accounts.config.referral_window_lapsed(auction_day, now),
+            ErrorCode::ReferralClaimWindowOpen
+        );
+        require_vault_owner(auction_day, &ctx.accounts.vault)?;
+        require!(
+            ctx.accounts.recipient.key() == ctx.accounts.config.recipient_pubkey,
+            ErrorCode::RecipientMismatch
+        );
+
+        let lamports = auction_day
+            .referral_fees_accrued
+            .min(vault_principal(auction_day, &ctx.accounts.vault));
+        transfer_from_vault(
+            auction_day,
+            &ctx.accounts.vault,
+            &ctx.accounts.recipient,
+            &ctx.accounts.system_program,
+            lamports,
+        )?;
+        auction_day.referral_fees_accrued = 0;
+
+        emit_cpi!(ReferralFeesSwept { day_index, lamports });
+        Ok(())
+    }
+
+    // Permissionless: creates the `Treasury` that holds settlement proceeds
+    // while `Config.treasury_custody` is on.
+    pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+        ctx.accounts.treasury.bump = ctx.bumps.treasury;
+        Ok(())
+    }
+
+    // Authority only. Pays custodied proceeds to `destination`; the account
+    // keeps its rent reserve.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        let available = treasury_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(Treasury::SPACE));
+        require!(amount <= available, ErrorCode::InsufficientTreasuryBalance);
+        treasury_info.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_withdrawn_lamports = treasury
+            .total_withdrawn_lamports
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit_cpi!(TreasuryWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            destination: ctx.accounts.destination.key(),
+            lamports: amount,
+            remaining_lamports: available - amount,
+        });
+        Ok(())
+    }
+
+    // SPL-token counterpart of `place_bid` for days whose `bid_mint` is set.
+    // Token days run the plain single-winner format; the day must already be
+    // initialized (`init_day`). Amounts are in the mint's base units.
+    pub fn place_bid_token(
+        ctx: Context<PlaceBidToken>,
+        day_index: i64,
+        new_amount: u64,
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        require!(new_amount > 0, ErrorCode::InvalidBidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            !auction_day.is_sealed()
+                && !auction_day.is_penny()
+                && !auction_day.is_candle()
+                && !auction_day.is_multi_winner(),
+            ErrorCode::TokenDayFormatUnsupported
+        );
+        require!(
+            !is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?,
+            ErrorCode::DayClosed
+        );
+        require!(auction_day.is_open_at(now), ErrorCode::WrongDay);
+
+        let bidder_count_before = auction_day.bidder_count;
+        let delta = apply_bid(
+            auction_day,
+            &mut ctx.accounts.bid_receipt,
+            Bidder {
+                key: ctx.accounts.bidder.key(),
+                mint: ctx.accounts.bid_mint.key(),
+                stats: None,
+                vetted: false,
+            },
+            &ctx.accounts.config,
+            None,
+            now,
+            new_amount,
+            &events,
+        )?;
+        if auction_day.bidder_count > bidder_count_before {
+            record_bid_index(
+                auction_day,
+                ctx.accounts.bid_index.as_deref(),
+                ctx.accounts.bidder.key(),
+                &ctx.accounts.bidder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
+        }
+        // The bidder covers any Token-2022 transfer fee, so the vault nets
+        // exactly what the bid added.
+        let gross = gross_for_net(&ctx.accounts.bid_mint, delta)?;
+        let vault_before = ctx.accounts.token_vault.amount;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bidder_token_account.to_account_info(),
+                mint: ctx.accounts.bid_mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(cpi_ctx, gross, ctx.accounts.bid_mint.decimals)?;
+        ctx.accounts.token_vault.reload()?;
+        require!(
+            ctx.accounts.token_vault.amount.saturating_sub(vault_before) >= delta,
+            ErrorCode::TransferFeeShortfall
+        );
+        Ok(())
+    }
+
+    // Token-day counterpart of `settle_day`: pays the winning bid to the
+    // recipient's token account and sizes the pools `refund_batch_token` draws on.
+    pub fn settle_day_token(ctx: Context<SettleDayToken>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_SETTLEMENT)?;
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            now >= auction_day.settleable_at() || auction_day.bidding_closed,
+            ErrorCode::TooEarly
+        );
+        require!(
+            ctx.accounts.token_vault.amount >= auction_day.total_bid_lamports,
+            ErrorCode::InsufficientVaultLamports
+        );
+
+        if auction_day.highest_bid == 0 {
+            auction_day.finalized = true;
+            emit_cpi!(DaySettled {
+                day_index,
+                winner: auction_day.winner,
+                winning_bid: 0,
+                bidder_count: auction_day.bidder_count,
+                refund_pool: 0,
+                fee_pool: 0,
+                display_payload: Vec::new(),
+                settled_at: now,
+                seq: auction_day.seq,
+            });
+            return Ok(());
+        }
+        let bidder_count = auction_day.bidder_count;
+        if bidder_count < auction_day.min_bidders {
+            cancel_auction_day(auction_day);
+            start_refund_window(auction_day, now)?;
+            emit_cpi!(DayCancelled {
+                day_index,
+                bidder_count,
+                refund_pool: auction_day.refund_pool_remaining,
+            });
+            return Ok(());
+        }
+
+        let (winning_total, winner_count, fee_pool, refund_pool) = settlement_pools(auction_day)?;
+        transfer_from_token_vault(
+            auction_day,
+            &ctx.accounts.bid_mint,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.recipient_token_account.to_account_info(),
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+            winning_total,
+        )?;
+        auction_day.refund_pool_remaining = refund_pool;
+        auction_day.fee_pool_remaining = fee_pool;
+        auction_day.finalized = true;
+        auction_day.refund_count_total = bidder_count
+            .checked_sub(winner_count)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.refund_count_completed = 0;
+        auction_day.claim_deadline_ts = content_display_end(auction_day);
+        start_refund_window(auction_day, now)?;
+
+        emit_cpi!(DaySettled {
+            day_index,
+            winner: auction_day.winner,
+            winning_bid: auction_day.highest_bid,
+            bidder_count,
+            refund_pool,
+            fee_pool,
+            display_payload: Vec::new(),
+            settled_at: now,
+            seq: auction_day.seq,
+        });
+        Ok(())
+    }
+
+    // Token-day counterpart of `refund_batch`. Remaining accounts are
+    // `(bid_receipt, bidder_token_account)` pairs. The cranker's fee share goes
+    // to `cranker_token_account` and the rest of each loser fee straight to the
+    // recipient's token account, so nothing waits for a sweep.
+    pub fn refund_batch_token<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBatchToken<'info>>,
+        day_index: i64,
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_REFUNDS)?;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2),
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let auction_day_key = auction_day.key();
+        let mut vault_amount = ctx.accounts.token_vault.amount;
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let (bid_receipt_info, bidder_token_info) = (&pair[0], &pair[1]);
+            let mut bid_receipt = Account::<BidReceipt>::try_from(bid_receipt_info)?;
+            require!(
+                bid_receipt.auction_day == auction_day_key,
+                ErrorCode::BidReceiptMismatch
+            );
+            if bid_receipt.refunded || auction_day.is_winner(&bid_receipt.bidder) {
+                continue;
+            }
+            let bidder_token_account = InterfaceAccount::<TokenAccount>::try_from(bidder_token_info)?;
+            require!(
+                bidder_token_account.owner == bid_receipt.bidder
+                    && bidder_token_account.mint == auction_day.bid_mint,
+                ErrorCode::TokenAccountMismatch
+            );
+
+            let treasury_before = auction_day.treasury_fees_accrued;
+            let (refund_amount, cranker_fee) =
+                refund_loser_receipt(auction_day, &mut bid_receipt, vault_amount, &events)?;
+            let treasury_fee = auction_day.treasury_fees_accrued - treasury_before;
+            auction_day.treasury_fees_accrued = treasury_before;
+            bid_receipt.exit(ctx.program_id)?;
+
+            for (to, amount) in [
+                (bidder_token_info.clone(), refund_amount),
+                (ctx.accounts.cranker_token_account.to_account_info(), cranker_fee),
+                (ctx.accounts.recipient_token_account.to_account_info(), treasury_fee),
+            ] {
+                if amount > 0 {
+                    transfer_from_token_vault(
+                        auction_day,
+                        &ctx.accounts.bid_mint,
+                        &ctx.accounts.token_vault,
+                        &to,
+                        &ctx.accounts.vault,
+                        &ctx.accounts.token_program,
+                        amount,
+                    )?;
+                }
+            }
+            vault_amount = vault_amount
+                .checked_sub(refund_amount + cranker_fee + treasury_fee)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    // Bid on a multi-currency day in any whitelisted mint. `new_amount` is the
+    // bid's total value in micro-USD; the base units for the increase are priced
+    // from the mint's Pyth feed at bid time. A receipt keeps its first mint.
+    pub fn place_bid_multi(
+        ctx: Context<PlaceBidMulti>,
+        day_index: i64,
+        new_amount: u64,
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_BIDDING)?;
+        require!(new_amount > 0, ErrorCode::InvalidBidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            auction_day.is_multi_currency(),
+            ErrorCode::BidCurrencyMismatch
+        );
+        require!(
+            !auction_day.is_sealed()
+                && !auction_day.is_penny()
+                && !auction_day.is_candle()
+                && !auction_day.is_multi_winner(),
+            ErrorCode::TokenDayFormatUnsupported
+        );
+        require!(
+            !is_day_closed(&ctx.accounts.schedule_override, ctx.program_id)?,
+            ErrorCode::DayClosed
+        );
+        require!(auction_day.is_open_at(now), ErrorCode::WrongDay);
+
+        let mint = ctx.accounts.bid_mint.key();
+        let quote = auction_day
+            .quote_mint(&mint)
+            .ok_or(ErrorCode::BidCurrencyMismatch)?;
+        require_keys_eq!(
+            ctx.accounts.price_feed.key(),
+            quote.price_feed,
+            ErrorCode::UsdPriceFeedMismatch
+        );
+        let price = PythPrice::try_from_account(&ctx.accounts.price_feed)?;
+        require!(
+            now.saturating_sub(price.publish_time) <= ctx.accounts.config.usd_max_staleness_seconds,
+            ErrorCode::UsdPriceStale
+        );
+        let bid_receipt = &mut ctx.accounts.bid_receipt;
+        require!(
+            bid_receipt.bidder == Pubkey::default() || bid_receipt.mint == mint,
+            ErrorCode::BidCurrencyMismatch
+        );
+
+        let bidder_count_before = auction_day.bidder_count;
+        let delta = apply_bid(
+            auction_day,
+            bid_receipt,
+            Bidder {
+                key: ctx.accounts.bidder.key(),
+                mint,
+                stats: None,
+                vetted: false,
+            },
+            &ctx.accounts.config,
+            None,
+            now,
+            new_amount,
+            &events,
+        )?;
+        if auction_day.bidder_count > bidder_count_before {
+            record_bid_index(
+                auction_day,
+                ctx.accounts.bid_index.as_deref(),
+                ctx.accounts.bidder.key(),
+                &ctx.accounts.bidder.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
+        }
+        let native_delta = price.units_for_micro_usd(delta, ctx.accounts.bid_mint.decimals)?;
+        bid_receipt.mint = mint;
+        bid_receipt.native_amount = bid_receipt
+            .native_amount
+            .checked_add(native_delta)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let gross = gross_for_net(&ctx.accounts.bid_mint, native_delta)?;
+        let vault_before = ctx.accounts.token_vault.amount;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bidder_token_account.to_account_info(),
+                mint: ctx.accounts.bid_mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(cpi_ctx, gross, ctx.accounts.bid_mint.decimals)?;
+        ctx.accounts.token_vault.reload()?;
+        require!(
+            ctx.accounts.token_vault.amount.saturating_sub(vault_before) >= native_delta,
+            ErrorCode::TransferFeeShortfall
+        );
+        Ok(())
+    }
+
+    // Multi-currency counterpart of `settle_day_token`. With a winner, the
+    // remaining accounts are `[winner_receipt, bid_mint, token_vault,
+    // recipient_token_account]` for the winner's mint; the winning deposit is
+    // paid out whole. Loser fees reach the recipient per mint in `refund_batch_multi`.
+    pub fn settle_day_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleDayMulti<'info>>,
+        day_index: i64,
+    ) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_SETTLEMENT)?;
+        let now = Clock::get()?.unix_timestamp;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            auction_day.is_multi_currency(),
+            ErrorCode::BidCurrencyMismatch
+        );
+        require!(
+            now >= auction_day.settleable_at() || auction_day.bidding_closed,
+            ErrorCode::TooEarly
+        );
+
+        if auction_day.highest_bid == 0 {
+            auction_day.finalized = true;
+            emit_cpi!(DaySettled {
+                day_index,
+                winner: auction_day.winner,
+                winning_bid: 0,
+                bidder_count: auction_day.bidder_count,
+                refund_pool: 0,
+                fee_pool: 0,
+                display_payload: Vec::new(),
+                settled_at: now,
+                seq: auction_day.seq,
+            });
+            return Ok(());
+        }
+        let bidder_count = auction_day.bidder_count;
+        if bidder_count < auction_day.min_bidders {
+            cancel_auction_day(auction_day);
+            start_refund_window(auction_day, now)?;
+            emit_cpi!(DayCancelled {
+                day_index,
+                bidder_count,
+                refund_pool: auction_day.refund_pool_remaining,
+            });
+            return Ok(());
+        }
+
+        let [winner_receipt_info, bid_mint_info, token_vault_info, recipient_info] =
+            ctx.remaining_accounts
+        else {
+            return err!(ErrorCode::InvalidRemainingAccounts);
+        };
+        let auction_day_key = auction_day.key();
+        let winner_receipt = Account::<BidReceipt>::try_from(winner_receipt_info)?;
+        require!(
+            winner_receipt.auction_day == auction_day_key
+                && auction_day.is_winner(&winner_receipt.bidder),
+            ErrorCode::BidReceiptMismatch
+        );
+        let bid_mint = InterfaceAccount::<Mint>::try_from(bid_mint_info)?;
+        require_keys_eq!(
+            bid_mint.key(),
+            winner_receipt.mint,
+            ErrorCode::BidCurrencyMismatch
+        );
+        let token_vault = InterfaceAccount::<TokenAccount>::try_from(token_vault_info)?;
+        let (token_vault_key, _) = Pubkey::find_program_address(
+            &[b"token_vault", auction_day_key.as_ref(), bid_mint.key().as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            token_vault.key(),
+            token_vault_key,
+            ErrorCode::TokenAccountMismatch
+        );
+        let recipient_token_account = InterfaceAccount::<TokenAccount>::try_from(recipient_info)?;
+        require!(
+            recipient_token_account.owner == ctx.accounts.config.recipient_pubkey
+                && recipient_token_account.mint == bid_mint.key(),
+            ErrorCode::TokenAccountMismatch
+        );
+
+        let (_, winner_count, fee_pool, refund_pool) = settlement_pools(auction_day)?;
+        transfer_from_token_vault(
+            auction_day,
+            &bid_mint,
+            &token_vault,
+            recipient_info,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+            winner_receipt.native_amount,
+        )?;
+        auction_day.refund_pool_remaining = refund_pool;
+        auction_day.fee_pool_remaining = fee_pool;
+        auction_day.finalized = true;
+        auction_day.refund_count_total = bidder_count
+            .checked_sub(winner_count)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.refund_count_completed = 0;
+        auction_day.claim_deadline_ts = content_display_end(auction_day);
+        start_refund_window(auction_day, now)?;
+
+        emit_cpi!(DaySettled {
+            day_index,
+            winner: auction_day.winner,
+            winning_bid: auction_day.highest_bid,
+            bidder_count,
+            refund_pool,
+            fee_pool,
+            display_payload: Vec::new(),
+            settled_at: now,
+            seq: auction_day.seq,
+        });
+        Ok(())
+    }
+
+    // Multi-currency counterpart of `refund_batch_token` for the receipts of
+    // one mint. Pool math runs in micro-USD; each payout is converted back to
+    // base units at the receipt's own deposit rate, so a receipt never pays
+    // out more than it deposited.
+    pub fn refund_batch_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBatchMulti<'info>>,
+        day_index: i64,
+    ) -> Result<()> {
+        let events = EventSink::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+        require_not_paused(&ctx.accounts.config, PAUSE_REFUNDS)?;
+        let auction_day = &mut ctx.accounts.auction_day;
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(
+            auction_day.is_multi_currency(),
+            ErrorCode::BidCurrencyMismatch
+        );
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2),
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        let auction_day_key = auction_day.key();
+        let mint = ctx.accounts.bid_mint.key();
+        let mut vault_amount = ctx.accounts.token_vault.amount;
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let (bid_receipt_info, bidder_token_info) = (&pair[0], &pair[1]);
+            let mut bid_receipt = Account::<BidReceipt>::try_from(bid_receipt_info)?;
+            require!(
+                bid_receipt.auction_day == auction_day_key,
+                ErrorCode::BidReceiptMismatch
+            );
+            require_keys_eq!(bid_receipt.mint, mint, ErrorCode::BidCurrencyMismatch);
+            if bid_receipt.refunded || auction_day.is_winner(&bid_receipt.bidder) {
+                continue;
+            }
+            let bidder_token_account = InterfaceAccount::<TokenAccount>::try_from(bidder_token_info)?;
+            require!(
+                bidder_token_account.owner == bid_receipt.bidder
+                    && bidder_token_account.mint == mint,
+                ErrorCode::TokenAccountMismatch
+            );
+
+            let value = bid_receipt.amount;
+            let native = bid_receipt.native_amount;
+            let treasury_before = auction_day.treasury_fees_accrued;
+            // The receipt's own value stands in for the vault here; base units
+            // are checked against the token vault below.
+            let (refund_amount, cranker_fee) =
+                refund_loser_receipt(auction_day, &mut bid_receipt, value, &events)?;
+            let treasury_fee = auction_day.treasury_fees_accrued - treasury_before;
+            auction_day.treasury_fees_accrued = treasury_before;
+            bid_receipt.exit(ctx.program_id)?;
+
+            let fees = native_share(native, value, cranker_fee + treasury_fee)?;
+            let cranker_native = native_share(native, value, cranker_fee)?;
+            let refund_native =
+                native_share(native, value, refund_amount + cranker_fee + treasury_fee)? - fees;
+            for (to, amount) in [
+                (bidder_token_info.clone(), refund_native),
+                (ctx.accounts.cranker_token_account.to_account_info(), cranker_native),
+                (ctx.accounts.recipient_token_account.to_account_info(), fees - cranker_native),
+            ] {
+                if amount > 0 {
+                    transfer_from_token_vault(
+                        auction_day,
+                        &ctx.accounts.bid_mint,
+                        &ctx.accounts.token_vault,
+                        &to,
+                        &ctx.accounts.vault,
+                        &ctx.accounts.token_program,
+                        amount,
+                    )?;
+                }
+            }
+            vault_amount = vault_amount
+                .checked_sub(refund_native + fees)
+                .ok_or(ErrorCode::InsufficientVaultLamports)?;
+        }
+        Ok(())
+    }
+
+    // Escrows an NFT (amount 1) or any SPL amount for the day's winner before
+    // the day settles. One prize per day; the day's vault PDA holds it.
+    pub fn deposit_prize(ctx: Context<DepositPrize>, day_index: i64, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidPrizeAmount);
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.prize_mint.to_account_info(),
+                to: ctx.accounts.prize_vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.prize_mint.decimals)?;
+        // Token-2022 transfer fees come out of the escrow; record what arrived.
+        ctx.accounts.prize_vault.reload()?;
+        let received = ctx.accounts.prize_vault.amount;
+
+        let prize = &mut ctx.accounts.prize;
+        prize.auction_day = auction_day.key();
+        prize.mint = ctx.accounts.prize_mint.key();
+        prize.amount = received;
+        prize.depositor = ctx.accounts.authority.key();
+        prize.claimed = false;
+        prize.bump = ctx.bumps.prize;
+        prize.reclaimed = false;
+
+        emit_cpi!(PrizeDeposited {
+            day_index,
+            mint: prize.mint,
+            amount: received,
+        });
+        Ok(())
+    }
+
+    // The settled day's winner withdraws the escrowed prize.
+    pub fn claim_prize(ctx: Context<ClaimPrize>, day_index: i64) -> Result<()> {
+        require_not_paused(&ctx.accounts.config, PAUSE_CLAIMS)?;
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            !auction_day.cancelled && auction_day.winner == ctx.accounts.winner.key(),
+            ErrorCode::Unauthorized
+        );
+        require!(!ctx.accounts.prize.claimed, ErrorCode::PrizeAlreadyClaimed);
+
+        transfer_from_token_vault(
+            auction_day,
+            &ctx.accounts.prize_mint,
+            &ctx.accounts.prize_vault,
+            &ctx.accounts.winner_token_account.to_account_info(),
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+            ctx.accounts.prize.amount,
+        )?;
+        let prize = &mut ctx.accounts.prize;
+        prize.claimed = true;
+
+        emit_cpi!(PrizeClaimed {
+            day_index,
+            winner: ctx.accounts.winner.key(),
+            mint: prize.mint,
+            amount: prize.amount,
+        });
+        Ok(())
+    }
+
+    // Returns an unclaimed prize to the authority. Days without a winner give
+    // it back as soon as they finalize; otherwise the winner keeps it for
+    // `prize_claim_window_days` after the day ends.
+    pub fn reclaim_prize(ctx: Context<ReclaimPrize>, day_index: i64) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(!ctx.accounts.prize.claimed, ErrorCode::PrizeAlreadyClaimed);
+        let has_winner = !auction_day.cancelled && auction_day.winner != Pubkey::default();
+        require!(
+            !has_winner
+                || ctx
+                    .accounts
+                    .config
+                    .claim_window_lapsed(auction_day, Clock::get()?.unix_timestamp),
+            ErrorCode::ClaimWindowOpen
+        );
+
+        transfer_from_token_vault(
+            auction_day,
+            &ctx.accounts.prize_mint,
+            &ctx.accounts.prize_vault,
+            &ctx.accounts.authority_token_account.to_account_info(),
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+            ctx.accounts.prize.amount,
+        )?;
+        let prize = &mut ctx.accounts.prize;
+        prize.claimed = true;
+        prize.reclaimed = true;
+
+        emit_cpi!(PrizeReclaimed {
+            day_index,
+            authority: ctx.accounts.authority.key(),
+            mint: prize.mint,
+            amount: prize.amount,
+        });
+        Ok(())
+    }
+
+    // Mints the settled day's winner a one-of-one Token-2022 trophy. Its
+    // metadata extension records the day and the winning bid. Anyone may pay
+    // for it; the mint is the `["trophy", auction_day]` PDA, so a day has at
+    // most one, and its mint authority is dropped after the single token.
+    pub fn mint_trophy(ctx: Context<MintTrophy>, day_index: i64) -> Result<()> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(
+            !auction_day.cancelled && auction_day.winner != Pubkey::default(),
+            ErrorCode::Unauthorized
+        );
+
+        let auction_day_key = auction_day.key();
+        let seeds: &[&[u8]] = &[b"trophy", auction_day_key.as_ref(), &[ctx.bumps.trophy_mint]];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+        let mint_info = ctx.accounts.trophy_mint.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let metadata = TokenMetadata {
+            name: format!("Daily Auction #{}", day_index),
+            symbol: TROPHY_SYMBOL.to_string(),
+            additional_metadata: vec![
+                ("day_index".to_string(), day_index.to_string()),
+                ("winning_bid".to_string(), auction_day.highest_bid.to_string()),
+            ],
+            ..Default::default()
+        };
+
+        // Token-2022 grows the mint for the metadata but does not fund the rent.
+        let required = Rent::get()?.minimum_balance(mint_info.data_len() + metadata.tlv_size_of()?);
+        let top_up = required.saturating_sub(mint_info.lamports());
+        if top_up > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: mint_info.clone(),
+                },
+            );
+            transfer(cpi_ctx, top_up)?;
+        }
+        token_interface::token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataInitialize {
+                    program_id: token_program.clone(),
+                    metadata: mint_info.clone(),
+                    update_authority: mint_info.clone(),
+                    mint_authority: mint_info.clone(),
+                    mint: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        )?;
+        for (key, value) in metadata.additional_metadata {
+            token_interface::token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TokenMetadataUpdateField {
+                        program_id: token_program.clone(),
+                        metadata: mint_info.clone(),
+                        update_authority: mint_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                Field::Key(key),
+                value,
+            )?;
+        }
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                MintTo {
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.winner_token_account.to_account_info(),
+                    authority: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                token_program,
+                SetAuthority {
+                    current_authority: mint_info.clone(),
+                    account_or_mint: mint_info,
+                },
+                signer_seeds,
+            ),
+            spl_token_2022::instruction::AuthorityType::MintTokens,
+            None,
+        )?;
+
+        emit_cpi!(TrophyMinted {
+            day_index,
+            winner: auction_day.winner,
+            mint: ctx.accounts.trophy_mint.key(),
+            winning_bid: auction_day.highest_bid,
+        });
+        Ok(())
+    }
+
+    // The day's owner posts (or replaces) its on-chain message for frontends
+    // to render. An empty message clears it. Until the trophy is minted the
+    // owner is the settled winner; after that it is whoever holds the trophy,
+    // so the slot can be transferred or sold before posting. If the owner
+    // posts nothing within `prize_claim_window_days`, the authority takes the
+    // slot over for good.
+    pub fn set_message(
+        ctx: Context<SetMessage>,
+        day_index: i64,
+        message: String,
+        uri: String,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            message.len() <= ctx.accounts.config.winner_message_limit(),
+            ErrorCode::WinnerMessageTooLong
+        );
+        require!(
+            uri.len() <= WINNER_MESSAGE_URI_MAX_LEN,
+            ErrorCode::WinnerMessageUriTooLong
+        );
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        require!(auction_day.finalized, ErrorCode::NotFinalized);
+        require!(!auction_day.cancelled, ErrorCode::Unauthorized);
+        let owner = ctx.accounts.owner.key();
+        let winner_message = &ctx.accounts.winner_message;
+        let now = Clock::get()?.unix_timestamp;
+        if owner == ctx.accounts.config.authority
+            && (winner_message.reassigned
+                || (winner_message.updated_at == 0
+                    && ctx.accounts.config.claim_window_lapsed(auction_day, now)))
+        {
+            ctx.accounts.winner_message.reassigned = true;
+        } else if winner_message.reassigned {
+            return err!(ErrorCode::Unauthorized);
+        } else if ctx.accounts.trophy_mint.data_is_empty() {
+            require!(auction_day.winner == owner, ErrorCode::Unauthorized);
+        } else {
+            let deed = ctx
+                .accounts
+                .deed_token_account
+                .as_ref()
+                .ok_or(ErrorCode::DayDeedRequired)?;
+            require!(
+                deed.mint == ctx.accounts.trophy_mint.key() && deed.owner == owner && deed.amount == 1,
+                ErrorCode::Unauthorized
+            );
+        }
+
+        // Grow the account for longer content; the owner funds the extra
+        // rent. It never shrinks, so switching back to short text is free.
+        let space = WinnerMessage::space_for(message.len(), uri.len());
+        let message_info = ctx.accounts.winner_message.to_account_info();
+        if space > message_info.data_len() {
+            let top_up = Rent::get()?
+                .minimum_balance(space)
+                .saturating_sub(message_info.lamports());
+            if top_up > 0 {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: message_info.clone(),
+                    },
+                );
+                transfer(cpi_ctx, top_up)?;
+            }
+            message_info.resize(space)?;
+        }
+
+        let winner_message = &mut ctx.accounts.winner_message;
+        winner_message.auction_day = auction_day.key();
+        winner_message.winner = owner;
+        winner_message.message = message;
+        winner_message.updated_at = now;
+        winner_message.bump = ctx.bumps.winner_message;
+        winner_message.uri = uri;
+        winner_message.content_hash = content_hash;
+
+        emit_cpi!(WinnerMessageSet {
+            day_index,
+            winner: winner_message.winner,
+            message: winner_message.message.clone(),
+            updated_at: now,
+            uri: winner_message.uri.clone(),
+            content_hash,
+        });
+        Ok(())
+    }
+
+    // Hides or restores a day's winner message without deleting it. The
+    // hidden flag survives later `set_message` calls.
+    pub fn moderate_message(
+        ctx: Context<ModerateMessage>,
+        day_index: i64,
+        hidden: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.auction_day.day_index == day_index,
+            ErrorCode::DayMismatch
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let winner_message = &mut ctx.accounts.winner_message;
+        winner_message.hidden = hidden;
+        winner_message.moderated_at = now;
+
+        emit_cpi!(WinnerMessageModerated {
+            day_index,
+            winner: winner_message.winner,
+            hidden,
+            moderated_at: now,
+        });
+        Ok(())
+    }
+
+    // Adds to the jackpot reserve that quiet days roll into the pot. Anyone
+    // may seed it.
+    pub fn fund_jackpot(ctx: Context<FundJackpot>, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.jackpot.to_account_info(),
+            },
+        );
+        transfer(cpi_ctx, amount)?;
+        let jackpot = &mut ctx.accounts.jackpot;
+        jackpot.reserve_lamports = jackpot
+            .reserve_lamports
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Read-only; meant to be simulated. Returns the amount the next bid must
+    // reach (the same `required_next_bid` the bid path checks) and the current
+    // leader, so clients don't replicate the increment math.
+    pub fn get_min_next_bid(ctx: Context<GetMinNextBid>, day_index: i64) -> Result<MinNextBid> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        Ok(MinNextBid {
+            day_index,
+            min_next_bid: required_next_bid(auction_day)?,
+            exact: auction_day.is_penny(),
+            leader: auction_day.winner,
+            highest_bid: auction_day.highest_bid,
+            bidding_open: !auction_day.finalized && !auction_day.bidding_closed,
+        })
+    }
+
+    // Read-only snapshot of a day for thin clients (via simulation) and other
+    // programs (via CPI return data), so neither decodes `AuctionDay` by hand.
+    pub fn get_day_status(ctx: Context<GetDayStatus>, day_index: i64) -> Result<DayStatus> {
+        let auction_day = &ctx.accounts.auction_day;
+        require!(auction_day.day_index == day_index, ErrorCode::DayMismatch);
+        Ok(DayStatus {
+            day_index,
+            start_ts: auction_day.start_ts,
+            end_ts: auction_day.end_ts,
+            bidding_closed: auction_day.bidding_closed,
+            finalized: auction_day.finalized,
+            cancelled: auction_day.cancelled,
+            winner: auction_day.winner,
+            highest_bid: auction_day.highest_bid,
+            bidder_count: auction_day.bidder_count,
+            total_bid_lamports: auction_day.total_bid_lamports,
+            refund_count_total: auction_day.refund_count_total,
+            refund_count_completed: auction_day.refund_count_completed,
+            refund_pool_remaining: auction_day.refund_pool_remaining,
+            fee_pool_remaining: auction_day.fee_pool_remaining,
+            seq: auction_day.seq,
+        })
+    }
+}
+
+// A day's winner is displayed for the whole following day.
+fn content_display_end(auction_day: &AuctionDay) -> i64 {
+    auction_day.start_ts.saturating_add(2 * SECONDS_PER_DAY)
+}
+
+// Price a day cleared at: the winning bid, or the lowest winning bid on
+// multi-winner days. Days without a sale have none.
+fn clearing_price(auction_day: &AuctionDay) -> Option<u64> {
+    if auction_day.cancelled {
+        return None;
+    }
+    auction_day.winning_bids().min()
+}
+
+fn price_series_slot(day_index: i64) -> usize {
+    day_index.rem_euclid(PRICE_SERIES_LEN as i64) as usize
+}
+
+fn claim_reminder_level(claim_deadline_ts: i64, now: i64) -> u8 {
+    if now >= claim_deadline_ts {
+        return CLAIM_REMINDER_EXPIRED;
+    }
+    CLAIM_REMINDER_LEADS
+        .iter()
+        .filter(|lead| now >= claim_deadline_ts.saturating_sub(**lead))
+        .count() as u8
+}
+
+fn require_not_paused(config: &Config, switches: u64) -> Result<()> {
+    require!(
+        config.paused_instructions & switches == 0,
+        ErrorCode::InstructionPaused
+    );
+    Ok(())
+}
+
+// With `min_cranker_bond_lamports` set, refund crankers need a bond of at
+// least that much with no unbond pending.
+fn require_cranker_bond(config: &Config, cranker_bond: Option<&CrankerBond>) -> Result<()> {
+    if config.min_cranker_bond_lamports == 0 {
+        return Ok(());
+    }
+    require!(
+        cranker_bond.is_some_and(|bond| {
+            bond.bonded_lamports >= config.min_cranker_bond_lamports
+                && bond.unbond_requested_at == 0
+        }),
+        ErrorCode::CrankerBondRequired
+    );
+    Ok(())
+}
+
+// Names `cranker` as the day's slash target. Only a call that paid a refund
+// counts, and only while bonds are required, so `slash_cranker` always finds
+// a bond behind `last_refund_cranker` and nobody can pin it on someone else
+// with an empty or replayed crank.
+fn record_refund_cranker(
+    config: &Config,
+    auction_day: &mut AuctionDay,
+    cranker: &Pubkey,
+    completed_before: u32,
+) {
+    if config.min_cranker_bond_lamports > 0
+        && auction_day.refund_count_completed > completed_before
+    {
+        auction_day.last_refund_cranker = *cranker;
+    }
+}
+
+// Books a finalized day must keep: refunds never outrun their count, and
+// until the sweep the vault still covers both pools and the treasury fees.
+fn check_day_invariants(auction_day: &AuctionDay, vault_lamports: u64) -> Result<()> {
+    require!(
+        auction_day.refund_count_completed <= auction_day.refund_count_total,
+        ErrorCode::DayInvariantViolated
+    );
+    // Vesting proceeds and referral fees outlive the sweep; everything else
+    // is owed until it.
+    let mut owed = auction_day
+        .vesting_total_lamports
+        .checked_sub(auction_day.vesting_claimed_lamports)
+        .and_then(|total| total.checked_add(auction_day.referral_fees_accrued))
+        .ok_or(ErrorCode::DayInvariantViolated)?;
+    if !auction_day.swept {
+        owed = owed
+            .checked_add(auction_day.refund_pool_remaining)
+            .and_then(|total| total.checked_add(auction_day.fee_pool_remaining))
+            .and_then(|total| total.checked_add(auction_day.treasury_fees_accrued))
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    require!(vault_lamports >= owed, ErrorCode::DayInvariantViolated);
+    Ok(())
+}
+
+// Open mode accepts any cranker; permissioned mode only the registered ones.
+fn require_registered_cranker(
+    config: &Config,
+    cranker_registry: Option<&CrankerRegistry>,
+    cranker: &Pubkey,
+) -> Result<()> {
+    if !config.permissioned_refunds {
+        return Ok(());
+    }
+    require!(
+        cranker_registry.is_some_and(|registry| registry.crankers.contains(cranker)),
+        ErrorCode::CrankerNotRegistered
+    );
+    Ok(())
+}
+
+// Days roll over at local midnight for `day_offset_seconds` (UTC when 0).
+fn current_day_index(clock: &Clock, config: &Config) -> i64 {
+    clock
+        .unix_timestamp
+        .saturating_add(config.day_offset_seconds)
+        .div_euclid(SECONDS_PER_DAY)
+}
+
+const fn parse_version_part(part: &str) -> u16 {
+    let bytes = part.as_bytes();
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        value = value * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+    value
+}
+
+fn program_capabilities() -> u64 {
+    let capabilities = CAPABILITY_IDEMPOTENCY_KEYS
+        | CAPABILITY_BPS_FEES
+        | CAPABILITY_VAULT_MIGRATION
+        | CAPABILITY_DESTRUCTIVE_ACTION_DELAY
+        | CAPABILITY_DISPLAY_HOOK
+        | CAPABILITY_BUY_NOW
+        | CAPABILITY_SOFT_CLOSE
+        | CAPABILITY_DAY_COMPLETE
+        | CAPABILITY_PRIORITY_LANE
+        | CAPABILITY_PRICE_FLOOR
+        | CAPABILITY_DAY_OFFSET
+        | CAPABILITY_CONTENT_MANAGER
+        | CAPABILITY_SCHEDULE_OVERRIDE
+        | CAPABILITY_CLOSE_RECEIPTS
+        | CAPABILITY_SEALED_BIDS
+        | CAPABILITY_BID_NETTING
+        | CAPABILITY_SCHEDULED_BIDS
+        | CAPABILITY_PENNY_AUCTION
+        | CAPABILITY_FOLLOWERS
+        | CAPABILITY_CANDLE_AUCTION
+        | CAPABILITY_EARLY_BIRD_DISCOUNT
+        | CAPABILITY_EXPOSURE_CAP
+        | CAPABILITY_MULTI_WINNER
+        | CAPABILITY_CONTENT_BOND
+        | CAPABILITY_REFUND_TIP
+        | CAPABILITY_VERIFY_WINNER
+        | CAPABILITY_WITHDRAW_BID
+        | CAPABILITY_PAUSE_SWITCHES
+        | CAPABILITY_PROMO_BIDS
+        | CAPABILITY_BUNDLE_BIDS
+        | CAPABILITY_DAY_CATEGORIES
+        | CAPABILITY_CLAIM_REMINDERS
+        | CAPABILITY_MIN_BIDDERS
+        | CAPABILITY_CANCEL_DAY
+        | CAPABILITY_PRICE_SERIES
+        | CAPABILITY_CLAIM_REFUND
+        | CAPABILITY_SWEEP_UNCLAIMED
+        | CAPABILITY_REFUND_VOUCHERS
+        | CAPABILITY_PARTICIPATION_CAP
+        | CAPABILITY_CLOSE_BID_RECEIPT
+        | CAPABILITY_COUNTER_RAISE
+        | CAPABILITY_CLOSE_DAY
+        | CAPABILITY_PARAM_HISTORY
+        | CAPABILITY_BID_INDEX
+        | CAPABILITY_REFUND_CURSOR
+        | CAPABILITY_REFUND_PROOFS
+        | CAPABILITY_SETTLE_REWARD
+        | CAPABILITY_CRANKER_REGISTRY
+        | CAPABILITY_CRANKER_FEE_SHARE
+        | CAPABILITY_CRANKER_BONDS
+        | CAPABILITY_VAULT_SURPLUS
+        | CAPABILITY_VAULT_RENT
+        | CAPABILITY_STREAK_BONUS
+        | CAPABILITY_REFERRALS
+        | CAPABILITY_JACKPOT
+        | CAPABILITY_RECIPIENT_SPLITS
+        | CAPABILITY_FEE_BURN
+        | CAPABILITY_PROTOCOL_RAKE
+        | CAPABILITY_TREASURY
+        | CAPABILITY_VESTING
+        | CAPABILITY_TOKEN_BIDDING;
+    let capabilities = if cfg!(feature = "compute-log") {
+        capabilities | CAPABILITY_COMPUTE_LOG
+    } else {
+        capabilities
+    };
+    if cfg!(feature = "devnet-faucet") {
+        capabilities | CAPABILITY_DEVNET_FAUCET
+    } else {
+        capabilities
+    }
+}
+
+// Capabilities past bit 63, once `capabilities` ran out of bits.
+fn program_capabilities_ext() -> u64 {
+    CAPABILITY_EXT_TOKEN_2022
+        | CAPABILITY_EXT_WSOL_BIDS
+        | CAPABILITY_EXT_USD_MINIMUMS
+        | CAPABILITY_EXT_MULTI_CURRENCY
+        | CAPABILITY_EXT_PRIZE_ESCROW
+        | CAPABILITY_EXT_TROPHY_NFT
+        | CAPABILITY_EXT_CNFT_RECEIPTS
+        | CAPABILITY_EXT_WINNER_MESSAGE
+        | CAPABILITY_EXT_MESSAGE_MODERATION
+        | CAPABILITY_EXT_MESSAGE_URI
+        | CAPABILITY_EXT_DAY_DEED
+        | CAPABILITY_EXT_DAY_META
+        | CAPABILITY_EXT_PRIZE_RECLAIM
+        | CAPABILITY_EXT_BID_GATE
+        | CAPABILITY_EXT_ACCESS_LIST
+        | CAPABILITY_EXT_WALLET_BID_CAP
+        | CAPABILITY_EXT_BID_COOLDOWN
+        | CAPABILITY_EXT_REFERRAL_CLAIM_WINDOW
+}
+
+// A missing `AuthorizationEpoch` PDA means the owner never revoked: epoch 0.
+fn current_authorization_epoch(account: &Option<Account<AuthorizationEpoch>>) -> u64 {
+    account.as_ref().map_or(0, |authorization_epoch| authorization_epoch.epoch)
+}
+
+// Use-time variant: the PDA is a required account so a third party cannot
+// skip a revocation by omitting it.
+fn authorization_epoch_of(account: &AccountInfo, program_id: &Pubkey) -> Result<u64> {
+    if account.owner != program_id || account.data_is_empty() {
+        return Ok(0);
+    }
+    let authorization_epoch = AuthorizationEpoch::try_deserialize(&mut &account.data.borrow()[..])?;
+    Ok(authorization_epoch.epoch)
+}
+
+// `init_if_needed` for PDAs passed as remaining accounts: creates the account
+// (funded by `payer`) and writes `T`'s discriminator when it does not exist yet,
+// leaving every other byte zeroed.
+fn ensure_program_account<'info, T: Discriminator>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<()> {
+    if info.owner == program_id {
+        return Ok(());
+    }
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    let required = Rent::get()?.minimum_balance(space);
+    let current = info.lamports();
+    if current == 0 {
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+                signer_seeds,
+            ),
+            required,
+            space as u64,
+            program_id,
+        )?;
+    } else {
+        if current < required {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: info.clone(),
+                    },
+                ),
+                required - current,
+            )?;
+        }
+        anchor_lang::system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Allocate {
+                    account_to_allocate: info.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Assign {
+                    account_to_assign: info.clone(),
+                },
+                signer_seeds,
+            ),
+            program_id,
+        )?;
+    }
+    info.try_borrow_mut_data()?[..8].copy_from_slice(T::DISCRIMINATOR);
+    Ok(())
+}
+
+// Appends `bidder` to the day's on-chain bidder list at the next ordinal, so
+// crankers can walk every receipt without an indexer.
+fn record_bid_index<'info>(
+    auction_day: &mut Account<'info, AuctionDay>,
+    bid_index: Option<&AccountInfo<'info>>,
+    bidder: Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let bid_index = bid_index.ok_or(ErrorCode::BidIndexRequired)?;
+    let auction_day_key = auction_day.key();
+    let ordinal = auction_day.bid_index_count;
+    let ordinal_seed = ordinal.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"bid_index", auction_day_key.as_ref(), &ordinal_seed],
+        program_id,
+    );
+    require_keys_eq!(bid_index.key(), expected, ErrorCode::BidIndexMismatch);
+    ensure_program_account::<BidIndex>(
+        bid_index,
+        payer,
+        system_program,
+        BidIndex::SPACE,
+        &[b"bid_index", auction_day_key.as_ref(), &ordinal_seed, &[bump]],
+        program_id,
+    )?;
+    let entry = BidIndex {
+        auction_day: auction_day_key,
+        ordinal,
+        bidder,
+        bump,
+    };
+    let mut data = bid_index.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    entry.try_serialize(&mut writer)?;
+    auction_day.bid_index_count = ordinal.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+fn is_day_closed(schedule_override: &AccountInfo, program_id: &Pubkey) -> Result<bool> {
+    if schedule_override.owner != program_id || schedule_override.data_is_empty() {
+        return Ok(false);
+    }
+    let schedule_override =
+        ScheduleOverride::try_deserialize(&mut &schedule_override.data.borrow()[..])?;
+    Ok(schedule_override.closed)
+}
+
+fn day_category(schedule_override: &AccountInfo, program_id: &Pubkey) -> Result<u8> {
+    if schedule_override.owner != program_id || schedule_override.data_is_empty() {
+        return Ok(CATEGORY_DEFAULT);
+    }
+    let schedule_override =
+        ScheduleOverride::try_deserialize(&mut &schedule_override.data.borrow()[..])?;
+    Ok(schedule_override.category)
+}
+
+// Resolves a freshly initialized day's category against the category table and
+// folds the rules into the day's snapshot, so bids, settlement, and refunds
+// read them like any other per-day parameter.
+fn apply_day_category(
+    auction_day: &mut AuctionDay,
+    schedule_override: &AccountInfo,
+    category_table: Option<&AccountInfo>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let category = day_category(schedule_override, program_id)?;
+    auction_day.category = category;
+    if category == CATEGORY_DEFAULT {
+        return Ok(());
+    }
+    let category_table = category_table
+        .filter(|info| info.owner == program_id && !info.data_is_empty())
+        .ok_or(ErrorCode::CategoryTableRequired)?;
+    let category_table =
+        CategoryTable::try_deserialize(&mut &category_table.data.borrow()[..])?;
+    let rules = category_table
+        .rules
+        .get(usize::from(category))
+        .ok_or(ErrorCode::InvalidCategory)?;
+
+    if rules.min_opening_bid_lamports > 0 {
+        auction_day.min_opening_bid_lamports = rules.min_opening_bid_lamports;
+    }
+    if rules.override_loser_fee {
+        auction_day.loser_fee_lamports = rules.loser_fee_lamports;
+        auction_day.loser_fee_bps = rules.loser_fee_bps;
+    }
+    auction_day.moderation_level = rules.moderation_level;
+    Ok(())
+}
+
+fn initialize_auction_day(
+    auction_day: &mut AuctionDay,
+    day_index: i64,
+    vault_bump: u8,
+    vault_lamports: u64,
+    config: &Config,
+    events: &EventSink,
+) -> Result<()> {
+    auction_day.day_index = day_index;
+    auction_day.finalized = false;
+    auction_day.winner = Pubkey::default();
+    auction_day.highest_bid = 0;
+    auction_day.bidder_count = 0;
+    auction_day.refund_count_total = 0;
+    auction_day.refund_count_completed = 0;
+    auction_day.total_bid_lamports = 0;
+    auction_day.refund_pool_remaining = 0;
+    auction_day.fee_pool_remaining = 0;
+    auction_day.vault_bump = vault_bump;
+    // Config changes only apply to days initialized afterwards.
+    auction_day.loser_fee_lamports = config.loser_fee_lamports;
+    auction_day.min_increment_lamports = config.min_increment_lamports;
+    auction_day.min_increment_bps = config.min_increment_bps;
+    auction_day.loser_fee_bps = config.loser_fee_bps;
+    auction_day.loser_fee_cap_lamports = config.loser_fee_cap_lamports;
+    auction_day.loser_fee_accrued = 0;
+    auction_day.vault_version = VAULT_VERSION_SYSTEM_OWNED;
+    auction_day.min_opening_bid_lamports = config.min_opening_bid_lamports;
+    auction_day.buy_now_lamports = 0;
+    auction_day.bidding_closed = false;
+    auction_day.bps_rounding = config.bps_rounding;
+    auction_day.start_ts = day_index
+        .saturating_mul(SECONDS_PER_DAY)
+        .saturating_sub(config.day_offset_seconds);
+    auction_day.end_ts = auction_day.start_ts.saturating_add(SECONDS_PER_DAY);
+    auction_day.soft_close_window_seconds = config.soft_close_window_seconds;
+    auction_day.soft_close_extension_seconds = config.soft_close_extension_seconds;
+    auction_day.priority_lane_seconds = config.priority_lane_seconds;
+    auction_day.top_bidders = [Pubkey::default(); TOP_BIDDERS_LEN];
+    auction_day.top_bids = [0; TOP_BIDDERS_LEN];
+    auction_day.sealed_reveal_seconds = config.sealed_reveal_seconds;
+    auction_day.penny_tick_lamports = config.penny_tick_lamports;
+    auction_day.penny_bid_fee_lamports = config.penny_bid_fee_lamports;
+    auction_day.penny_timer_seconds = config.penny_timer_seconds;
+    auction_day.penny_fees_collected = 0;
+    auction_day.follower_count = 0;
+    auction_day.candle_window_seconds = config.candle_window_seconds;
+    auction_day.candle_start_ts = auction_day
+        .end_ts
+        .saturating_sub(config.candle_window_seconds);
+    auction_day.candle_leaders = [Pubkey::default(); CANDLE_SLOTS];
+    auction_day.candle_bids = [0; CANDLE_SLOTS];
+    auction_day.candle_resolved = false;
+    auction_day.candle_cutoff_ts = 0;
+    auction_day.early_bird_window_seconds = config.early_bird_window_seconds;
+    auction_day.early_bird_discount_bps = config.early_bird_discount_bps;
+    auction_day.exposure_lamports = 0;
+    auction_day.winner_slots = config.winner_slots.max(1);
+    auction_day.highest_bid_ts = 0;
+    auction_day.withdraw_cutoff_seconds = config.withdraw_cutoff_seconds;
+    auction_day.withdraw_penalty_bps = config.withdraw_penalty_bps;
+    auction_day.withdraw_penalties = 0;
+    auction_day.promo_bid_count = 0;
+    auction_day.category = CATEGORY_DEFAULT;
+    auction_day.moderation_level = 0;
+    auction_day.claim_deadline_ts = 0;
+    auction_day.reminder_level = 0;
+    auction_day.min_bidders = config.min_bidders;
+    auction_day.cancelled = false;
+    auction_day.refund_window_seconds = config.refund_window_seconds;
+    auction_day.refund_deadline_ts = 0;
+    auction_day.swept = false;
+    auction_day.participation_cap_base_lamports = config.participation_cap_base_lamports;
+    auction_day.participation_cap_step_lamports = config.participation_cap_step_lamports;
+    auction_day.participation_veteran_days = config.participation_veteran_days;
+    auction_day.receipts_closed = 0;
+    auction_day.promo_bids_reclaimed = 0;
+    auction_day.bid_index_count = 0;
+    auction_day.refund_cursor = 0;
+    auction_day.refund_root = [0; 32];
+    auction_day.settle_reward_lamports = config.settle_reward_lamports;
+    auction_day.cranker_fee_share_bps = config.cranker_fee_share_bps;
+    auction_day.treasury_fees_accrued = 0;
+    auction_day.last_refund_cranker = Pubkey::default();
+    auction_day.surplus_to_winner = config.surplus_to_winner;
+    auction_day.streak_bonus_threshold = config.streak_bonus_threshold;
+    auction_day.streak_bonus_bps = config.streak_bonus_bps;
+    auction_day.referral_fee_bps = config.referral_fee_bps;
+    auction_day.referral_fees_accrued = 0;
+    auction_day.vesting_days = config.vesting_days;
+    auction_day.vesting_total_lamports = 0;
+    auction_day.vesting_claimed_lamports = 0;
+    auction_day.vesting_start_ts = 0;
+    auction_day.bid_mint = config.bid_mint;
+    auction_day.quote_mints = config.quote_mints;
+    auction_day.quote_mint_count = config.quote_mint_count;
+    auction_day.receipt_tree = config.receipt_tree;
+    auction_day.bid_gate = config.bid_gate;
+    auction_day.bid_gate_collection = config.bid_gate_collection;
+    auction_day.max_bid_per_wallet_lamports = config.max_bid_per_wallet_lamports;
+    auction_day.bid_cooldown_seconds = config.bid_cooldown_seconds;
+    auction_day.penny_deadline_ts = 0;
+    if auction_day.is_multi_currency() {
+        auction_day.min_increment_lamports = config
+            .min_increment_usd_cents
+            .checked_mul(MICRO_USD_PER_CENT)
+            .ok_or(ErrorCode::MathOverflow)?;
+        auction_day.min_opening_bid_lamports = config
+            .min_opening_bid_usd_cents
+            .checked_mul(MICRO_USD_PER_CENT)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    // Creating the vault alongside the day funds its rent; a vault that only
+    // ever received bids has no reserve until `fund_vault_rent`.
+    auction_day.vault_rent_lamports = vault_lamports.min(Rent::get()?.minimum_balance(0));
+
+    events.emit(DayInitialized {
+        day_index,
+        start_ts: auction_day.start_ts,
+        end_ts: auction_day.end_ts,
+        initialized_at: Clock::get()?.unix_timestamp,
+    })?;
+    Ok(())
+}
+
+// Counts lamports entering an open day's vault against the global exposure
+// cap. `settle_day` releases the day's whole contribution at once, so interim
+// refunds (reveal excess, candle excess) stay counted until then.
+fn add_exposure(config: &mut Config, auction_day: &mut AuctionDay, lamports: u64) -> Result<()> {
+    config.open_exposure_lamports = config
+        .open_exposure_lamports
+        .checked_add(lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        config.max_vault_exposure_lamports == 0
+            || config.open_exposure_lamports <= config.max_vault_exposure_lamports,
+        ErrorCode::VaultExposureCapExceeded
+    );
+    auction_day.exposure_lamports = auction_day
+        .exposure_lamports
+        .checked_add(lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+fn remove_exposure(config: &mut Config, auction_day: &mut AuctionDay, lamports: u64) {
+    let lamports = lamports.min(auction_day.exposure_lamports);
+    config.open_exposure_lamports = config.open_exposure_lamports.saturating_sub(lamports);
+    auction_day.exposure_lamports -= lamports;
+}
+
+fn release_exposure(config: &mut Config, auction_day: &mut AuctionDay) {
+    // Saturating: days opened before exposure tracking existed were never counted.
+    config.open_exposure_lamports = config
+        .open_exposure_lamports
+        .saturating_sub(auction_day.exposure_lamports);
+    auction_day.exposure_lamports = 0;
+}
+
+fn sealed_bid_commitment(
+    auction_day: &Pubkey,
+    bidder: &Pubkey,
+    amount: u64,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    hashv(&[
+        b"sealed_bid",
+        auction_day.as_ref(),
+        bidder.as_ref(),
+        &amount.to_le_bytes(),
+        salt,
+    ])
+    .to_bytes()
+}
+
+// Anti-snipe: a new leading bid inside the final window pushes `end_ts` out.
+fn maybe_extend_soft_close(
+    auction_day: &mut AuctionDay,
+    now: i64,
+    events: &EventSink,
+) -> Result<()> {
+    if auction_day.soft_close_window_seconds == 0 || auction_day.soft_close_extension_seconds == 0 {
+        return Ok(());
+    }
+    let window_start = auction_day
+        .end_ts
+        .checked_sub(auction_day.soft_close_window_seconds)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if now < window_start {
+        return Ok(());
+    }
+    auction_day.end_ts = auction_day
+        .end_ts
+        .checked_add(auction_day.soft_close_extension_seconds)
+        .ok_or(ErrorCode::MathOverflow)?;
+    events.emit(SoftCloseExtended {
+        day_index: auction_day.day_index,
+        end_ts: auction_day.end_ts,
+    })?;
+    Ok(())
+}
+
+// Penny countdown: every bid restarts a `penny_timer_seconds` countdown and
+// bidding closes once it runs out. `end_ts` is pushed out to the deadline when
+// needed, so the countdown is never cut short by the day's normal close.
+fn reset_penny_timer(auction_day: &mut AuctionDay, now: i64, events: &EventSink) -> Result<()> {
+    let deadline = now
+        .checked_add(auction_day.penny_timer_seconds)
+        .ok_or(ErrorCode::MathOverflow)?;
+    auction_day.penny_deadline_ts = deadline;
+    auction_day.end_ts = auction_day.end_ts.max(deadline);
+    events.emit(PennyTimerReset {
+        day_index: auction_day.day_index,
+        leader: auction_day.winner,
+        highest_bid: auction_day.highest_bid,
+        deadline_ts: deadline,
+        end_ts: auction_day.end_ts,
+    })?;
+    Ok(())
+}
+
+fn require_vault_owner(auction_day: &AuctionDay, vault: &AccountInfo) -> Result<()> {
+    let expected_owner = if auction_day.vault_version == VAULT_VERSION_PROGRAM_OWNED {
+        &crate::ID
+    } else {
+        &system_program::ID
+    };
+    require!(vault.owner == expected_owner, ErrorCode::InvalidVaultOwner);
+    Ok(())
+}
+
+// Pays out of the day's vault principal; the rent reserve is never touched.
+// System-owned vaults sign a system transfer with the vault seeds; migrated
+// program-owned vaults are debited directly.
+fn transfer_from_vault<'info>(
+    auction_day: &Account<'info, AuctionDay>,
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        amount <= vault_principal(auction_day, vault),
+        ErrorCode::VaultRentReserved
+    );
+    move_vault_lamports(auction_day, vault, to, system_program, amount)
+}
+
+// Vault lamports beyond its rent reserve.
+fn vault_principal(auction_day: &AuctionDay, vault: &AccountInfo) -> u64 {
+    vault.lamports().saturating_sub(auction_day.vault_rent_lamports)
+}
+
+fn move_vault_lamports<'info>(
+    auction_day: &Account<'info, AuctionDay>,
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    if auction_day.vault_version == VAULT_VERSION_PROGRAM_OWNED {
+        vault.sub_lamports(amount)?;
+        to.add_lamports(amount)?;
+        return Ok(());
+    }
+    let auction_day_key = auction_day.key();
+    let seeds: &[&[u8]] = &[b"vault", auction_day_key.as_ref(), &[auction_day.vault_bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Transfer {
+            from: vault.clone(),
+            to: to.clone(),
+        },
+        signer_seeds,
+    );
+    transfer(cpi_ctx, amount)
+}
+
+// Fee withheld from a losing receipt of `amount`. Flat unless `loser_fee_bps`
+// is set, in which case it is a percentage of the bid capped at
+// `loser_fee_cap_lamports` (0 = uncapped). Never more than the bid itself, so
+// a losing bid at or below the fee refunds zero instead of blocking the day.
+fn loser_fee_for(auction_day: &AuctionDay, amount: u64) -> Result<u64> {
+    let fee = if auction_day.loser_fee_bps == 0 {
+        auction_day.loser_fee_lamports
+    } else {
+        let fee = apply_bps(amount, auction_day.loser_fee_bps, auction_day.bps_rounding)?;
+        if auction_day.loser_fee_cap_lamports == 0 {
+            fee
+        } else {
+            fee.min(auction_day.loser_fee_cap_lamports)
+        }
+    };
+    Ok(fee.min(amount))
+}
+
+// Early-bird discount on a loser fee: the full `early_bird_discount_bps` for a
+// receipt opened at `start_ts`, decaying linearly to zero at the end of the window.
+fn early_bird_discount(auction_day: &AuctionDay, first_bid_ts: i64, loser_fee: u64) -> Result<u64> {
+    let window = auction_day.early_bird_window_seconds;
+    if window == 0 || auction_day.early_bird_discount_bps == 0 {
+        return Ok(0);
+    }
+    let elapsed = first_bid_ts.saturating_sub(auction_day.start_ts).max(0);
+    if elapsed >= window {
+        return Ok(0);
+    }
+    let bps = (u64::from(auction_day.early_bird_discount_bps) * (window - elapsed) as u64
+        / window as u64) as u16;
+    apply_bps(loser_fee, bps, auction_day.bps_rounding)
+}
+
+// `amount * bps / 10_000` rounded per the day's policy. Every bps computation
+// goes through here so settle_day and refund_batch can never disagree.
+fn apply_bps(amount: u64, bps: u16, rounding: RoundingPolicy) -> Result<u64> {
+    let numerator = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let denominator = BPS_DENOMINATOR as u128;
+    let value = match rounding {
+        RoundingPolicy::Floor => numerator / denominator,
+        RoundingPolicy::Ceil => numerator.div_ceil(denominator),
+    };
+    Ok(u64::try_from(value).map_err(|_| ErrorCode::MathOverflow)?)
+}
+
+// The wallet placing a bid, the mint it pays in (`Pubkey::default()` for
+// lamports), when supplied its participation history, and whether `place_bid`
+// vetted it against the day's bid gate and the access list.
+struct Bidder<'a> {
+    key: Pubkey,
+    mint: Pubkey,
+    stats: Option<&'a mut BidderStats>,
+    vetted: bool,
+}
+
+// `emit_cpi!` for helpers that run outside an instruction handler. The event is
+// a self-CPI signed by the event authority PDA, so it is recorded in the
+// transaction's inner instructions, which RPC log truncation cannot drop.
+pub struct EventSink<'info> {
+    authority: AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'info> EventSink<'info> {
+    fn new(authority: &AccountInfo<'info>, bump: u8) -> Self {
+        Self {
+            authority: authority.clone(),
+            bump,
+        }
+    }
+
+    fn emit<E: anchor_lang::Event>(&self, event: E) -> Result<()> {
+        let data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .iter()
+            .copied()
+            .chain(event.data())
+            .collect();
+        let ix = anchor_lang::solana_program::instruction::Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![anchor_lang::solana_program::instruction::AccountMeta::new_readonly(
+                *self.authority.key,
+                true,
+            )],
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            std::slice::from_ref(&self.authority),
+            &[&[b"__event_authority", &[self.bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+// `BidPlaced` for every accepted bid, plus `Outbid` when the lead changed hands.
+fn emit_bid_events(
+    auction_day: &mut AuctionDay,
+    bidder: Pubkey,
+    previous_amount: u64,
+    amount: u64,
+    (previous_leader, previous_high): (Pubkey, u64),
+    now: i64,
+    events: &EventSink,
+) -> Result<()> {
+    let seq = auction_day.next_seq()?;
+    events.emit(BidPlaced {
+        day_index: auction_day.day_index,
+        bidder,
+        amount,
+        previous_amount,
+        leading: auction_day.winner == bidder,
+        placed_at: now,
+        seq,
+    })?;
+    if auction_day.winner != previous_leader && previous_leader != Pubkey::default() {
+        events.emit(Outbid {
+            day_index: auction_day.day_index,
+            previous_leader,
+            previous_bid: previous_high,
+            leader: auction_day.winner,
+            bid: auction_day.highest_bid,
+            outbid_at: now,
+            seq,
+        })?;
+    }
+    Ok(())
+}
+
+// Validates an open-format bid and applies it to the day and receipt. Returns
+// the lamports the bid adds to the vault (including any penny bid fee);
+// funding it and checking the bidding window are left to the caller.
+#[allow(clippy::too_many_arguments)]
+fn apply_bid(
+    auction_day: &mut Account<AuctionDay>,
+    bid_receipt: &mut Account<BidReceipt>,
+    bidder: Bidder,
+    config: &Config,
+    price_floor: Option<&UncheckedAccount>,
+    now: i64,
+    new_amount: u64,
+    events: &EventSink,
+) -> Result<u64> {
+    let Bidder {
+        key: bidder,
+        mint,
+        stats: bidder_stats,
+        vetted,
+    } = bidder;
+    require!(!auction_day.finalized, ErrorCode::AlreadyFinalized);
+    require!(!auction_day.is_gated() || vetted, ErrorCode::BidGateNotMet);
+    require!(
+        config.access_mode == ACCESS_MODE_OFF || vetted,
+        ErrorCode::AccessEntryRequired
+    );
+    let accepts_mint = if auction_day.is_multi_currency() {
+        auction_day.quote_mint(&mint).is_some()
+    } else {
+        mint == auction_day.bid_mint
+    };
+    require!(accepts_mint, ErrorCode::BidCurrencyMismatch);
+    require!(!auction_day.bidding_closed, ErrorCode::BiddingClosed);
+    require_within_participation_cap(auction_day, bidder_stats.as_deref(), new_amount)?;
+    require_within_wallet_cap(auction_day, new_amount)?;
+
+    let required = required_next_bid(auction_day)?;
+    if auction_day.is_penny() {
+        require!(new_amount == required, ErrorCode::PennyTickMismatch);
+    } else {
+        require!(new_amount >= required, ErrorCode::BidTooLow);
+    }
+
+    if config.floor_adapter_account != Pubkey::default() {
+        let price_floor = price_floor.ok_or(ErrorCode::PriceFloorMissing)?;
+        require_keys_eq!(
+            price_floor.key(),
+            config.floor_adapter_account,
+            ErrorCode::PriceFloorMismatch
+        );
+        let feed = PriceFloorFeed::try_from_account(price_floor, &config.floor_adapter_program)?;
+        require!(
+            now.saturating_sub(feed.updated_at) <= config.floor_max_staleness_seconds,
+            ErrorCode::PriceFloorStale
+        );
+        require!(new_amount >= feed.floor_lamports, ErrorCode::BelowPriceFloor);
+    }
+
+    if auction_day.in_priority_lane(now) {
+        require!(
+            auction_day.top_bidders.contains(&bidder),
+            ErrorCode::PriorityLaneOnly
+        );
+    }
+
+    let is_new_receipt = bid_receipt.bidder == Pubkey::default();
+    if is_new_receipt {
+        bid_receipt.auction_day = auction_day.key();
+        bid_receipt.bidder = bidder;
+        bid_receipt.refunded = false;
+        bid_receipt.first_bid_ts = now;
+        auction_day.bidder_count = auction_day
+            .bidder_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if let Some(stats) = bidder_stats {
+            stats.record_day(bidder, auction_day.day_index)?;
+        }
+    }
+    require!(bid_receipt.bidder == bidder, ErrorCode::BidderMismatch);
+    // Damps bot micro-increment spam: a wallet waits out the cooldown before
+    // raising its own bid again.
+    require!(
+        is_new_receipt
+            || auction_day.bid_cooldown_seconds == 0
+            || now
+                >= bid_receipt
+                    .last_bid_ts
+                    .saturating_add(auction_day.bid_cooldown_seconds),
+        ErrorCode::BidCooldownActive
+    );
+
+    let previous_amount = bid_receipt.amount;
+    require!(new_amount > previous_amount, ErrorCode::BidDecrease);
+    let delta = new_amount
+        .checked_sub(previous_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let previous_fee = if is_new_receipt {
+        0
+    } else {
+        loser_fee_for(auction_day, previous_amount)?
+    };
+    auction_day.loser_fee_accrued = auction_day
+        .loser_fee_accrued
+        .checked_sub(previous_fee)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(loser_fee_for(auction_day, new_amount)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    bid_receipt.amount = new_amount;
+    auction_day.total_bid_lamports = auction_day
+        .total_bid_lamports
+        .checked_add(delta)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    bid_receipt.last_bid_ts = now;
+
+    // Only a strictly higher bid takes the lead; an equal bid leaves the
+    // earlier leader (and `highest_bid_ts`) in place.
+    let previous_leader = auction_day.winner;
+    let previous_high = auction_day.highest_bid;
+    auction_day.record_top_bid(bidder, new_amount);
+    if new_amount > auction_day.highest_bid {
+        auction_day.highest_bid = new_amount;
+        auction_day.winner = bidder;
+        auction_day.highest_bid_ts = now;
+        if auction_day.is_candle() {
+            auction_day.record_candle_leader(bidder, new_amount, now);
+        }
+        maybe_extend_soft_close(auction_day, now, events)?;
+    }
+    emit_bid_events(
+        auction_day,
+        bidder,
+        previous_amount,
+        new_amount,
+        (previous_leader, previous_high),
+        now,
+        events,
+    )?;
+
+    let mut vault_delta = delta;
+    if auction_day.is_penny() {
+        let bid_fee = auction_day.penny_bid_fee_lamports;
+        auction_day.penny_fees_collected = auction_day
+            .penny_fees_collected
+            .checked_add(bid_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        vault_delta = vault_delta
+            .checked_add(bid_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        reset_penny_timer(auction_day, now, events)?;
+    }
+
+    if auction_day.buy_now_lamports > 0 && new_amount >= auction_day.buy_now_lamports {
+        auction_day.bidding_closed = true;
+        events.emit(BuyNowExecuted {
+            day_index: auction_day.day_index,
+            winner: auction_day.winner,
+            amount: new_amount,
+            buy_now_lamports: auction_day.buy_now_lamports,
+        })?;
+    }
+    Ok(vault_delta)
+}
+
+// Splits a settled day's bids into what the winners pay, the loser fee pool,
+// and the refund pool. Returns `(winning_total, winner_count, fee_pool, refund_pool)`.
+fn settlement_pools(auction_day: &AuctionDay) -> Result<(u64, u32, u64, u64)> {
+    let mut winning_total: u64 = 0;
+    let mut winner_fees: u64 = 0;
+    let mut winner_count: u32 = 0;
+    for bid in auction_day.winning_bids() {
+        winning_total = winning_total
+            .checked_add(bid)
+            .ok_or(ErrorCode::MathOverflow)?;
+        winner_fees = winner_fees
+            .checked_add(loser_fee_for(auction_day, bid)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        winner_count += 1;
+    }
+    let loser_sum = auction_day
+        .total_bid_lamports
+        .checked_sub(winning_total)
+        .ok_or(ErrorCode::MathOverflow)?;
+    // `loser_fee_accrued` sums the fee of every receipt; drop the winners'.
+    let fee_pool = auction_day
+        .loser_fee_accrued
+        .checked_sub(winner_fees)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(loser_sum >= fee_pool, ErrorCode::FeePoolTooLarge);
+    let refund_pool = loser_sum
+        .checked_sub(fee_pool)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok((winning_total, winner_count, fee_pool, refund_pool))
+}
+
+// Finalizes a day without a winner: nothing goes to the recipient and
+// `refund_batch` returns every receipt in full. Penny bid fees and withdrawal
+// penalties stay in the vault.
+fn cancel_auction_day(auction_day: &mut AuctionDay) {
+    auction_day.finalized = true;
+    auction_day.cancelled = true;
+    auction_day.winner = Pubkey::default();
+    auction_day.refund_pool_remaining = auction_day.total_bid_lamports;
+    auction_day.fee_pool_remaining = 0;
+    auction_day.refund_count_total = auction_day.bidder_count;
+    auction_day.refund_count_completed = 0;
+}
+
+// Per-day bid ceiling from the bidder's recorded history, `base + step * days`,
+// lifted entirely once the wallet reaches `participation_veteran_days`. A
+// bidder without a `BidderStats` account has no history.
+fn require_within_participation_cap(
+    auction_day: &AuctionDay,
+    bidder_stats: Option<&BidderStats>,
+    amount: u64,
+) -> Result<()> {
+    if auction_day.participation_cap_base_lamports == 0 {
+        return Ok(());
+    }
+    let days = bidder_stats.map_or(0, |stats| stats.days_participated);
+    if auction_day.participation_veteran_days > 0 && days >= auction_day.participation_veteran_days {
+        return Ok(());
+    }
+    let cap = auction_day.participation_cap_base_lamports.saturating_add(
+        auction_day
+            .participation_cap_step_lamports
+            .saturating_mul(u64::from(days)),
+    );
+    require!(amount <= cap, ErrorCode::ParticipationCapExceeded);
+    Ok(())
+}
+
+// Flat per-day ceiling on one wallet's total bid, for community days where the
+// operator wants to keep whales from dominating.
+fn require_within_wallet_cap(auction_day: &AuctionDay, amount: u64) -> Result<()> {
+    require!(
+        auction_day.max_bid_per_wallet_lamports == 0
+            || amount <= auction_day.max_bid_per_wallet_lamports,
+        ErrorCode::WalletBidCapExceeded
+    );
+    Ok(())
+}
+
+// True when a system transfer of the receipt's refund to `to` would fail: an
+// executable account, or an empty account the refund cannot make rent-exempt.
+fn refund_undeliverable(
+    auction_day: &AuctionDay,
+    bid_receipt: &BidReceipt,
+    to: &AccountInfo,
+    rent: &Rent,
+) -> Result<bool> {
+    if to.executable {
+        return Ok(true);
+    }
+    if to.lamports() > 0 {
+        return Ok(false);
+    }
+    // Lower bound on the refund; the early-bird discount only adds to it.
+    let full_fee = if auction_day.cancelled {
+        0
+    } else {
+        loser_fee_for(auction_day, bid_receipt.amount)?
+    };
+    let refund = bid_receipt.amount.saturating_sub(full_fee);
+    // A zero refund moves nothing, so any wallet can take it.
+    Ok(refund > 0 && !rent.is_exempt(refund, to.data_len()))
+}
+
+// Bubblegum accounts for minting participation cNFTs into a day's `receipt_tree`.
+struct ReceiptMinter<'info> {
+    bubblegum_program: AccountInfo<'info>,
+    tree_config: AccountInfo<'info>,
+    merkle_tree: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    authority_bump: u8,
+    log_wrapper: AccountInfo<'info>,
+    compression_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+}
+
+// Borsh layout of Bubblegum's `MetadataArgs`. The unused optional structs
+// (`collection`, `uses`) are always None, which serializes as a single 0 byte.
+#[derive(AnchorSerialize)]
+struct BubblegumMetadataArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    edition_nonce: Option<u8>,
+    token_standard: Option<u8>,
+    collection: Option<u8>,
+    uses: Option<u8>,
+    token_program_version: u8,
+    creators: Vec<u8>,
+}
+
+impl<'info> ReceiptMinter<'info> {
+    // Mints one participation cNFT to `leaf_owner` via Bubblegum `mint_v1`.
+    // The `["cnft_authority"]` PDA signs as both payer and tree delegate.
+    fn mint(&self, leaf_owner: &AccountInfo<'info>, day_index: i64) -> Result<()> {
+        let args = BubblegumMetadataArgs {
+            name: format!("Daily Auction #{} Bidder", day_index),
+            symbol: TROPHY_SYMBOL.to_string(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            // TokenStandard::NonFungible
+            token_standard: Some(0),
+            collection: None,
+            uses: None,
+            // TokenProgramVersion::Original
+            token_program_version: 0,
+            creators: Vec::new(),
+        };
+        let mut data = BUBBLEGUM_MINT_V1_DISCRIMINATOR.to_vec();
+        args.serialize(&mut data)?;
+        let ix = anchor_lang::solana_program::instruction::Instruction {
+            program_id: BUBBLEGUM_PROGRAM,
+            accounts: vec![
+                AccountMeta::new(*self.tree_config.key, false),
+                AccountMeta::new_readonly(*leaf_owner.key, false),
+                AccountMeta::new_readonly(*leaf_owner.key, false),
+                AccountMeta::new(*self.merkle_tree.key, false),
+                AccountMeta::new_readonly(*self.authority.key, true),
+                AccountMeta::new_readonly(*self.authority.key, true),
+                AccountMeta::new_readonly(*self.log_wrapper.key, false),
+                AccountMeta::new_readonly(*self.compression_program.key, false),
+                AccountMeta::new_readonly(*self.system_program.key, false),
+            ],
+            data,
+        };
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                self.tree_config.clone(),
+                leaf_owner.clone(),
+                self.merkle_tree.clone(),
+                self.authority.clone(),
+                self.log_wrapper.clone(),
+                self.compression_program.clone(),
+                self.system_program.clone(),
+                self.bubblegum_program.clone(),
+            ],
+            &[&[b"cnft_authority", &[self.authority_bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+// Accounts and settings shared by every receipt a refund crank visits.
+struct RefundCtx<'info> {
+    instruction: &'static str,
+    day_index: i64,
+    vault: AccountInfo<'info>,
+    cranker: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent: Rent,
+    events: EventSink<'info>,
+}
+
+// Refunds one receipt for `refund_batch` / `refund_next`. Returns false when
+// the refund was skipped as undeliverable and the receipt is still pending.
+// With a caller-supplied `receipt_bump` the address check skips the bump
+// search; a non-canonical bump yields an address the program never created,
+// which the owner check below rejects.
+fn refund_receipt<'info>(
+    ctx: &RefundCtx<'info>,
+    auction_day: &mut Account<'info, AuctionDay>,
+    bidder: &Pubkey,
+    receipt_bump: Option<u8>,
+    bid_receipt_info: &AccountInfo<'info>,
+    bidder_info: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<bool> {
+    require!(bidder_info.key == bidder, ErrorCode::BidderMismatch);
+
+    let auction_day_key = auction_day.key();
+    let expected_receipt = match receipt_bump {
+        Some(bump) => Pubkey::create_program_address(
+            &[b"bid_receipt", auction_day_key.as_ref(), bidder.as_ref(), &[bump]],
+            program_id,
+        )
+        .map_err(|_| ErrorCode::BidReceiptMismatch)?,
+        None => {
+            Pubkey::find_program_address(
+                &[b"bid_receipt", auction_day_key.as_ref(), bidder.as_ref()],
+                program_id,
+            )
+            .0
+        }
+    };
+    require!(
+        bid_receipt_info.key == &expected_receipt,
+        ErrorCode::BidReceiptMismatch
+    );
+    require!(
+        bid_receipt_info.owner == program_id,
+        ErrorCode::BidReceiptOwnerMismatch
+    );
+
+    let mut bid_receipt = {
+        let mut data_slice: &[u8] = &bid_receipt_info.data.borrow();
+        BidReceipt::try_deserialize(&mut data_slice)?
+    };
+
+    require!(
+        bid_receipt.auction_day == auction_day.key(),
+        ErrorCode::BidReceiptMismatch
+    );
+    require!(bid_receipt.bidder == *bidder, ErrorCode::BidderMismatch);
+    log_compute(ctx.instruction, "receipt_loaded");
+
+    if bid_receipt.refunded {
+        return Ok(true);
+    }
+
+    // Winners are excluded from `refund_count_total`, so they are
+    // marked without counting toward completion.
+    if auction_day.is_winner(bidder) {
+        bid_receipt.refunded = true;
+        pay_refund_tip(bid_receipt_info, &mut bid_receipt, bidder_info)?;
+        release_counter_raise(bid_receipt_info, &mut bid_receipt, bidder_info)?;
+        write_bid_receipt(bid_receipt_info, &bid_receipt)?;
+        return Ok(true);
+    }
+
+    // A wallet that cannot take the refund would fail the whole batch.
+    // Skip it a few times, then escrow the refund in the receipt.
+    let undeliverable = refund_undeliverable(auction_day, &bid_receipt, bidder_info, &ctx.rent)?;
+    if undeliverable {
+        bid_receipt.delivery_attempts = bid_receipt.delivery_attempts.saturating_add(1);
+        if bid_receipt.delivery_attempts < REFUND_DELIVERY_MAX_ATTEMPTS {
+            write_bid_receipt(bid_receipt_info, &bid_receipt)?;
+            msg!("{}: refund to {} undeliverable, skipped", ctx.instruction, bidder);
+            return Ok(false);
+        }
+    }
+
+    let vault_lamports = vault_principal(auction_day, &ctx.vault);
+    let (refund_amount, loser_fee) =
+        refund_loser_receipt(auction_day, &mut bid_receipt, vault_lamports, &ctx.events)?;
+
+    let refund_destination = if undeliverable {
+        bid_receipt.voucher_lamports = refund_amount;
+        ctx.events.emit(RefundVoucherIssued {
+            day_index: ctx.day_index,
+            bidder: *bidder,
+            lamports: refund_amount,
+        })?;
+        bid_receipt_info
+    } else {
+        bidder_info
+    };
+    transfer_from_vault(
+        auction_day,
+        &ctx.vault,
+        refund_destination,
+        &ctx.system_program,
+        refund_amount,
+    )?;
+    transfer_from_vault(
+        auction_day,
+        &ctx.vault,
+        &ctx.cranker,
+        &ctx.system_program,
+        loser_fee,
+    )?;
+    pay_refund_tip(bid_receipt_info, &mut bid_receipt, &ctx.cranker)?;
+    // An unexecuted counter-raise escrow follows the refund.
+    if undeliverable {
+        bid_receipt.voucher_lamports = bid_receipt
+            .voucher_lamports
+            .checked_add(bid_receipt.counter_raise_escrow)
+            .ok_or(ErrorCode::MathOverflow)?;
+        bid_receipt.counter_raise_escrow = 0;
+    } else {
+        release_counter_raise(bid_receipt_info, &mut bid_receipt, bidder_info)?;
+    }
+
+    write_bid_receipt(bid_receipt_info, &bid_receipt)?;
+    log_compute(ctx.instruction, "receipt_refunded");
+    Ok(true)
+}
+
+// Opens the day's refund window at finalization; 0 keeps refunds open forever.
+fn start_refund_window(auction_day: &mut AuctionDay, now: i64) -> Result<()> {
+    if auction_day.refund_window_seconds > 0 {
+        auction_day.refund_deadline_ts = now
+            .checked_add(auction_day.refund_window_seconds)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+    Ok(())
+}
+
+// Marks a losing receipt refunded and draws its refund and loser fee from the
+// day's pools. Returns `(refund_amount, loser_fee)`; the caller moves the lamports.
+// Pools are sized with undiscounted fees, so any early-bird discount is paid
+// to the bidder out of the fee pool.
+fn refund_loser_receipt(
+    auction_day: &mut AuctionDay,
+    bid_receipt: &mut BidReceipt,
+    vault_lamports: u64,
+    events: &EventSink,
+) -> Result<(u64, u64)> {
+    require!(!auction_day.swept, ErrorCode::RefundWindowClosed);
+    // Cancelled days refund every bid in full.
+    let full_fee = if auction_day.cancelled {
+        0
+    } else {
+        loser_fee_for(auction_day, bid_receipt.amount)?
+    };
+    let pool_refund = bid_receipt
+        .amount
+        .checked_sub(full_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    // The settle reward was carved from the fee pool, so the last refunds may
+    // find it short. The cranker's fee absorbs the shortfall first.
+    let fee_share = full_fee.min(auction_day.fee_pool_remaining);
+    let discount =
+        early_bird_discount(auction_day, bid_receipt.first_bid_ts, full_fee)?.min(fee_share);
+    let loser_fee = fee_share
+        .checked_sub(discount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let refund_amount = pool_refund
+        .checked_add(discount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    require!(
+        auction_day.refund_pool_remaining >= pool_refund,
+        ErrorCode::InsufficientRefundPool
+    );
+    require!(
+        vault_lamports
+            >= pool_refund
+                .checked_add(fee_share)
+                .ok_or(ErrorCode::MathOverflow)?,
+        ErrorCode::InsufficientVaultLamports
+    );
+
+    bid_receipt.refunded = true;
+    auction_day.refund_pool_remaining = auction_day
+        .refund_pool_remaining
+        .checked_sub(pool_refund)
+        .ok_or(ErrorCode::MathOverflow)?;
+    auction_day.fee_pool_remaining = auction_day
+        .fee_pool_remaining
+        .checked_sub(fee_share)
+        .ok_or(ErrorCode::MathOverflow)?;
+    auction_day.refund_count_completed = auction_day
+        .refund_count_completed
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let referral_fee = take_referral_fee(auction_day, bid_receipt, loser_fee)?;
+    let cranker_fee = take_cranker_fee(auction_day, loser_fee - referral_fee)?;
+
+    events.emit(RefundIssued {
+        day_index: auction_day.day_index,
+        bidder: bid_receipt.bidder,
+        refund_lamports: refund_amount,
+        loser_fee,
+        refunded_at: Clock::get()?.unix_timestamp,
+        seq: auction_day.next_seq()?,
+    })?;
+    Ok((refund_amount, cranker_fee))
+}
+
+// Sets aside the referrer's cut of a referred receipt's loser fee until
+// `claim_referral_fee`. Returns the cut; the rest of the fee is split as usual.
+fn take_referral_fee(
+    auction_day: &mut AuctionDay,
+    bid_receipt: &mut BidReceipt,
+    loser_fee: u64,
+) -> Result<u64> {
+    if bid_receipt.referrer == Pubkey::default() {
+        return Ok(0);
+    }
+    let referral_fee = apply_bps(
+        loser_fee,
+        auction_day.referral_fee_bps,
+        RoundingPolicy::Floor,
+    )?;
+    bid_receipt.referral_fee_lamports = referral_fee;
+    auction_day.referral_fees_accrued = auction_day
+        .referral_fees_accrued
+        .checked_add(referral_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(referral_fee)
+}
+
+// Splits a loser fee by the day's `cranker_fee_share_bps`. Returns the
+// cranker's share; the rest stays in the vault as treasury fees until
+// `sweep_unclaimed`.
+fn take_cranker_fee(auction_day: &mut AuctionDay, loser_fee: u64) -> Result<u64> {
+    let cranker_fee = apply_bps(
+        loser_fee,
+        auction_day.cranker_fee_share_bps,
+        RoundingPolicy::Floor,
+    )?;
+    auction_day.treasury_fees_accrued = auction_day
+        .treasury_fees_accrued
+        .checked_add(loser_fee - cranker_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(cranker_fee)
+}
+
+// Minimum amount the next bid must reach. With `min_increment_bps` set, the
+// increment scales with the leading bid and the flat increment acts as a floor.
+fn required_next_bid(auction_day: &AuctionDay) -> Result<u64> {
+    let highest_bid = auction_day.highest_bid;
+    // Penny days move in exact ticks from zero.
+    if auction_day.is_penny() {
+        return Ok(highest_bid
+            .checked_add(auction_day.penny_tick_lamports)
+            .ok_or(ErrorCode::MathOverflow)?);
+    }
+    if highest_bid == 0 {
+        return Ok(auction_day.min_opening_bid_lamports);
+    }
+    let flat_increment = auction_day.min_increment_lamports;
+    let bps_increment = apply_bps(
+        highest_bid,
+        auction_day.min_increment_bps,
+        auction_day.bps_rounding,
+    )?;
+    let increment = flat_increment.max(bps_increment);
+    Ok(highest_bid
+        .checked_add(increment)
+        .ok_or(ErrorCode::MathOverflow)?)
+}
+
+// `part` of a multi-currency receipt's micro-USD `value`, in the base units of
+// its `native` deposit (rounded down).
+fn native_share(native: u64, value: u64, part: u64) -> Result<u64> {
+    if value == 0 {
+        return Ok(0);
+    }
+    let share = u128::from(native) * u128::from(part) / u128::from(value);
+    Ok(u64::try_from(share).map_err(|_| ErrorCode::MathOverflow)?)
+}
+
+// Applies `Config.access_mode` to the bidder's `["access", wallet]` entry. The
+// PDA must be passed while enforcement is on, even when it does not exist, so
+// a denylisted wallet cannot skip the check by leaving it out.
+fn require_access(
+    config: &Config,
+    access_entry: Option<&UncheckedAccount>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if config.access_mode == ACCESS_MODE_OFF {
+        return Ok(());
+    }
+    let info = access_entry.ok_or(ErrorCode::AccessEntryRequired)?;
+    let status = if info.owner != program_id || info.data_is_empty() {
+        ACCESS_NONE
+    } else {
+        AccessEntry::try_deserialize(&mut &info.data.borrow()[..])?.status
+    };
+    require!(status != ACCESS_DENIED, ErrorCode::WalletDenied);
+    require!(
+        config.access_mode != ACCESS_MODE_ALLOWLIST || status == ACCESS_ALLOWED,
+        ErrorCode::WalletNotAllowlisted
+    );
+    Ok(())
+}
+
+// Records the win on the winner's `BidderProfile` and returns the streak it
+// extends to, or 0 without a profile. While the day pays a streak bonus the
+// PDA must be passed, even when it does not exist, so a settler cannot break
+// the winner's streak by leaving it out.
+fn record_winner_profile(
+    auction_day: &AuctionDay,
+    winner_profile: Option<&UncheckedAccount>,
+    program_id: &Pubkey,
+) -> Result<u32> {
+    if auction_day.streak_bonus_threshold == 0 && winner_profile.is_none() {
+        return Ok(0);
+    }
+    let info = winner_profile.ok_or(ErrorCode::WinnerProfileRequired)?;
+    if info.owner != program_id || info.data_is_empty() {
+        return Ok(0);
+    }
+    let mut profile = BidderProfile::try_deserialize(&mut &info.data.borrow()[..])?;
+    let streak = profile.record_win(auction_day.day_index)?;
+    let mut data = info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    profile.try_serialize(&mut writer)?;
+    Ok(streak)
+}
+
+// Gated days need the bidder to hold the gate: a token account it owns with
+// at least one of the gate mint, or, for collection gates, an NFT whose
+// Metaplex metadata names the gate as its verified collection.
+fn require_bid_gate(
+    auction_day: &AuctionDay,
+    bidder: &Pubkey,
+    gate_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    gate_metadata: Option<&UncheckedAccount>,
+) -> Result<()> {
+    if !auction_day.is_gated() {
+        return Ok(());
+    }
+    let token_account = gate_token_account.ok_or(ErrorCode::BidGateNotMet)?;
+    require!(
+        token_account.owner == *bidder && token_account.amount >= 1,
+        ErrorCode::BidGateNotMet
+    );
+    if !auction_day.bid_gate_collection {
+        require!(
+            token_account.mint == auction_day.bid_gate,
+            ErrorCode::BidGateNotMet
+        );
+        return Ok(());
+    }
+    let metadata = gate_metadata.ok_or(ErrorCode::BidGateNotMet)?;
+    let collection = metadata_verified_collection(metadata, &token_account.mint)?;
+    require!(
+        collection == Some(auction_day.bid_gate),
+        ErrorCode::BidGateNotMet
+    );
+    Ok(())
+}
+
+// The verified collection of a Metaplex `Metadata` account for `mint`. Only
+// the fields in front of `collection` are walked; strings are borsh with their
+// padding included in the length.
+fn metadata_verified_collection(info: &AccountInfo, mint: &Pubkey) -> Result<Option<Pubkey>> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM,
+    );
+    require!(
+        info.key() == expected && info.owner == &TOKEN_METADATA_PROGRAM,
+        ErrorCode::InvalidGateMetadata
+    );
+    let data = info.try_borrow_data()?;
+    let mut cursor = 0usize;
+    let mut take = |len: usize| -> Result<&[u8]> {
+        let bytes = data
+            .get(cursor..cursor + len)
+            .ok_or(ErrorCode::InvalidGateMetadata)?;
+        cursor += len;
+        Ok(bytes)
+    };
+    require!(take(1)?[0] == METADATA_KEY_V1, ErrorCode::InvalidGateMetadata);
+    take(32)?; // update_authority
+    require!(take(32)? == mint.as_ref(), ErrorCode::InvalidGateMetadata);
+    for _ in 0..3 {
+        // name, symbol, uri
+        let len = u32::from_le_bytes(take(4)?.try_into().unwrap()) as usize;
+        take(len)?;
+    }
+    take(2)?; // seller_fee_basis_points
+    if take(1)?[0] == 1 {
+        // creators: address, verified, share
+        let count = u32::from_le_bytes(take(4)?.try_into().unwrap()) as usize;
+        take(count.checked_mul(34).ok_or(ErrorCode::InvalidGateMetadata)?)?;
+    }
+    take(2)?; // primary_sale_happened, is_mutable
+    for _ in 0..2 {
+        // edition_nonce, token_standard
+        if take(1)?[0] == 1 {
+            take(1)?;
+        }
+    }
+    if take(1)?[0] == 0 {
+        return Ok(None);
+    }
+    let verified = take(1)?[0] == 1;
+    let key = Pubkey::try_from(take(32)?).map_err(|_| ErrorCode::InvalidGateMetadata)?;
+    Ok(verified.then_some(key))
+}
+
+// Re-prices the day's USD-denominated minimums in lamports from the configured
+// Pyth SOL/USD feed. Lamport-only settings leave the day untouched.
+fn apply_usd_minimums(
+    auction_day: &mut AuctionDay,
+    config: &Config,
+    usd_price_feed: Option<&UncheckedAccount>,
+    now: i64,
+) -> Result<()> {
+    if config.usd_price_feed == Pubkey::default()
+        || (config.min_increment_usd_cents == 0 && config.min_opening_bid_usd_cents == 0)
+    {
+        return Ok(());
+    }
+    let usd_price_feed = usd_price_feed.ok_or(ErrorCode::UsdPriceFeedMissing)?;
+    require_keys_eq!(
+        usd_price_feed.key(),
+        config.usd_price_feed,
+        ErrorCode::UsdPriceFeedMismatch
+    );
+    let price = PythPrice::try_from_account(usd_price_feed)?;
+    require!(
+        now.saturating_sub(price.publish_time) <= config.usd_max_staleness_seconds,
+        ErrorCode::UsdPriceStale
+    );
+    if config.min_increment_usd_cents > 0 {
+        auction_day.min_increment_lamports =
+            price.cents_to_lamports(config.min_increment_usd_cents)?;
+    }
+    if config.min_opening_bid_usd_cents > 0 {
+        auction_day.min_opening_bid_lamports =
+            price.cents_to_lamports(config.min_opening_bid_usd_cents)?;
+    }
+    Ok(())
+}
+
+// Structured compute markers for tracking regressions from transaction logs.
+// Compiled out unless the `compute-log` feature is enabled.
+#[inline(always)]
+fn log_compute(instruction: &str, section: &str) {
+    #[cfg(feature = "compute-log")]
+    {
+        msg!("compute ix={} section={}", instruction, section);
+        #[cfg(target_os = "solana")]
+        unsafe {
+            solana_msg::syscalls::sol_log_compute_units_()
+        };
+    }
+    #[cfg(not(feature = "compute-log"))]
+    let _ = (instruction, section);
+}
+
+// Returns true when `key` was already recorded by a previous crank on this day.
+// Otherwise records it; a failed instruction reverts the record with everything else.
+fn is_crank_replay(
+    auction_day: &mut AuctionDay,
+    key: Option<[u8; CRANK_KEY_LEN]>,
+) -> Result<bool> {
+    let Some(key) = key else {
+        return Ok(false);
+    };
+    require!(key != [0u8; CRANK_KEY_LEN], ErrorCode::InvalidIdempotencyKey);
+    if auction_day.recent_crank_keys.contains(&key) {
+        return Ok(true);
+    }
+    let slot = auction_day.crank_key_cursor as usize % CRANK_KEY_HISTORY;
+    auction_day.recent_crank_keys[slot] = key;
+    auction_day.crank_key_cursor = ((slot + 1) % CRANK_KEY_HISTORY) as u8;
+    Ok(false)
+}
+
+fn is_uninitialized_auction_day(auction_day: &AuctionDay) -> bool {
+    auction_day.day_index == 0
+        && !auction_day.finalized
+        && auction_day.winner == Pubkey::default()
+        && auction_day.highest_bid == 0
+        && auction_day.bidder_count == 0
+        && auction_day.refund_count_total == 0
+        && auction_day.refund_count_completed == 0
+        && auction_day.total_bid_lamports == 0
+        && auction_day.refund_pool_remaining == 0
+        && auction_day.fee_pool_remaining == 0
+}
+
+fn close_program_account<'info>(
+    info: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = info.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ErrorCode::MathOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+    info.assign(&system_program::ID);
+    info.resize(0)?;
+    Ok(())
+}
+
+// Pays tokens out of a token day's vault; the day's vault PDA is the token
+// account's authority. The vault sends exactly `amount`, so pools stay solvent
+// under Token-2022 transfer fees: the fee comes out of what `to` receives.
+fn transfer_from_token_vault<'info>(
+    auction_day: &Account<'info, AuctionDay>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    to: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let auction_day_key = auction_day.key();
+    let seeds: &[&[u8]] = &[b"vault", auction_day_key.as_ref(), &[auction_day.vault_bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.clone(),
+        TransferChecked {
+            from: token_vault.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to.clone(),
+            authority: vault.clone(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+}
+
+// What a sender must transfer for `net` to arrive: `net` plus the mint's
+// current Token-2022 transfer fee, if it has one.
+fn gross_for_net(mint: &InterfaceAccount<Mint>, net: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    if mint_info.owner != &spl_token_2022::ID {
+        return Ok(net);
+    }
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let Ok(fee_config) = state.get_extension::<TransferFeeConfig>() else {
+        return Ok(net);
+    };
+    let gross = fee_config
+        .get_epoch_fee(Clock::get()?.epoch)
+        .calculate_pre_fee_amount(net)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(gross)
+}
+
+// Pays out of a system-owned PDA with the single seed `seed`, such as the
+// promo pool or the protocol treasury.
+fn transfer_from_system_pda<'info>(
+    pda: &AccountInfo<'info>,
+    seed: &[u8],
+    bump: u8,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[seed, &[bump]];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Transfer {
+            from: pda.clone(),
+            to: to.clone(),
+        },
+        signer_seeds,
+    );
+    transfer(cpi_ctx, amount)
+}
+
+// Moves a receipt's escrowed refund tip to `to` (the refunding cranker, or the
+// bidder when no cranker earned it).
+fn pay_refund_tip<'info>(
+    receipt_info: &AccountInfo<'info>,
+    receipt: &mut BidReceipt,
+    to: &AccountInfo<'info>,
+) -> Result<()> {
+    let tip = receipt.refund_tip_lamports;
+    if tip > 0 {
+        receipt_info.sub_lamports(tip)?;
+        to.add_lamports(tip)?;
+        receipt.refund_tip_lamports = 0;
+    }
+    Ok(())
+}
+
+// Returns an armed counter-raise's escrow and disarms it.
+fn release_counter_raise<'info>(
+    receipt_info: &AccountInfo<'info>,
+    receipt: &mut BidReceipt,
+    to: &AccountInfo<'info>,
+) -> Result<()> {
+    let escrow = receipt.counter_raise_escrow;
+    if escrow > 0 {
+        receipt_info.sub_lamports(escrow)?;
+        to.add_lamports(escrow)?;
+    }
+    receipt.counter_raise_escrow = 0;
+    receipt.counter_raise_increment = 0;
+    receipt.counter_raise_max_bid = 0;
+    receipt.counter_raise_tip_lamports = 0;
+    Ok(())
+}
+
+// Parses an Ed25519 program instruction carrying exactly one signature whose
+// key and message live in the instruction itself. Returns `(signer, message)`;
+// the runtime already rejected the transaction if the signature was invalid.
+fn ed25519_single_signature(data: &[u8]) -> Result<(Pubkey, &[u8])> {
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const SELF: u16 = u16::MAX;
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidSignatureInstruction
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    require!(
+        read_u16(4) == SELF && read_u16(8) == SELF && read_u16(14) == SELF,
+        ErrorCode::InvalidSignatureInstruction
+    );
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    Ok((Pubkey::try_from(public_key).unwrap(), message))
+}
+
+fn write_bid_receipt(account_info: &AccountInfo, receipt: &BidReceipt) -> Result<()> {
+    let mut data = account_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    receipt.try_serialize(&mut writer)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"config"],
+        bump,
+        space = Config::SPACE
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct InitDay<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = AuctionDay::SPACE
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump,
+        space = 0,
+        owner = system_program::ID
+    )]
+    /// CHECK: PDA vault is system-owned (enforced by owner constraint + runtime checks).
+    pub vault: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"category_table"],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `apply_day_category` for categorized days.
+    pub category_table: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = AuctionDay::SPACE
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump,
+        space = 0,
+        owner = system_program::ID
+    )]
+    /// CHECK: PDA vault is system-owned (enforced by owner constraint + runtime checks).
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = BidReceipt::SPACE
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    /// CHECK: validated against `config.floor_adapter_account` and its owner program.
+    pub price_floor: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"category_table"],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `apply_day_category` for categorized days.
+    pub category_table: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bidder_stats", bidder.key().as_ref()],
+        bump,
+        space = BidderStats::SPACE
+    )]
+    pub bidder_stats: Option<Account<'info, BidderStats>>,
+    #[account(mut)]
+    /// CHECK: `BidIndex` PDA for `auction_day.bid_index_count`; verified and
+    /// created by `record_bid_index` when the bid opens a new receipt.
+    pub bid_index: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"global_stats"],
+        bump,
+        space = GlobalStats::SPACE
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bidder_profile", bidder.key().as_ref()],
+        bump,
+        space = BidderProfile::SPACE
+    )]
+    pub bidder_profile: Option<Account<'info, BidderProfile>>,
+    /// CHECK: only its address is recorded on a newly opened receipt.
+    pub referrer: Option<UncheckedAccount<'info>>,
+    // Closed into the bidder's wallet before the bid is paid (see `place_bid`).
+    #[account(
+        mut,
+        token::mint = anchor_spl::token::spl_token::native_mint::ID,
+        token::authority = bidder
+    )]
+    pub wsol_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// CHECK: validated against `config.usd_price_feed` and the Pyth owner.
+    pub usd_price_feed: Option<UncheckedAccount<'info>>,
+    // Proof of holding for gated days; see `require_bid_gate`.
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// CHECK: Metaplex metadata PDA of the gate token's mint; parsed by hand.
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+    // Required while `config.access_mode` is on; usually uninitialized.
+    #[account(seeds = [b"access", bidder.key().as_ref()], bump)]
+    /// CHECK: PDA address only; read as an `AccessEntry` when it exists.
+    pub access_entry: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64, new_amount: u64, refund_day_index: i64)]
+pub struct NetBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = AuctionDay::SPACE
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump,
+        space = 0,
+        owner = system_program::ID
+    )]
+    /// CHECK: PDA vault is system-owned (enforced by owner constraint + runtime checks).
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = BidReceipt::SPACE
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", refund_day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub refund_auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", refund_auction_day.key().as_ref()],
+        bump = refund_auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `refund_auction_day.vault_version`.
+    pub refund_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", refund_auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub refund_receipt: Account<'info, BidReceipt>,
+    /// CHECK: validated against `config.floor_adapter_account` and its owner program.
+    pub price_floor: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"category_table"],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `apply_day_category` for categorized days.
+    pub category_table: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bidder_stats", bidder.key().as_ref()],
+        bump,
+        space = BidderStats::SPACE
+    )]
+    pub bidder_stats: Option<Account<'info, BidderStats>>,
+    #[account(mut)]
+    /// CHECK: `BidIndex` PDA for `auction_day.bid_index_count`; verified and
+    /// created by `record_bid_index` when the bid opens a new receipt.
+    pub bid_index: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ScheduleBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"scheduled_bid", day_index.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = ScheduledBid::SPACE
+    )]
+    pub scheduled_bid: Account<'info, ScheduledBid>,
+    #[account(
+        seeds = [b"authorization_epoch", bidder.key().as_ref()],
+        bump = authorization_epoch.bump
+    )]
+    pub authorization_epoch: Option<Account<'info, AuthorizationEpoch>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ExecuteScheduledBid<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut, address = scheduled_bid.owner)]
+    /// CHECK: receives the closed schedule's leftover escrow and rent.
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = AuctionDay::SPACE
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump,
+        space = 0,
+        owner = system_program::ID
+    )]
+    /// CHECK: PDA vault is system-owned (enforced by owner constraint + runtime checks).
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), owner.key().as_ref()],
+        bump,
+        space = BidReceipt::SPACE
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"scheduled_bid", day_index.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump = scheduled_bid.bump
+    )]
+    pub scheduled_bid: Account<'info, ScheduledBid>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"authorization_epoch", owner.key().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `authorization_epoch_of`.
+    pub authorization_epoch: UncheckedAccount<'info>,
+    /// CHECK: validated against `config.floor_adapter_account` and its owner program.
+    pub price_floor: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"category_table"],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `apply_day_category` for categorized days.
+    pub category_table: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"bidder_stats", owner.key().as_ref()],
+        bump,
+        space = BidderStats::SPACE
+    )]
+    pub bidder_stats: Option<Account<'info, BidderStats>>,
+    #[account(mut)]
+    /// CHECK: `BidIndex` PDA for `auction_day.bid_index_count`; verified and
+    /// created by `record_bid_index` when the bid opens a new receipt.
+    pub bid_index: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CancelScheduledBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"scheduled_bid", day_index.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump = scheduled_bid.bump
+    )]
+    pub scheduled_bid: Account<'info, ScheduledBid>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetCounterRaise<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    #[account(
+        seeds = [b"authorization_epoch", bidder.key().as_ref()],
+        bump = authorization_epoch.bump
+    )]
+    pub authorization_epoch: Option<Account<'info, AuthorizationEpoch>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CancelCounterRaise<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ExecuteCounterRaise<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut, address = bid_receipt.bidder)]
+    /// CHECK: receives the counter-raise's leftover escrow.
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault is system-owned while the day is open.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bid_receipt.bidder.as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"authorization_epoch", owner.key().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `authorization_epoch_of`.
+    pub authorization_epoch: UncheckedAccount<'info>,
+    /// CHECK: validated against `config.floor_adapter_account` and its owner program.
+    pub price_floor: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"bidder_stats", owner.key().as_ref()],
+        bump
+    )]
+    pub bidder_stats: Option<Account<'info, BidderStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CommitBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = AuctionDay::SPACE
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump,
+        space = 0,
+        owner = system_program::ID
+    )]
+    /// CHECK: PDA vault is system-owned (enforced by owner constraint + runtime checks).
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = BidReceipt::SPACE
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"sealed_bid", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = SealedBid::SPACE
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"category_table"],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `apply_day_category` for categorized days.
+    pub category_table: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: `BidIndex` PDA for `auction_day.bid_index_count`; verified and
+    /// created by `record_bid_index` when the bid opens a new receipt.
+    pub bid_index: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct RevealBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    #[account(
+        mut,
+        seeds = [b"sealed_bid", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBid>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"bidder_stats", bidder.key().as_ref()],
+        bump
+    )]
+    pub bidder_stats: Option<Account<'info, BidderStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SettleDay<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault is system-owned (enforced by runtime check).
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: recipient is validated against config.
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"display_hook", day_index.to_le_bytes().as_ref()],
+        bump = display_hook.bump
+    )]
+    pub display_hook: Option<Account<'info, DayDisplayHook>>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), auction_day.winner.as_ref()],
+        bump
+    )]
+    pub winner_receipt: Option<Account<'info, BidReceipt>>,
+    #[account(mut, address = auction_day.winner)]
+    /// CHECK: receives the winner receipt's refund tip and counter-raise escrow.
+    pub winner: Option<UncheckedAccount<'info>>,
+    #[account(address = config.authority)]
+    pub authority: Option<Signer<'info>>,
+    // Receives `settle_reward_lamports`; settling without it forgoes the reward.
+    #[account(mut)]
+    pub settler: Option<Signer<'info>>,
+    #[account(mut, seeds = [b"global_stats"], bump)]
+    /// CHECK: `GlobalStats` PDA, uninitialized until the first `place_bid`.
+    pub global_stats: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"bidder_profile", auction_day.winner.as_ref()], bump)]
+    /// CHECK: `BidderProfile` PDA, possibly uninitialized (see `record_winner_profile`).
+    pub winner_profile: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"jackpot"], bump)]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+    #[account(mut, seeds = [b"protocol_treasury"], bump)]
+    /// CHECK: system-owned PDA collecting the protocol rake.
+    pub protocol_treasury: Option<UncheckedAccount<'info>>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Option<Account<'info, Treasury>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct RecordParams<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"param_history"],
+        bump,
+        space = ParamHistory::SPACE
+    )]
+    pub param_history: Box<Account<'info, ParamHistory>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct PublishPrice<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"price_series"],
+        bump,
+        space = PriceSeries::SPACE
+    )]
+    pub price_series: Account<'info, PriceSeries>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SweepUnclaimed<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: must equal `config.recipient_pubkey`.
+    pub recipient: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(mut, address = INCINERATOR)]
+    /// CHECK: fixed burn address; required when the sweep burns fees.
+    pub incinerator: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct RefundBatch<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"cranker_registry"],
+        bump = cranker_registry.bump
+    )]
+    pub cranker_registry: Option<Account<'info, CrankerRegistry>>,
+    #[account(
+        seeds = [b"cranker_bond", cranker.key().as_ref()],
+        bump = cranker_bond.bump
+    )]
+    pub cranker_bond: Option<Account<'info, CrankerBond>>,
+    // Participation cNFT accounts, required while `auction_day.receipt_tree` is set.
+    #[account(address = BUBBLEGUM_PROGRAM)]
+    /// CHECK: program address pinned above.
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: Bubblegum's `TreeConfig` for `merkle_tree`; Bubblegum verifies it.
+    pub tree_config: Option<UncheckedAccount<'info>>,
+    #[account(mut, address = auction_day.receipt_tree @ ErrorCode::ReceiptTreeMismatch)]
+    /// CHECK: the day's receipt tree; Bubblegum verifies it.
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"cnft_authority"], bump)]
+    /// CHECK: PDA that signs as the tree delegate; holds nothing.
+    pub cnft_authority: Option<UncheckedAccount<'info>>,
+    #[account(address = SPL_NOOP_PROGRAM)]
+    /// CHECK: program address pinned above.
+    pub log_wrapper: Option<UncheckedAccount<'info>>,
+    #[account(address = SPL_ACCOUNT_COMPRESSION_PROGRAM)]
+    /// CHECK: program address pinned above.
+    pub compression_program: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct RefundNext<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"cranker_registry"],
+        bump = cranker_registry.bump
+    )]
+    pub cranker_registry: Option<Account<'info, CrankerRegistry>>,
+    #[account(
+        seeds = [b"cranker_bond", cranker.key().as_ref()],
+        bump = cranker_bond.bump
+    )]
+    pub cranker_bond: Option<Account<'info, CrankerBond>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ClaimRefundWithProof<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(mut, address = bid_receipt.bidder)]
+    /// CHECK: receives the proven refund.
+    pub bidder: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bid_receipt.bidder.as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"cranker_registry"],
+        bump = cranker_registry.bump
+    )]
+    pub cranker_registry: Option<Account<'info, CrankerRegistry>>,
+    #[account(
+        seeds = [b"cranker_bond", caller.key().as_ref()],
+        bump = cranker_bond.bump
+    )]
+    pub cranker_bond: Option<Account<'info, CrankerBond>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CloseDay<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = rent_destination,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault; `require_vault_owner` checks it against `vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, address = config.day_rent_destination)]
+    /// CHECK: rent destination fixed by config.
+    pub rent_destination: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CloseBidReceipt<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CloseReceiptsBatch<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    // Receipts are only closable once refunds are certified complete.
+    #[account(
+        seeds = [b"day_complete", auction_day.key().as_ref()],
+        bump = day_complete.bump
+    )]
+    pub day_complete: Account<'info, DayComplete>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAllAuthorizations<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"authorization_epoch", owner.key().as_ref()],
+        bump,
+        space = AuthorizationEpoch::SPACE
+    )]
+    pub authorization_epoch: Account<'info, AuthorizationEpoch>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct MigrateVault<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetDisplayPayload<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"display_hook", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = DayDisplayHook::SPACE
+    )]
+    pub display_hook: Account<'info, DayDisplayHook>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetScheduleOverride<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; the day may not exist yet.
+    pub auction_day: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = ScheduleOverride::SPACE
+    )]
+    pub schedule_override: Account<'info, ScheduleOverride>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetDayMeta<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; the day may not exist yet.
+    pub auction_day: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"day_meta", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = DayMeta::SPACE
+    )]
+    pub day_meta: Account<'info, DayMeta>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetAccessEntry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"access", wallet.as_ref()],
+        bump,
+        space = AccessEntry::SPACE
+    )]
+    pub access_entry: Account<'info, AccessEntry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCranker<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"cranker_registry"],
+        bump,
+        space = CrankerRegistry::SPACE
+    )]
+    pub cranker_registry: Account<'info, CrankerRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCategoryRules<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"category_table"],
+        bump,
+        space = CategoryTable::SPACE
+    )]
+    pub category_table: Account<'info, CategoryTable>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetDayCategory<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; must not be initialized yet.
+    pub auction_day: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump,
+        space = ScheduleOverride::SPACE
+    )]
+    pub schedule_override: Account<'info, ScheduleOverride>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CancelDay<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetBuyNowPrice<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetReceiptTree<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CertifyDayComplete<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"day_complete", auction_day.key().as_ref()],
+        bump,
+        space = DayComplete::SPACE
+    )]
+    pub day_complete: Account<'info, DayComplete>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct VerifyWinner<'info> {
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    /// CHECK: instructions sysvar, used to read the preceding Ed25519 check.
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(action: DestructiveAction, target: Pubkey)]
+pub struct ProposeDestructiveAction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pending_action", target.as_ref()],
+        bump,
+        space = PendingAdminAction::SPACE
+    )]
+    pub pending_action: Account<'info, PendingAdminAction>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(target: Pubkey)]
+pub struct CancelDestructiveAction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_action", target.as_ref()],
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAdminAction>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(target: Pubkey)]
+pub struct WarnDestructiveAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"pending_action", target.as_ref()],
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAdminAction>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResetGlobalStats<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_action", global_stats.key().as_ref()],
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAdminAction>,
+    #[account(mut, seeds = [b"global_stats"], bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseGlobalStats<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_action", global_stats.key().as_ref()],
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAdminAction>,
+    #[account(mut, close = authority, seeds = [b"global_stats"], bump)]
+    pub global_stats: Account<'info, GlobalStats>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SyncProgramInfo<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"program_info"],
+        bump,
+        space = ProgramInfo::SPACE
+    )]
+    pub program_info: Account<'info, ProgramInfo>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ResolveCandle<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault is system-owned while the day is open.
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: validated against `config.randomness_account` and its owner program.
+    pub randomness: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: must be the leader of the drawn slot; receives any post-cutoff excess.
+    pub winner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), winner.key().as_ref()],
+        bump
+    )]
+    pub winner_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct FollowDay<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"follower", auction_day.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = Follower::SPACE
+    )]
+    pub follower: Account<'info, Follower>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct UnfollowDay<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"follower", auction_day.key().as_ref(), user.key().as_ref()],
+        bump = follower.bump
+    )]
+    pub follower: Account<'info, Follower>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetContentManager<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = winner,
+        seeds = [b"content_manager", auction_day.key().as_ref()],
+        bump,
+        space = ContentManager::SPACE
+    )]
+    pub content_manager: Account<'info, ContentManager>,
+    #[account(
+        seeds = [b"authorization_epoch", winner.key().as_ref()],
+        bump = authorization_epoch.bump
+    )]
+    pub authorization_epoch: Option<Account<'info, AuthorizationEpoch>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct WithdrawBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault is system-owned while the day is open.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct RedeemVoucher<'info> {
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    #[account(mut)]
+    /// CHECK: any account chosen by the bidder to receive the voucher.
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceBundleBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: validated against `config.floor_adapter_account` and its owner program.
+    pub price_floor: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"category_table"],
+        bump
+    )]
+    /// CHECK: PDA address only; read by `apply_day_category` for categorized days.
+    pub category_table: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bidder_stats", bidder.key().as_ref()],
+        bump,
+        space = BidderStats::SPACE
+    )]
+    pub bidder_stats: Option<Account<'info, BidderStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct PlacePromoBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"promo_pool"],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding operator promo funds.
+    pub promo_pool: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = bidder,
+        seeds = [b"promo_bid", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = PromoBid::SPACE
+    )]
+    pub promo_bid: Account<'info, PromoBid>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundPromoPool<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"promo_pool"],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding operator promo funds.
+    pub promo_pool: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(feature = "devnet-faucet")]
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FaucetDrip<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"faucet"],
+        bump
+    )]
+    /// CHECK: data-less PDA; only signs as the play-money mint authority.
+    pub faucet: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        seeds = [b"faucet_claim", wallet.key().as_ref()],
+        bump,
+        space = FaucetClaim::SPACE
+    )]
+    pub faucet_claim: Account<'info, FaucetClaim>,
+    #[account(mut, address = config.bid_mint @ ErrorCode::BidCurrencyMismatch)]
+    pub bid_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = wallet,
+        token::token_program = token_program
+    )]
+    pub wallet_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPromoPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"promo_pool"],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding operator promo funds.
+    pub promo_pool: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury"],
+        bump
+    )]
+    /// CHECK: system-owned PDA collecting the protocol rake.
+    pub protocol_treasury: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64, bidder: Pubkey)]
+pub struct ReclaimPromoBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        close = promo_pool,
+        seeds = [b"promo_bid", auction_day.key().as_ref(), bidder.as_ref()],
+        bump = promo_bid.bump
+    )]
+    pub promo_bid: Account<'info, PromoBid>,
+    #[account(
+        mut,
+        seeds = [b"promo_pool"],
+        bump
+    )]
+    /// CHECK: system-owned PDA holding operator promo funds.
+    pub promo_pool: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetRefundTip<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct Poke<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        seeds = [b"content", auction_day.key().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; initialized once the winner submits content.
+    pub content: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SubmitContent<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = winner,
+        seeds = [b"content", auction_day.key().as_ref()],
+        bump,
+        space = DayContent::SPACE
+    )]
+    pub content: Account<'info, DayContent>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ReleaseContentBond<'info> {
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        close = winner,
+        has_one = winner,
+        seeds = [b"content", auction_day.key().as_ref()],
+        bump = content.bump
+    )]
+    pub content: Account<'info, DayContent>,
+    #[account(mut)]
+    /// CHECK: must match `content.winner`; receives the bond and rent.
+    pub winner: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct CloseSlashedContent<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [b"content", auction_day.key().as_ref()],
+        bump = content.bump
+    )]
+    pub content: Account<'info, DayContent>,
+    #[account(mut, address = config.recipient_pubkey @ ErrorCode::RecipientMismatch)]
+    /// CHECK: receives the rent; pinned to `config.recipient_pubkey`.
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SlashContentBond<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"content", auction_day.key().as_ref()],
+        bump = content.bump
+    )]
+    pub content: Account<'info, DayContent>,
+    /// CHECK: recipient is validated against config.
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct FundVaultRent<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault; `require_vault_owner` checks it against `vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ClaimVested<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: must equal `config.recipient_pubkey`.
+    pub recipient: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64, bidder: Pubkey)]
+pub struct ClaimReferralFee<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.as_ref()],
+        bump,
+        has_one = referrer @ ErrorCode::ReferrerMismatch
+    )]
+    pub bid_receipt: Account<'info, BidReceipt>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SweepReferralFees<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault, owner validated against `auction_day.vault_version`.
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: must equal `config.recipient_pubkey`.
+    pub recipient: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"treasury"],
+        bump,
+        space = Treasury::SPACE
+    )]
+    pub treasury: Account<'info, Treasury>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    /// CHECK: any account the authority directs the withdrawal to.
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct PlaceBidToken<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the token vault.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = BidReceipt::SPACE
+    )]
+    pub bid_receipt: Box<Account<'info, BidReceipt>>,
+    #[account(address = auction_day.bid_mint @ ErrorCode::BidCurrencyMismatch)]
+    pub bid_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"token_vault", auction_day.key().as_ref()],
+        bump,
+        token::mint = bid_mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = bidder,
+        token::token_program = token_program
+    )]
+    pub bidder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    /// CHECK: `BidIndex` PDA for `auction_day.bid_index_count`; verified and
+    /// created by `record_bid_index` when the bid opens a new receipt.
+    pub bid_index: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SettleDayToken<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the token vault.
+    pub vault: UncheckedAccount<'info>,
+    #[account(address = auction_day.bid_mint @ ErrorCode::BidCurrencyMismatch)]
+    pub bid_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", auction_day.key().as_ref()],
+        bump,
+        token::mint = bid_mint,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = config.recipient_pubkey,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct RefundBatchToken<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the token vault.
+    pub vault: UncheckedAccount<'info>,
+    #[account(address = auction_day.bid_mint @ ErrorCode::BidCurrencyMismatch)]
+    pub bid_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", auction_day.key().as_ref()],
+        bump,
+        token::mint = bid_mint,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = config.recipient_pubkey,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = cranker,
+        token::token_program = token_program
+    )]
+    pub cranker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct PlaceBidMulti<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the token vaults.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
+        bump,
+        space = BidReceipt::SPACE
+    )]
+    pub bid_receipt: Box<Account<'info, BidReceipt>>,
+    // Checked against `auction_day.quote_mints` in the handler.
+    pub bid_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: validated against the mint's `QuoteMint.price_feed` and the Pyth owner.
+    pub price_feed: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [b"token_vault", auction_day.key().as_ref(), bid_mint.key().as_ref()],
+        bump,
+        token::mint = bid_mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = bidder,
+        token::token_program = token_program
+    )]
+    pub bidder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"schedule_override", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: PDA address only; usually uninitialized (see `is_day_closed`).
+    pub schedule_override: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    /// CHECK: `BidIndex` PDA for `auction_day.bid_index_count`; verified and
+    /// created by `record_bid_index` when the bid opens a new receipt.
+    pub bid_index: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SettleDayMulti<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the token vaults.
+    pub vault: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct RefundBatchMulti<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        mut,
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the token vaults.
+    pub vault: UncheckedAccount<'info>,
+    pub bid_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_vault", auction_day.key().as_ref(), bid_mint.key().as_ref()],
+        bump,
+        token::mint = bid_mint,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = config.recipient_pubkey,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = bid_mint,
+        token::authority = cranker,
+        token::token_program = token_program
+    )]
+    pub cranker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct DepositPrize<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the prize vault.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"prize", auction_day.key().as_ref()],
+        bump,
+        space = Prize::SPACE
+    )]
+    pub prize: Box<Account<'info, Prize>>,
+    pub prize_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"prize_vault", auction_day.key().as_ref()],
+        bump,
+        token::mint = prize_mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub prize_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = prize_mint,
+        token::authority = authority,
+        token::token_program = token_program
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ClaimPrize<'info> {
+    pub winner: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the prize vault.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"prize", auction_day.key().as_ref()],
+        bump = prize.bump
+    )]
+    pub prize: Box<Account<'info, Prize>>,
+    #[account(address = prize.mint @ ErrorCode::TokenAccountMismatch)]
+    pub prize_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"prize_vault", auction_day.key().as_ref()],
+        bump,
+        token::mint = prize_mint,
+        token::token_program = token_program
+    )]
+    pub prize_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = prize_mint,
+        token::authority = winner,
+        token::token_program = token_program
+    )]
+    pub winner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ReclaimPrize<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: the day's vault PDA; only signs for the prize vault.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"prize", auction_day.key().as_ref()],
+        bump = prize.bump
+    )]
+    pub prize: Box<Account<'info, Prize>>,
+    #[account(address = prize.mint @ ErrorCode::TokenAccountMismatch)]
+    pub prize_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"prize_vault", auction_day.key().as_ref()],
+        bump,
+        token::mint = prize_mint,
+        token::token_program = token_program
+    )]
+    pub prize_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = prize_mint,
+        token::token_program = token_program
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct MintTrophy<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Box<Account<'info, AuctionDay>>,
+    #[account(address = auction_day.winner @ ErrorCode::Unauthorized)]
+    /// CHECK: the day's winner; only receives the trophy.
+    pub winner: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"trophy", auction_day.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = trophy_mint,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = trophy_mint,
+        extensions::metadata_pointer::metadata_address = trophy_mint
+    )]
+    pub trophy_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = trophy_mint,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program
+    )]
+    pub winner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SetMessage<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [b"winner_message", auction_day.key().as_ref()],
+        bump,
+        space = WinnerMessage::SPACE
+    )]
+    pub winner_message: Account<'info, WinnerMessage>,
+    pub system_program: Program<'info, System>,
+    // The day's deed. Once minted, `deed_token_account` must be the signer's
+    // account holding it.
+    #[account(seeds = [b"trophy", auction_day.key().as_ref()], bump)]
+    /// CHECK: may not exist yet; only its key and emptiness are read.
+    pub trophy_mint: UncheckedAccount<'info>,
+    pub deed_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct ModerateMessage<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"winner_message", auction_day.key().as_ref()],
+        bump = winner_message.bump
+    )]
+    pub winner_message: Account<'info, WinnerMessage>,
+}
+
+#[derive(Accounts)]
+pub struct FundJackpot<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = funder,
+        seeds = [b"jackpot"],
+        bump,
+        space = Jackpot::SPACE
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct GetMinNextBid<'info> {
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+}
+
+// Return data of `get_min_next_bid`. On penny days (`exact`) the next bid must
+// equal `min_next_bid` rather than reach it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MinNextBid {
+    pub day_index: i64,
+    pub min_next_bid: u64,
+    pub exact: bool,
+    pub leader: Pubkey,
+    pub highest_bid: u64,
+    pub bidding_open: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct GetDayStatus<'info> {
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+}
+
+// Return data of `get_day_status`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DayStatus {
+    pub day_index: i64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub bidding_closed: bool,
+    pub finalized: bool,
+    pub cancelled: bool,
+    pub winner: Pubkey,
+    pub highest_bid: u64,
+    pub bidder_count: u32,
+    pub total_bid_lamports: u64,
+    pub refund_count_total: u32,
+    pub refund_count_completed: u32,
+    pub refund_pool_remaining: u64,
+    pub fee_pool_remaining: u64,
+    pub seq: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BondCranker<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        seeds = [b"cranker_bond", cranker.key().as_ref()],
+        bump,
+        space = CrankerBond::SPACE
+    )]
+    pub cranker_bond: Account<'info, CrankerBond>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = cranker,
+        seeds = [b"cranker_bond", cranker.key().as_ref()],
+        bump = cranker_bond.bump
+    )]
+    pub cranker_bond: Account<'info, CrankerBond>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCrankerBond<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        close = cranker,
+        has_one = cranker,
+        seeds = [b"cranker_bond", cranker.key().as_ref()],
+        bump = cranker_bond.bump
+    )]
+    pub cranker_bond: Account<'info, CrankerBond>,
+}
+
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct VerifyDay<'info> {
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault; only its balance is read.
+    pub vault: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day_index: i64)]
+pub struct SlashCranker<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction_day: Account<'info, AuctionDay>,
+    #[account(
+        mut,
+        seeds = [b"vault", auction_day.key().as_ref()],
+        bump = auction_day.vault_bump
+    )]
+    /// CHECK: PDA vault; receives the slashed lamports.
+    pub vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"cranker_bond", auction_day.last_refund_cranker.as_ref()],
+        bump = cranker_bond.bump
+    )]
+    pub cranker_bond: Account<'info, CrankerBond>,
+}
+
+#[account]
+pub struct Config {
+    pub recipient_pubkey: Pubkey,
+    pub loser_fee_lamports: u64,
+    pub min_increment_lamports: u64,
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub min_increment_bps: u16,
+    pub loser_fee_bps: u16,
+    pub loser_fee_cap_lamports: u64,
+    pub destructive_action_delay_seconds: i64,
+    pub min_opening_bid_lamports: u64,
+    pub bps_rounding: RoundingPolicy,
+    pub soft_close_window_seconds: i64,
+    pub soft_close_extension_seconds: i64,
+    pub priority_lane_seconds: i64,
+    pub floor_adapter_program: Pubkey,
+    pub floor_adapter_account: Pubkey,
+    pub floor_max_staleness_seconds: i64,
+    pub day_offset_seconds: i64,
+    pub receipt_rent_policy: ReceiptRentPolicy,
+    pub sealed_reveal_seconds: i64,
+    pub penny_tick_lamports: u64,
+    pub penny_bid_fee_lamports: u64,
+    pub penny_timer_seconds: i64,
+    pub candle_window_seconds: i64,
+    pub randomness_program: Pubkey,
+    pub randomness_account: Pubkey,
+    pub early_bird_window_seconds: i64,
+    pub early_bird_discount_bps: u16,
+    pub max_vault_exposure_lamports: u64,
+    pub open_exposure_lamports: u64,
+    pub winner_slots: u8,
+    pub content_bond_lamports: u64,
+    pub withdraw_cutoff_seconds: i64,
+    pub withdraw_penalty_bps: u16,
+    pub paused_instructions: u64,
+    pub promo_bid_max_lamports: u64,
+    pub min_bidders: u32,
+    pub refund_window_seconds: i64,
+    pub participation_cap_base_lamports: u64,
+    pub participation_cap_step_lamports: u64,
+    pub participation_veteran_days: u32,
+    // Receives AuctionDay and vault rent from `close_day`.
+    pub day_rent_destination: Pubkey,
+    // Paid from the fee pool to whoever settles a day; 0 disables it.
+    pub settle_reward_lamports: u64,
+    // Restricts refund cranks to the `CrankerRegistry`.
+    pub permissioned_refunds: bool,
+    // Share of each loser fee paid to the cranker; the rest goes to the
+    // recipient at sweep time.
+    pub cranker_fee_share_bps: u16,
+    // Bond `refund_batch` and `refund_next` require of crankers; 0 = none.
+    pub min_cranker_bond_lamports: u64,
+    // Vault surplus found at settlement goes to the winner instead of the recipient.
+    pub surplus_to_winner: bool,
+    // Every `streak_bonus_threshold`-th consecutive daily win rebates
+    // `streak_bonus_bps` of the winning bid to the winner; 0 disables it.
+    pub streak_bonus_threshold: u32,
+    pub streak_bonus_bps: u16,
+    // Share of a referred bidder's loser fee set aside for the referrer.
+    pub referral_fee_bps: u16,
+    // Moved from the jackpot reserve into the pot by each bidless or
+    // cancelled day; 0 disables rollovers.
+    pub jackpot_rollover_lamports: u64,
+    // Share of a settled day's penny fees and withdrawal penalties paid into
+    // the jackpot reserve.
+    pub jackpot_fee_bps: u16,
+    // Shares of each settlement payout taken off the recipient's; only the
+    // first `recipient_split_count` entries are used.
+    pub recipient_splits: [RecipientSplit; MAX_RECIPIENT_SPLITS],
+    pub recipient_split_count: u8,
+    // Share of each day's treasury fees burned at `sweep_unclaimed`.
+    pub burn_bps: u16,
+    // Lifetime lamports sent to the incinerator.
+    pub total_burned_lamports: u64,
+    // Share of the winning bid `settle_day` pays to the protocol treasury.
+    pub protocol_rake_bps: u16,
+    // `settle_day` pays the recipient's share into the `Treasury` PDA
+    // instead of `recipient_pubkey`.
+    pub treasury_custody: bool,
+    // Days over which settlement proceeds vest to the recipient; 0 pays at once.
+    pub vesting_days: u32,
+    // SPL mint new days take bids in (`place_bid_token`); default = lamports.
+    pub bid_mint: Pubkey,
+    // Pyth SOL/USD `PriceUpdateV2` account `place_bid` reads USD minimums with.
+    pub usd_price_feed: Pubkey,
+    pub usd_max_staleness_seconds: i64,
+    // Non-zero values replace the lamport minimums at each `place_bid`.
+    pub min_increment_usd_cents: u64,
+    pub min_opening_bid_usd_cents: u64,
+    // Whitelist for multi-currency days (`place_bid_multi`); the first
+    // `quote_mint_count` entries are live.
+    pub quote_mints: [QuoteMint; MAX_QUOTE_MINTS],
+    pub quote_mint_count: u8,
+    // Bubblegum tree `refund_batch` mints participation cNFTs into; the
+    // `["cnft_authority"]` PDA must be its tree delegate. Default = off.
+    pub receipt_tree: Pubkey,
+    // Bytes a winner message may grow to; `set_message` reallocs past the
+    // initial 280 at the winner's expense. Values below 280 act as 280.
+    pub winner_message_max_len: u16,
+    // Days after a day's end its winner has to claim the prize and post the
+    // message before the authority may take them back; 0 = never.
+    pub prize_claim_window_days: u32,
+    // Members-only days: bidders must hold this mint, or with
+    // `bid_gate_collection` an NFT of this verified Metaplex collection.
+    // Default = open to everyone.
+    pub bid_gate: Pubkey,
+    pub bid_gate_collection: bool,
+    // `ACCESS_MODE_*`. Applies live to every bid, unlike the day snapshots.
+    pub access_mode: u8,
+    // Largest total bid one wallet may hold on a day; 0 = unlimited.
+    // Snapshotted per day.
+    pub max_bid_per_wallet_lamports: u64,
+    // Minimum seconds between one wallet's bid updates on a day; 0 = none.
+    // Snapshotted per day.
+    pub bid_cooldown_seconds: i64,
+    // Days after a day's end its referrers have to claim their fees before
+    // `sweep_referral_fees` may take them; 0 = never.
+    pub referral_claim_window_days: u32,
+}
+
+impl Config {
+    pub const SPACE: usize = 8
+        + 32
+        + 8
+        + 8
+        + 1
+        + 32
+        + 2
+        + 2
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 8
+        + 32
+        + 32
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 32
+        + 8
+        + 2
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 2
+        + 8
+        + 8
+        + 4
+        + 8
+        + 8
+        + 8
+        + 4
+        + 32
+        + 8
+        + 1
+        + 2
+        + 8
+        + 1
+        + 4
+        + 2
+        + 2
+        + 8
+        + 2
+        + RecipientSplit::SPACE * MAX_RECIPIENT_SPLITS
+        + 1
+        + 2
+        + 8
+        + 2
+        + 1
+        + 4
+        + 32
+        + 32
+        + 8
+        + 8
+        + 8
+        + QuoteMint::SPACE * MAX_QUOTE_MINTS
+        + 1
+        + 32
+        + 2
+        + 4
+        + 32
+        + 1
+        + 1
+        + 8
+        + 8
+        + 4;
+
+    fn winner_message_limit(&self) -> usize {
+        (self.winner_message_max_len as usize).max(WINNER_MESSAGE_MAX_LEN)
+    }
+
+    fn claim_window_lapsed(&self, auction_day: &AuctionDay, now: i64) -> bool {
+        self.prize_claim_window_days > 0
+            && now
+                >= auction_day
+                    .end_ts
+                    .saturating_add(self.prize_claim_window_days as i64 * SECONDS_PER_DAY)
+    }
+
+    fn referral_window_lapsed(&self, auction_day: &AuctionDay, now: i64) -> bool {
+        self.referral_claim_window_days > 0
+            && now
+                >= auction_day
+                    .end_ts
+                    .saturating_add(self.referral_claim_window_days as i64 * SECONDS_PER_DAY)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RecipientSplit {
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+impl RecipientSplit {
+    pub const SPACE: usize = 32 + 2;
+}
+
+// A mint multi-currency days accept, priced by its Pyth `<mint>/USD` feed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct QuoteMint {
+    pub mint: Pubkey,
+    pub price_feed: Pubkey,
+}
+
+impl QuoteMint {
+    pub const SPACE: usize = 32 + 32;
+}
+
+// Rounding for basis-point math. `Floor` leaves fractional lamports with the
+// bidder (refund pool); `Ceil` assigns them to the fee side.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RoundingPolicy {
+    #[default]
+    Floor,
+    Ceil,
+}
+
+// Where `close_receipts_batch` sends reclaimed receipt rent: the bidder who
+// paid it, the cranker doing the cleanup, or `recipient_pubkey`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ReceiptRentPolicy {
+    #[default]
+    Owner,
+    Payer,
+    Treasury,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub recipient_pubkey: Option<Pubkey>,
+    pub loser_fee_lamports: Option<u64>,
+    pub min_increment_lamports: Option<u64>,
+    pub min_increment_bps: Option<u16>,
+    pub authority: Option<Pubkey>,
+    pub loser_fee_bps: Option<u16>,
+    pub loser_fee_cap_lamports: Option<u64>,
+    pub destructive_action_delay_seconds: Option<i64>,
+    pub min_opening_bid_lamports: Option<u64>,
+    pub bps_rounding: Option<RoundingPolicy>,
+    pub soft_close_window_seconds: Option<i64>,
+    pub soft_close_extension_seconds: Option<i64>,
+    pub priority_lane_seconds: Option<i64>,
+    pub floor_adapter_program: Option<Pubkey>,
+    pub floor_adapter_account: Option<Pubkey>,
+    pub floor_max_staleness_seconds: Option<i64>,
+    pub day_offset_seconds: Option<i64>,
+    pub receipt_rent_policy: Option<ReceiptRentPolicy>,
+    pub sealed_reveal_seconds: Option<i64>,
+    pub penny_tick_lamports: Option<u64>,
+    pub penny_bid_fee_lamports: Option<u64>,
+    pub penny_timer_seconds: Option<i64>,
+    pub candle_window_seconds: Option<i64>,
+    pub randomness_program: Option<Pubkey>,
+    pub randomness_account: Option<Pubkey>,
+    pub early_bird_window_seconds: Option<i64>,
+    pub early_bird_discount_bps: Option<u16>,
+    pub max_vault_exposure_lamports: Option<u64>,
+    pub winner_slots: Option<u8>,
+    pub content_bond_lamports: Option<u64>,
+    pub withdraw_cutoff_seconds: Option<i64>,
+    pub withdraw_penalty_bps: Option<u16>,
+    pub paused_instructions: Option<u64>,
+    pub promo_bid_max_lamports: Option<u64>,
+    pub min_bidders: Option<u32>,
+    pub refund_window_seconds: Option<i64>,
+    pub participation_cap_base_lamports: Option<u64>,
+    pub participation_cap_step_lamports: Option<u64>,
+    pub participation_veteran_days: Option<u32>,
+    pub day_rent_destination: Option<Pubkey>,
+    pub settle_reward_lamports: Option<u64>,
+    pub permissioned_refunds: Option<bool>,
+    pub cranker_fee_share_bps: Option<u16>,
+    pub min_cranker_bond_lamports: Option<u64>,
+    pub surplus_to_winner: Option<bool>,
+    pub streak_bonus_threshold: Option<u32>,
+    pub streak_bonus_bps: Option<u16>,
+    pub referral_fee_bps: Option<u16>,
+    pub jackpot_rollover_lamports: Option<u64>,
+    pub jackpot_fee_bps: Option<u16>,
+    // Replaces the whole table; an empty list pays everything to the recipient.
+    pub recipient_splits: Option<Vec<RecipientSplit>>,
+    pub burn_bps: Option<u16>,
+    pub protocol_rake_bps: Option<u16>,
+    pub treasury_custody: Option<bool>,
+    pub vesting_days: Option<u32>,
+    pub bid_mint: Option<Pubkey>,
+    pub usd_price_feed: Option<Pubkey>,
+    pub usd_max_staleness_seconds: Option<i64>,
+    pub min_increment_usd_cents: Option<u64>,
+    pub min_opening_bid_usd_cents: Option<u64>,
+    // Replaces the whole whitelist; an empty list turns multi-currency days off.
+    pub quote_mints: Option<Vec<QuoteMint>>,
+    pub receipt_tree: Option<Pubkey>,
+    pub winner_message_max_len: Option<u16>,
+    pub prize_claim_window_days: Option<u32>,
+    pub bid_gate: Option<Pubkey>,
+    pub bid_gate_collection: Option<bool>,
+    pub access_mode: Option<u8>,
+    pub max_bid_per_wallet_lamports: Option<u64>,
+    pub bid_cooldown_seconds: Option<i64>,
+    pub referral_claim_window_days: Option<u32>,
+}
+
+#[account]
+pub struct AuctionDay {
+    pub day_index: i64,
+    pub finalized: bool,
+    pub winner: Pubkey,
+    pub highest_bid: u64,
+    pub bidder_count: u32,
+    pub refund_count_total: u32,
+    pub refund_count_completed: u32,
+    pub total_bid_lamports: u64,
+    pub refund_pool_remaining: u64,
+    pub fee_pool_remaining: u64,
+    pub vault_bump: u8,
+    pub recent_crank_keys: [[u8; CRANK_KEY_LEN]; CRANK_KEY_HISTORY],
+    pub crank_key_cursor: u8,
+    pub loser_fee_lamports: u64,
+    pub min_increment_lamports: u64,
+    pub min_increment_bps: u16,
+    pub loser_fee_bps: u16,
+    pub loser_fee_cap_lamports: u64,
+    pub loser_fee_accrued: u64,
+    pub vault_version: u8,
+    pub min_opening_bid_lamports: u64,
+    pub buy_now_lamports: u64,
+    pub bidding_closed: bool,
+    pub bps_rounding: RoundingPolicy,
+    pub end_ts: i64,
+    pub soft_close_window_seconds: i64,
+    pub soft_close_extension_seconds: i64,
+    pub start_ts: i64,
+    pub priority_lane_seconds: i64,
+    pub top_bidders: [Pubkey; TOP_BIDDERS_LEN],
+    pub top_bids: [u64; TOP_BIDDERS_LEN],
+    pub sealed_reveal_seconds: i64,
+    pub penny_tick_lamports: u64,
+    pub penny_bid_fee_lamports: u64,
+    pub penny_timer_seconds: i64,
+    pub penny_fees_collected: u64,
+    pub follower_count: u32,
+    pub candle_window_seconds: i64,
+    pub candle_start_ts: i64,
+    pub candle_leaders: [Pubkey; CANDLE_SLOTS],
+    pub candle_bids: [u64; CANDLE_SLOTS],
+    pub candle_resolved: bool,
+    pub candle_cutoff_ts: i64,
+    pub early_bird_window_seconds: i64,
+    pub early_bird_discount_bps: u16,
+    pub exposure_lamports: u64,
+    pub winner_slots: u8,
+    pub highest_bid_ts: i64,
+    pub withdraw_cutoff_seconds: i64,
+    pub withdraw_penalty_bps: u16,
+    pub withdraw_penalties: u64,
+    pub promo_bid_count: u32,
+    pub category: u8,
+    pub moderation_level: u8,
+    pub claim_deadline_ts: i64,
+    pub reminder_level: u8,
+    pub min_bidders: u32,
+    pub cancelled: bool,
+    pub refund_window_seconds: i64,
+    pub refund_deadline_ts: i64,
+    pub swept: bool,
+    pub participation_cap_base_lamports: u64,
+    pub participation_cap_step_lamports: u64,
+    pub participation_veteran_days: u32,
+    // Closed receipts and reclaimed promo bids; `close_day` waits for both
+    // to catch up with their counts.
+    pub receipts_closed: u32,
+    pub promo_bids_reclaimed: u32,
+    // Next free `BidIndex` ordinal. Never decreases, so withdrawn bidders
+    // leave a dangling entry rather than a reused slot.
+    pub bid_index_count: u32,
+    // Next `BidIndex` ordinal `refund_next` will visit.
+    pub refund_cursor: u32,
+    // Authority-attested Merkle root of `(bidder, refund_amount)`; zero = none.
+    pub refund_root: [u8; 32],
+    pub settle_reward_lamports: u64,
+    pub cranker_fee_share_bps: u16,
+    // Loser-fee remainders held back from crankers; paid out by `sweep_unclaimed`.
+    pub treasury_fees_accrued: u64,
+    // Signer of the latest `refund_batch` or `refund_next`; whose bond `slash_cranker` takes.
+    pub last_refund_cranker: Pubkey,
+    pub surplus_to_winner: bool,
+    // Vault lamports held back as rent; payouts only draw on the rest.
+    pub vault_rent_lamports: u64,
+    // Bumped on every bid and refund and carried in their events, so
+    // indexers can order them and spot gaps or replays.
+    pub seq: u64,
+    pub streak_bonus_threshold: u32,
+    pub streak_bonus_bps: u16,
+    pub referral_fee_bps: u16,
+    // Referral fees set aside on receipts and not yet claimed.
+    pub referral_fees_accrued: u64,
+    pub vesting_days: u32,
+    // Proceeds escrowed at settlement and what `claim_vested` has paid so far.
+    pub vesting_total_lamports: u64,
+    pub vesting_claimed_lamports: u64,
+    pub vesting_start_ts: i64,
+    // Snapshot of `Config.bid_mint`. On token days the `*_lamports` amount
+    // fields hold the mint's base units.
+    pub bid_mint: Pubkey,
+    // Snapshot of `Config.quote_mints`. On multi-currency days the `*_lamports`
+    // amount fields hold micro-USD.
+    pub quote_mints: [QuoteMint; MAX_QUOTE_MINTS],
+    pub quote_mint_count: u8,
+    // Snapshot of `Config.receipt_tree`, overridable per day with `set_receipt_tree`.
+    pub receipt_tree: Pubkey,
+    // Snapshot of `Config.bid_gate` and `Config.bid_gate_collection`.
+    pub bid_gate: Pubkey,
+    pub bid_gate_collection: bool,
+    // Snapshot of `Config.max_bid_per_wallet_lamports`.
+    pub max_bid_per_wallet_lamports: u64,
+    // Snapshot of `Config.bid_cooldown_seconds`.
+    pub bid_cooldown_seconds: i64,
+    // Penny days: last bid time plus `penny_timer_seconds`; zero until the
+    // first bid. Bidding closes and settlement opens once it passes.
+    pub penny_deadline_ts: i64,
+}
+
+impl AuctionDay {
+    pub const SPACE: usize = 8
+        + 8
+        + 1
+        + 32
+        + 8
+        + 4
+        + 4
+        + 4
+        + 8
+        + 8
+        + 8
+        + 1
+        + CRANK_KEY_LEN * CRANK_KEY_HISTORY
+        + 1
+        + 8
+        + 8
+        + 2
+        + 2
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 1
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32 * TOP_BIDDERS_LEN
+        + 8 * TOP_BIDDERS_LEN
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 4
+        + 8
+        + 8
+        + 32 * CANDLE_SLOTS
+        + 8 * CANDLE_SLOTS
+        + 1
+        + 8
+        + 8
+        + 2
+        + 8
+        + 1
+        + 8
+        + 8
+        + 2
+        + 8
+        + 4
+        + 1
+        + 1
+        + 8
+        + 1
+        + 4
+        + 1
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 4
+        + 4
+        + 4
+        + 4
+        + 4
+        + 32
+        + 8
+        + 2
+        + 8
+        + 32
+        + 1
+        + 8
+        + 8
+        + 4
+        + 2
+        + 2
+        + 8
+        + 4
+        + 8
+        + 8
+        + 8
+        + 32
+        + QuoteMint::SPACE * MAX_QUOTE_MINTS
+        + 1
+        + 32
+        + 32
+        + 1
+        + 8
+        + 8
+        + 8;
+
+    // Bidding window is `[start_ts, end_ts)`; `end_ts` moves with soft-close extensions.
+    // Penny days close earlier once the countdown since the last bid runs out.
+    pub fn is_open_at(&self, now: i64) -> bool {
+        now >= self.start_ts && now < self.bidding_end_ts()
+    }
+
+    pub fn bidding_end_ts(&self) -> i64 {
+        if self.penny_deadline_ts > 0 {
+            self.end_ts.min(self.penny_deadline_ts)
+        } else {
+            self.end_ts
+        }
+    }
+
+    // Gated days only take bids through `place_bid` with proof of holding.
+    pub fn is_gated(&self) -> bool {
+        self.bid_gate != Pubkey::default()
+    }
+
+    // Multi-currency days take `place_bid_multi` in any of `quote_mints`.
+    pub fn is_multi_currency(&self) -> bool {
+        self.quote_mint_count > 0
+    }
+
+    pub fn quote_mint(&self, mint: &Pubkey) -> Option<QuoteMint> {
+        self.quote_mints[..self.quote_mint_count as usize]
+            .iter()
+            .find(|quote| quote.mint == *mint)
+            .copied()
+    }
+
+    // Sealed days take `commit_bid` during `[start_ts, end_ts)` and `reveal_bid`
+    // during `[end_ts, end_ts + sealed_reveal_seconds)`.
+    pub fn is_sealed(&self) -> bool {
+        self.sealed_reveal_seconds > 0
+    }
+
+    // Penny days: fixed `penny_tick_lamports` steps, a non-refundable
+    // `penny_bid_fee_lamports` per bid, and a countdown kept in `penny_deadline_ts`.
+    pub fn is_penny(&self) -> bool {
+        self.penny_tick_lamports > 0
+    }
+
+    // Candle days split `[candle_start_ts, candle_start_ts + candle_window_seconds)`
+    // into `CANDLE_SLOTS` slots; slot `i` holds the leader as of the end of that slot.
+    pub fn is_candle(&self) -> bool {
+        self.candle_window_seconds > 0
+    }
+
+    pub fn record_candle_leader(&mut self, leader: Pubkey, amount: u64, now: i64) {
+        let elapsed = now.saturating_sub(self.candle_start_ts).max(0);
+        let slot = ((elapsed as u128 * CANDLE_SLOTS as u128) / self.candle_window_seconds as u128)
+            .min(CANDLE_SLOTS as u128 - 1) as usize;
+        for (slot_leader, slot_bid) in self
+            .candle_leaders
+            .iter_mut()
+            .zip(self.candle_bids.iter_mut())
+            .skip(slot)
+        {
+            *slot_leader = leader;
+            *slot_bid = amount;
+        }
+    }
+
+    // The drawn slot never precedes the first bid, so a day with bids always
+    // has a winner.
+    pub fn candle_slot_for(&self, randomness: &[u8; 32]) -> usize {
+        let drawn = (u64::from_le_bytes(randomness[..8].try_into().unwrap())
+            % CANDLE_SLOTS as u64) as usize;
+        let first_bid_slot = self
+            .candle_bids
+            .iter()
+            .position(|bid| *bid > 0)
+            .unwrap_or(CANDLE_SLOTS - 1);
+        drawn.max(first_bid_slot)
+    }
+
+    pub fn candle_slot_end(&self, slot: usize) -> i64 {
+        self.candle_start_ts.saturating_add(
+            self.candle_window_seconds * (slot as i64 + 1) / CANDLE_SLOTS as i64,
+        )
+    }
+
+    // Multi-winner days: the top `winner_slots` entries of the leaderboard all
+    // win and pay their own bids.
+    pub fn is_multi_winner(&self) -> bool {
+        self.winner_slots > 1
+    }
+
+    pub fn winning_bids(&self) -> impl Iterator<Item = u64> + '_ {
+        let slots = if self.is_multi_winner() {
+            &self.top_bids[..self.winner_slots as usize]
+        } else {
+            core::slice::from_ref(&self.highest_bid)
+        };
+        slots.iter().copied().filter(|bid| *bid > 0)
+    }
+
+    pub fn settleable_at(&self) -> i64 {
+        self.bidding_end_ts().saturating_add(self.sealed_reveal_seconds)
+    }
+
+    pub fn next_seq(&mut self) -> Result<u64> {
+        self.seq = self.seq.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(self.seq)
+    }
+
+    // Escrowed proceeds vested by `now`, linear over `vesting_days` from settlement.
+    pub fn vested_lamports(&self, now: i64) -> Result<u64> {
+        if self.vesting_total_lamports == 0 {
+            return Ok(0);
+        }
+        let duration = i64::from(self.vesting_days)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let elapsed = now.saturating_sub(self.vesting_start_ts).clamp(0, duration);
+        let vested = u128::from(self.vesting_total_lamports) * elapsed as u128 / duration as u128;
+        u64::try_from(vested).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+
+    pub fn is_winner(&self, candidate: &Pubkey) -> bool {
+        if self.cancelled {
+            return false;
+        }
+        if self.is_multi_winner() {
+            return self
+                .top_bidders
+                .iter()
+                .zip(self.top_bids.iter())
+                .take(self.winner_slots as usize)
+                .any(|(bidder, bid)| *bid > 0 && bidder == candidate);
+        }
+        *candidate == self.winner
+    }
+
+    // During the final `priority_lane_seconds` only current top bidders may bid.
+    // The lane only engages once the leaderboard is full.
+    pub fn in_priority_lane(&self, now: i64) -> bool {
+        self.priority_lane_seconds > 0
+            && self.top_bids[TOP_BIDDERS_LEN - 1] > 0
+            && now >= self.end_ts.saturating_sub(self.priority_lane_seconds)
+    }
+
+    // Drops a withdrawn bidder and shifts the rest up; the freed slot stays empty.
+    pub fn remove_top_bid(&mut self, bidder: &Pubkey) {
+        if let Some(position) = self.top_bidders.iter().position(|key| key == bidder) {
+            self.top_bidders.copy_within(position + 1.., position);
+            self.top_bids.copy_within(position + 1.., position);
+            self.top_bidders[TOP_BIDDERS_LEN - 1] = Pubkey::default();
+            self.top_bids[TOP_BIDDERS_LEN - 1] = 0;
+        }
+    }
+
+    // Keeps `top_bidders`/`top_bids` sorted by amount, descending. Ties keep
+    // the earlier bidder ahead.
+    pub fn record_top_bid(&mut self, bidder: Pubkey, amount: u64) {
+        let mut entries = [(Pubkey::default(), 0u64); TOP_BIDDERS_LEN + 1];
+        let mut len = 0;
+        for i in 0..TOP_BIDDERS_LEN {
+            if self.top_bids[i] > 0 && self.top_bidders[i] != bidder {
+                entries[len] = (self.top_bidders[i], self.top_bids[i]);
+                len += 1;
+            }
+        }
+        let position = entries[..len]
+            .iter()
+            .position(|(_, existing)| *existing < amount)
+            .unwrap_or(len);
+        entries.copy_within(position..len, position + 1);
+        entries[position] = (bidder, amount);
+        for (i, (key, bid)) in entries.iter().take(TOP_BIDDERS_LEN).enumerate() {
+            self.top_bidders[i] = *key;
+            self.top_bids[i] = *bid;
+        }
+    }
+}
+
+#[account]
+pub struct BidReceipt {
+    pub auction_day: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub refunded: bool,
+    pub last_bid_ts: i64,
+    pub first_bid_ts: i64,
+    pub refund_tip_lamports: u64,
+    pub delivery_attempts: u8,
+    // Refund escrowed in this receipt after repeated delivery failures.
+    pub voucher_lamports: u64,
+    // One-shot "outbid me back" order; see `set_counter_raise`.
+    pub counter_raise_increment: u64,
+    pub counter_raise_max_bid: u64,
+    pub counter_raise_tip_lamports: u64,
+    pub counter_raise_escrow: u64,
+    pub counter_raise_epoch: u64,
+    // Set from `place_bid`'s optional referrer when the receipt opens.
+    pub referrer: Pubkey,
+    // The referrer's unclaimed cut of this receipt's loser fee.
+    pub referral_fee_lamports: u64,
+    // Multi-currency days: the mint this receipt bid in and the base units
+    // deposited, while `amount` holds their micro-USD value at bid time.
+    pub mint: Pubkey,
+    pub native_amount: u64,
+}
+
+impl BidReceipt {
+    pub const SPACE: usize =
+        8 + 32 + 32 + 8 + 1 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 32 + 8;
+}
+
+// On-chain bidder list: entry `ordinal` of a day at
+// `["bid_index", auction_day, ordinal_le_u32]`, written when `place_bid` opens
+// a new receipt.
+#[account]
+pub struct BidIndex {
+    pub auction_day: Pubkey,
+    pub ordinal: u32,
+    pub bidder: Pubkey,
+    pub bump: u8,
+}
+
+impl BidIndex {
+    pub const SPACE: usize = 8 + 32 + 4 + 32 + 1;
+}
+
+// Participation-only promo bid: counted for points, never escrowed, never a
+// winner. Rent is fronted by the promo pool and reclaimed after settlement.
+#[account]
+pub struct PromoBid {
+    pub auction_day: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub placed_at: i64,
+    pub bump: u8,
+}
+
+impl PromoBid {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+// Price floor adapter interface. Any program can publish a dynamic floor
+// (NFT floor, AMM TWAP, USD peg, ...) for `place_bid` by owning an account
+// whose data starts with:
+//   magic: [u8; 8] = "AFLOOR01", floor_lamports: u64 (LE), updated_at: i64 (LE)
+pub struct PriceFloorFeed {
+    pub floor_lamports: u64,
+    pub updated_at: i64,
+}
+
+impl PriceFloorFeed {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    pub fn try_from_account(account: &AccountInfo, expected_owner: &Pubkey) -> Result<Self> {
+        require_keys_eq!(
+            *account.owner,
+            *expected_owner,
+            ErrorCode::InvalidPriceFloorAdapter
+        );
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= Self::LEN && data[..8] == PRICE_FLOOR_MAGIC,
+            ErrorCode::InvalidPriceFloorAdapter
+        );
+        let floor_lamports = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let updated_at = i64::from_le_bytes(data[16..24].try_into().unwrap());
+        Ok(Self {
+            floor_lamports,
+            updated_at,
+        })
+    }
+}
+
+// Subset of a Pyth `PriceUpdateV2` account: discriminator, write_authority,
+// verification_level (Partial { u8 } | Full), then the price message
+// (feed_id, price i64, conf u64, exponent i32, publish_time i64, ...).
+pub struct PythPrice {
+    pub price: i64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    // Offset of the price message for a fully verified update.
+    const MESSAGE_OFFSET: usize = 8 + 32 + 1;
+    const LEN: usize = Self::MESSAGE_OFFSET + 32 + 8 + 8 + 4 + 8;
+
+    pub fn try_from_account(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *account.owner,
+            PYTH_PUSH_ORACLE,
+            ErrorCode::InvalidUsdPriceFeed
+        );
+        let data = account.try_borrow_data()?;
+        // Only fully verified updates (verification_level tag 1) are accepted.
+        require!(
+            data.len() >= Self::LEN
+                && data[..8] == PYTH_PRICE_UPDATE_DISCRIMINATOR
+                && data[40] == 1,
+            ErrorCode::InvalidUsdPriceFeed
+        );
+        let message = &data[Self::MESSAGE_OFFSET + 32..];
+        let price = i64::from_le_bytes(message[0..8].try_into().unwrap());
+        let exponent = i32::from_le_bytes(message[16..20].try_into().unwrap());
+        let publish_time = i64::from_le_bytes(message[20..28].try_into().unwrap());
+        require!(
+            price > 0 && (-18..=18).contains(&exponent),
+            ErrorCode::InvalidUsdPriceFeed
+        );
+        Ok(Self {
+            price,
+            exponent,
+            publish_time,
+        })
+    }
+
+    // Lamports worth `cents` on a SOL/USD feed, rounded up so a USD minimum is
+    // never undercut.
+    pub fn cents_to_lamports(&self, cents: u64) -> Result<u64> {
+        let micro_usd = cents
+            .checked_mul(MICRO_USD_PER_CENT)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.units_for_micro_usd(micro_usd, 9)
+    }
+
+    // Base units of a `decimals`-place asset worth `micro_usd`, rounded up:
+    // micro_usd * 10^(decimals - exponent - 6) / price.
+    pub fn units_for_micro_usd(&self, micro_usd: u64, decimals: u8) -> Result<u64> {
+        let shift = i32::from(decimals) - self.exponent - 6;
+        let scale = 10u128
+            .checked_pow(shift.unsigned_abs())
+            .ok_or(ErrorCode::MathOverflow)?;
+        let (numerator, denominator) = if shift >= 0 {
+            (u128::from(micro_usd).checked_mul(scale), Some(self.price as u128))
+        } else {
+            (Some(u128::from(micro_usd)), (self.price as u128).checked_mul(scale))
+        };
+        let (Some(numerator), Some(denominator)) = (numerator, denominator) else {
+            return err!(ErrorCode::MathOverflow);
+        };
+        let units = numerator.div_ceil(denominator);
+        Ok(u64::try_from(units).map_err(|_| ErrorCode::MathOverflow)?)
+    }
+}
+
+// Randomness adapter interface (Switchboard VRF or any other oracle) for candle
+// days. The configured account's owner program must write, per day:
+//   magic: [u8; 8] = "ARAND001", auction_day: Pubkey, value: [u8; 32], produced_at: i64 (LE)
+pub struct RandomnessFeed {
+    pub auction_day: Pubkey,
+    pub value: [u8; 32],
+    pub produced_at: i64,
+}
+
+impl RandomnessFeed {
+    pub const LEN: usize = 8 + 32 + 32 + 8;
+
+    pub fn try_from_account(account: &AccountInfo, expected_owner: &Pubkey) -> Result<Self> {
+        require_keys_eq!(
+            *account.owner,
+            *expected_owner,
+            ErrorCode::RandomnessMismatch
+        );
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= Self::LEN && data[..8] == RANDOMNESS_MAGIC,
+            ErrorCode::RandomnessMismatch
+        );
+        let auction_day = Pubkey::try_from(&data[8..40]).unwrap();
+        let value: [u8; 32] = data[40..72].try_into().unwrap();
+        let produced_at = i64::from_le_bytes(data[72..80].try_into().unwrap());
+        Ok(Self {
+            auction_day,
+            value,
+            produced_at,
+        })
+    }
+}
+
+// Pre-committed bid, executed by any cranker inside its window. Lamports above
+// rent are the escrow (`amount + tip_lamports`); the account closes to `owner`
+// on execution or cancellation. Voided by `revoke_all_authorizations`.
+#[account]
+pub struct ScheduledBid {
+    pub owner: Pubkey,
+    pub day_index: i64,
+    pub amount: u64,
+    pub execute_at: i64,
+    pub tolerance_seconds: i64,
+    pub tip_lamports: u64,
+    pub only_if_not_leading: bool,
+    pub authorization_epoch: u64,
+    pub bump: u8,
+}
+
+impl ScheduledBid {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 1;
+}
+
+// Hidden bid for a sealed day. The escrowed deposit lives on the bidder's
+// `BidReceipt`; this only holds the commitment
+// `sha256("sealed_bid" || auction_day || bidder || amount_le || salt)`.
+#[account]
+pub struct SealedBid {
+    pub auction_day: Pubkey,
+    pub bidder: Pubkey,
+    pub commitment: [u8; 32],
+    pub revealed: bool,
+    pub revealed_amount: u64,
+    pub bump: u8,
+}
+
+impl SealedBid {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 1 + 8 + 1;
+}
+
+// Immutable end-of-life marker, created once every refund for the day is done.
+#[account]
+pub struct DayComplete {
+    pub auction_day: Pubkey,
+    pub day_index: i64,
+    pub completed_at: i64,
+    pub winner: Pubkey,
+    pub winning_bid: u64,
+    pub refund_count: u32,
+    pub refund_pool_remaining: u64,
+    pub fee_pool_remaining: u64,
+    pub bump: u8,
+}
+
+impl DayComplete {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 32 + 8 + 4 + 8 + 8 + 1;
+}
+
+// Opaque payload for the display pipeline (CDN cache key, slot id, ...),
+// echoed verbatim in `DaySettled`.
+#[account]
+pub struct DayDisplayHook {
+    pub day_index: i64,
+    pub payload: Vec<u8>,
+    pub bump: u8,
+}
+
+impl DayDisplayHook {
+    pub const SPACE: usize = 8 + 8 + 4 + DISPLAY_PAYLOAD_MAX_LEN + 1;
+}
+
+// Admin calendar entry for one day (holidays, maintenance). Only days that were
+// ever overridden have this account.
+#[account]
+pub struct ScheduleOverride {
+    pub day_index: i64,
+    pub closed: bool,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub category: u8,
+}
+
+impl ScheduleOverride {
+    pub const SPACE: usize = 8 + 8 + 1 + 8 + 1 + 1;
+}
+
+// Admin allow/deny status for one wallet, set with `set_access_entry`. Only
+// wallets that were ever listed have this account.
+#[account]
+pub struct AccessEntry {
+    pub wallet: Pubkey,
+    pub status: u8,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl AccessEntry {
+    pub const SPACE: usize = 8 + 32 + 1 + 8 + 1;
+}
+
+// What a day auctions, written by the authority with `set_day_meta`. Keyed by
+// day index so clients can fetch it before the day exists.
+#[account]
+pub struct DayMeta {
+    pub day_index: i64,
+    pub title: String,
+    pub description: String,
+    pub image_uri: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl DayMeta {
+    pub const SPACE: usize = 8
+        + 8
+        + 4
+        + DAY_META_TITLE_MAX_LEN
+        + 4
+        + DAY_META_DESCRIPTION_MAX_LEN
+        + 4
+        + DAY_META_IMAGE_URI_MAX_LEN
+        + 8
+        + 1;
+}
+
+// Last day a wallet drew from the devnet faucet.
+#[cfg(feature = "devnet-faucet")]
+#[account]
+pub struct FaucetClaim {
+    pub wallet: Pubkey,
+    pub last_claim_day: i64,
+    pub bump: u8,
+}
+
+#[cfg(feature = "devnet-faucet")]
+impl FaucetClaim {
+    pub const SPACE: usize = 8 + 32 + 8 + 1;
+}
+
+// Per-wallet participation history, written by the program whenever the
+// wallet opens a receipt on a new day.
+#[account]
+pub struct BidderStats {
+    pub wallet: Pubkey,
+    pub days_participated: u32,
+    pub first_day_index: i64,
+    pub last_day_index: i64,
+}
+
+impl BidderStats {
+    pub const SPACE: usize = 8 + 32 + 4 + 8 + 8;
+
+    pub fn record_day(&mut self, wallet: Pubkey, day_index: i64) -> Result<()> {
+        if self.days_participated == 0 {
+            self.wallet = wallet;
+            self.first_day_index = day_index;
+            self.last_day_index = day_index;
+        }
+        self.days_participated = self
+            .days_participated
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.first_day_index = self.first_day_index.min(day_index);
+        self.last_day_index = self.last_day_index.max(day_index);
+        Ok(())
+    }
+}
+
+// Lifetime protocol totals for the website. `place_bid` creates it and keeps
+// the record bid current; `settle_day` adds each day that settles with a
+// winner. Cancelled and bidless days are not counted.
+#[account]
+pub struct GlobalStats {
+    pub days_settled: u64,
+    // Sum of every settled day's `total_bid_lamports`.
+    pub total_bid_volume_lamports: u64,
+    pub total_winning_lamports: u64,
+    // Loser fees (including any settle reward), penny bid fees, and
+    // withdrawal penalties.
+    pub total_fees_lamports: u64,
+    // Refund pools as set at settlement.
+    pub total_refunds_lamports: u64,
+    pub highest_bid_lamports: u64,
+    pub highest_bid_day_index: i64,
+}
+
+impl GlobalStats {
+    pub const SPACE: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn reset(&mut self) {
+        self.days_settled = 0;
+        self.total_bid_volume_lamports = 0;
+        self.total_winning_lamports = 0;
+        self.total_fees_lamports = 0;
+        self.total_refunds_lamports = 0;
+        self.highest_bid_lamports = 0;
+        self.highest_bid_day_index = 0;
+    }
+
+    pub fn record_bid(&mut self, day_index: i64, amount: u64) {
+        if amount > self.highest_bid_lamports {
+            self.highest_bid_lamports = amount;
+            self.highest_bid_day_index = day_index;
+        }
+    }
+
+    pub fn record_settlement(
+        &mut self,
+        auction_day: &AuctionDay,
+        winning_total: u64,
+        fees: u64,
+        refunds: u64,
+    ) -> Result<()> {
+        // Bids placed outside `place_bid` still reach the record here.
+        self.record_bid(auction_day.day_index, auction_day.highest_bid);
+        self.days_settled = self
+            .days_settled
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_bid_volume_lamports = self
+            .total_bid_volume_lamports
+            .checked_add(auction_day.total_bid_lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_winning_lamports = self
+            .total_winning_lamports
+            .checked_add(winning_total)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_fees_lamports = self
+            .total_fees_lamports
+            .checked_add(fees)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_refunds_lamports = self
+            .total_refunds_lamports
+            .checked_add(refunds)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+// Per-wallet lifetime bidding record for leaderboards, created lazily by
+// `place_bid`. A day counts as a loss once the wallet bids on a later day
+// without having been recorded as its winner; a win settled after that point
+// takes the loss back.
+#[account]
+pub struct BidderProfile {
+    pub wallet: Pubkey,
+    pub total_bids: u64,
+    pub total_lamports_bid: u64,
+    pub days_bid: u32,
+    pub wins: u32,
+    pub losses: u32,
+    // Latest day bid on that is not yet counted as a win or loss.
+    pub pending_day_index: i64,
+    // Latest day counted as a loss, so a late win can undo it.
+    pub last_loss_day_index: i64,
+    // Consecutive daily wins ending at `last_win_day_index`.
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub last_win_day_index: i64,
+}
+
+impl BidderProfile {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 4 + 4 + 4 + 8 + 8 + 4 + 4 + 8;
+
+    pub fn record_bid(&mut self, wallet: Pubkey, day_index: i64, lamports: u64) -> Result<()> {
+        if self.wallet == Pubkey::default() {
+            self.wallet = wallet;
+            self.pending_day_index = NO_DAY;
+            self.last_loss_day_index = NO_DAY;
+            self.last_win_day_index = NO_DAY;
+        }
+        if self.pending_day_index != day_index {
+            // `place_bid` only takes bids for the current day, so a pending
+            // earlier day has ended without a recorded win.
+            if self.pending_day_index != NO_DAY {
+                self.losses = self.losses.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+                self.last_loss_day_index = self.pending_day_index;
+            }
+            self.pending_day_index = day_index;
+            self.days_bid = self.days_bid.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        }
+        self.total_bids = self.total_bids.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        self.total_lamports_bid = self
+            .total_lamports_bid
+            .checked_add(lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    // Returns the streak the win extends to.
+    pub fn record_win(&mut self, day_index: i64) -> Result<u32> {
+        if self.pending_day_index == day_index {
+            self.pending_day_index = NO_DAY;
+        } else if self.last_loss_day_index == day_index {
+            self.losses -= 1;
+            self.last_loss_day_index = NO_DAY;
+        }
+        self.wins = self.wins.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        // Days settled out of order start a new streak.
+        self.current_streak = if self.last_win_day_index != NO_DAY
+            && day_index == self.last_win_day_index + 1
+        {
+            self.current_streak.checked_add(1).ok_or(ErrorCode::MathOverflow)?
+        } else {
+            1
+        };
+        self.last_win_day_index = day_index;
+        self.best_streak = self.best_streak.max(self.current_streak);
+        Ok(self.current_streak)
+    }
+}
+
+// Program-owned custody for settlement proceeds (`Config.treasury_custody`).
+// Only `withdraw_treasury` moves lamports out; the totals give auditors the
+// full in/out trail alongside the events.
+#[account]
+pub struct Treasury {
+    pub total_deposited_lamports: u64,
+    pub total_withdrawn_lamports: u64,
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const SPACE: usize = 8 + 8 + 8 + 1;
+}
+
+// Global prize pot for quiet days. `reserve_lamports` (fee share and
+// `fund_jackpot` deposits) is rolled into `pot_lamports` by each bidless or
+// cancelled day, and the next day to settle with a winner takes the pot.
+#[account]
+pub struct Jackpot {
+    pub reserve_lamports: u64,
+    pub pot_lamports: u64,
+    pub rollover_count: u32,
+}
+
+impl Jackpot {
+    pub const SPACE: usize = 8 + 8 + 8 + 4;
+
+    // Moves up to `amount` from the reserve into the pot. Returns what moved.
+    pub fn roll_over(&mut self, amount: u64) -> Result<u64> {
+        let lamports = amount.min(self.reserve_lamports);
+        if lamports == 0 {
+            return Ok(0);
+        }
+        self.reserve_lamports -= lamports;
+        self.pot_lamports = self
+            .pot_lamports
+            .checked_add(lamports)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.rollover_count = self.rollover_count.saturating_add(1);
+        Ok(lamports)
+    }
+}
+
+// The winner's message for a day, set with `set_message`.
+#[account]
+pub struct WinnerMessage {
+    pub auction_day: Pubkey,
+    // Who posted: the winner, or the trophy holder once it is minted.
+    pub winner: Pubkey,
+    pub message: String,
+    pub updated_at: i64,
+    pub bump: u8,
+    // Set by the authority through `moderate_message`. Hidden messages stay
+    // on-chain for audit; frontends should not display them.
+    pub hidden: bool,
+    pub moderated_at: i64,
+    // Off-chain long-form content and the SHA-256 of its bytes; empty URI =
+    // none.
+    pub uri: String,
+    pub content_hash: [u8; 32],
+    // The owner let the claim window lapse; only the authority may post now.
+    pub reassigned: bool,
+}
+
+impl WinnerMessage {
+    pub const SPACE: usize = WinnerMessage::space_for(WINNER_MESSAGE_MAX_LEN, 0);
+
+    const fn space_for(message_len: usize, uri_len: usize) -> usize {
+        8 + 32 + 32 + 4 + message_len + 8 + 1 + 1 + 8 + 4 + uri_len + 32 + 1
+    }
+}
+
+// Prize escrowed for a day's winner by `deposit_prize`. The tokens sit in the
+// `["prize_vault", auction_day]` token account owned by the day's vault PDA.
+#[account]
+pub struct Prize {
+    pub auction_day: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub depositor: Pubkey,
+    pub claimed: bool,
+    pub bump: u8,
+    // Taken back by the authority through `reclaim_prize`; `claimed` is set too.
+    pub reclaimed: bool,
+}
+
+impl Prize {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 32 + 1 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PricePoint {
+    pub day_index: i64,
+    pub price: u64,
+}
+
+impl PricePoint {
+    pub const SPACE: usize = 8 + 8;
+}
+
+// Reference series of daily clearing prices for other programs. Day `d` lives
+// in `points[d mod PRICE_SERIES_LEN]`; readers check `day_index` to tell a
+// fresh point from an older day that shared the slot.
+#[account]
+pub struct PriceSeries {
+    pub latest_day_index: i64,
+    pub points: [PricePoint; PRICE_SERIES_LEN],
+    pub bump: u8,
+}
+
+impl PriceSeries {
+    pub const SPACE: usize = 8 + 8 + PricePoint::SPACE * PRICE_SERIES_LEN + 1;
+}
+
+// Fee, increment and feature parameters a day ran with, as snapshotted into
+// its `AuctionDay` (after any category rules).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DayParams {
+    pub loser_fee_lamports: u64,
+    pub loser_fee_bps: u16,
+    pub loser_fee_cap_lamports: u64,
+    pub min_increment_lamports: u64,
+    pub min_increment_bps: u16,
+    pub min_opening_bid_lamports: u64,
+    pub bps_rounding: RoundingPolicy,
+    pub features: u64,
+}
+
+impl DayParams {
+    pub const SPACE: usize = 8 + 2 + 8 + 8 + 2 + 8 + 1 + 8;
+
+    fn of(auction_day: &AuctionDay) -> Self {
+        let flags = [
+            (auction_day.is_sealed(), PARAM_FEATURE_SEALED),
+            (auction_day.is_penny(), PARAM_FEATURE_PENNY),
+            (auction_day.is_candle(), PARAM_FEATURE_CANDLE),
+            (auction_day.is_multi_winner(), PARAM_FEATURE_MULTI_WINNER),
+            (auction_day.soft_close_window_seconds > 0, PARAM_FEATURE_SOFT_CLOSE),
+            (auction_day.priority_lane_seconds > 0, PARAM_FEATURE_PRIORITY_LANE),
+            (auction_day.early_bird_window_seconds > 0, PARAM_FEATURE_EARLY_BIRD),
+            (auction_day.withdraw_cutoff_seconds > 0, PARAM_FEATURE_WITHDRAWALS),
+            (auction_day.min_bidders > 0, PARAM_FEATURE_MIN_BIDDERS),
+            (auction_day.refund_window_seconds > 0, PARAM_FEATURE_REFUND_WINDOW),
+            (
+                auction_day.participation_cap_base_lamports > 0,
+                PARAM_FEATURE_PARTICIPATION_CAP,
+            ),
+            (
+                auction_day.max_bid_per_wallet_lamports > 0,
+                PARAM_FEATURE_WALLET_CAP,
+            ),
+            (auction_day.bid_cooldown_seconds > 0, PARAM_FEATURE_BID_COOLDOWN),
+        ];
+        Self {
+            loser_fee_lamports: auction_day.loser_fee_lamports,
+            loser_fee_bps: auction_day.loser_fee_bps,
+            loser_fee_cap_lamports: auction_day.loser_fee_cap_lamports,
+            min_increment_lamports: auction_day.min_increment_lamports,
+            min_increment_bps: auction_day.min_increment_bps,
+            min_opening_bid_lamports: auction_day.min_opening_bid_lamports,
+            bps_rounding: auction_day.bps_rounding,
+            features: flags
+                .iter()
+                .filter(|(enabled, _)| *enabled)
+                .fold(0, |features, (_, bit)| features | bit),
+        }
+    }
+}
+
+// One run of consecutive recorded days that shared the same `DayParams`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ParamSet {
+    pub first_day_index: i64,
+    pub last_day_index: i64,
+    pub params: DayParams,
+}
+
+impl ParamSet {
+    pub const SPACE: usize = 8 + 8 + DayParams::SPACE;
+}
+
+// Append-only audit trail of which rules governed which days. Entries never
+// change except for the latest one's `last_day_index`.
+#[account]
+pub struct ParamHistory {
+    pub entries: Vec<ParamSet>,
+    pub bump: u8,
+}
+
+impl ParamHistory {
+    pub const SPACE: usize = 8 + 4 + ParamSet::SPACE * PARAM_HISTORY_MAX_ENTRIES + 1;
+}
+
+// Operator rules for one content category. Zero reserve keeps the day's
+// `Config` opening bid; the loser fee is only replaced when
+// `override_loser_fee` is set. `moderation_level` is advisory for off-chain
+// review (0 = default, up to `MAX_MODERATION_LEVEL`).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CategoryRules {
+    pub label: [u8; 16],
+    pub min_opening_bid_lamports: u64,
+    pub override_loser_fee: bool,
+    pub loser_fee_lamports: u64,
+    pub loser_fee_bps: u16,
+    pub moderation_level: u8,
+}
+
+impl CategoryRules {
+    pub const SPACE: usize = 16 + 8 + 1 + 8 + 2 + 1;
+}
+
+// Singleton list of crankers allowed to refund in permissioned mode.
+#[account]
+pub struct CrankerRegistry {
+    pub crankers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl CrankerRegistry {
+    pub const SPACE: usize = 8 + 4 + 32 * CRANKER_REGISTRY_MAX_ENTRIES + 1;
+}
+
+// SOL a cranker stakes as recourse for the refunds it runs.
+#[account]
+pub struct CrankerBond {
+    pub cranker: Pubkey,
+    pub bonded_lamports: u64,
+    // Zero while bonded; otherwise when unbonding was requested.
+    pub unbond_requested_at: i64,
+    pub bump: u8,
+}
+
+impl CrankerBond {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 1;
+}
+
+// Singleton table of category rules, indexed by `ScheduleOverride.category`.
+// Slot 0 (`CATEGORY_DEFAULT`) is never read.
+#[account]
+pub struct CategoryTable {
+    pub rules: [CategoryRules; MAX_CATEGORIES],
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl CategoryTable {
+    pub const SPACE: usize = 8 + CategoryRules::SPACE * MAX_CATEGORIES + 8 + 1;
+}
+
+// Per-wallet revocation counter. Every authorization a wallet grants (delegates,
+// standing orders, scheduled bids, ...) records the epoch current at grant time
+// and is only honored while it still matches, so one bump revokes them all.
+#[account]
+pub struct AuthorizationEpoch {
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub revoked_at: i64,
+    pub bump: u8,
+}
+
+impl AuthorizationEpoch {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 1;
+}
+
+// Runtime feature detection for clients and CPI callers. Rewritten from the
+// compiled-in version and capability bits by `sync_program_info` after each upgrade.
+#[account]
+pub struct ProgramInfo {
+    pub version: [u16; 3],
+    pub capabilities: u64,
+    pub updated_at: i64,
+    pub updated_by: Pubkey,
+    pub bump: u8,
+    pub capabilities_ext: u64,
+}
+
+impl ProgramInfo {
+    pub const SPACE: usize = 8 + 2 * 3 + 8 + 8 + 32 + 1 + 8;
+}
+
+// Watch-only participation marker: no funds beyond rent, counted in
+// `AuctionDay.follower_count` separately from `bidder_count`.
+#[account]
+pub struct Follower {
+    pub auction_day: Pubkey,
+    pub user: Pubkey,
+    pub followed_at: i64,
+    pub bump: u8,
+}
+
+impl Follower {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+// Content-only delegate for a day's winner. It carries no on-chain power over
+// funds; the editor accepts `manager` in place of the winner while the grant's
+// epoch still matches the winner's `AuthorizationEpoch`. Pubkey::default() clears it.
+#[account]
+pub struct ContentManager {
+    pub auction_day: Pubkey,
+    pub winner: Pubkey,
+    pub manager: Pubkey,
+    pub authorization_epoch: u64,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+impl ContentManager {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+// Winner's content submission for a day. `bond_lamports` sits on top of the
+// rent until `release_content_bond` or `slash_content_bond`.
+#[account]
+pub struct DayContent {
+    pub auction_day: Pubkey,
+    pub winner: Pubkey,
+    pub content_hash: [u8; 32],
+    pub updated_at: i64,
+    pub bond_lamports: u64,
+    pub slashed: bool,
+    pub bump: u8,
+}
+
+impl DayContent {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DestructiveAction {
+    // `close_global_stats`.
+    CloseStatsAccount,
+    // `reset_global_stats`.
+    ResetCounters,
+}
+
+// Two-step confirmation for admin operations that destroy aggregate state.
+// The destructive instruction closes this account via `close` after
+// `require_ready` passes, so each proposal authorizes exactly one execution.
+#[account]
+pub struct PendingAdminAction {
+    pub action: DestructiveAction,
+    pub target: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_at: i64,
+    pub executable_at: i64,
+    // Last `DestructiveActionProposed` or `DestructiveActionPending`.
+    pub last_warned_at: i64,
+    pub bump: u8,
+}
+
+impl PendingAdminAction {
+    pub const SPACE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    pub fn require_ready(
+        &self,
+        action: DestructiveAction,
+        target: Pubkey,
+        now: i64,
+        events: &EventSink,
+    ) -> Result<()> {
+        require!(
+            self.action == action && self.target == target,
+            ErrorCode::DestructiveActionMismatch
+        );
+        require!(now >= self.executable_at, ErrorCode::DestructiveActionNotReady);
+        events.emit(DestructiveActionExecuted {
+            action,
+            target,
+            proposed_at: self.proposed_at,
+        })?;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DestructiveActionProposed {
+    pub action: DestructiveAction,
+    pub target: Pubkey,
+    pub proposer: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct DestructiveActionCancelled {
+    pub action: DestructiveAction,
+    pub target: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct DestructiveActionPending {
+    pub action: DestructiveAction,
+    pub target: Pubkey,
+    pub executable_at: i64,
+    pub seconds_remaining: i64,
+}
+
+#[event]
+pub struct DestructiveActionExecuted {
+    pub action: DestructiveAction,
+    pub target: Pubkey,
+    pub proposed_at: i64,
+}
+
+#[event]
+pub struct SoftCloseExtended {
+    pub day_index: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct PennyTimerReset {
+    pub day_index: i64,
+    pub leader: Pubkey,
+    pub highest_bid: u64,
+    pub deadline_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct BuyNowExecuted {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub buy_now_lamports: u64,
+}
+
+#[cfg(feature = "devnet-faucet")]
+#[event]
+pub struct FaucetDripped {
+    pub wallet: Pubkey,
+    pub day_index: i64,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RefundVoucherIssued {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct RefundVoucherRedeemed {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub destination: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct DayAccountsClosed {
+    pub day_index: i64,
+    pub rent_destination: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct RefundProofClaimed {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub loser_fee: u64,
+}
+
+#[event]
+pub struct UnclaimedSwept {
+    pub day_index: i64,
+    pub lamports: u64,
+    pub treasury_fees: u64,
+    pub refunds_forfeited: u32,
+}
+
+#[event]
+pub struct ParamsActivated {
+    pub entry_index: u32,
+    pub first_day_index: i64,
+    pub params: DayParams,
+}
+
+#[event]
+pub struct PricePublished {
+    pub day_index: i64,
+    pub price: u64,
+}
+
+#[event]
+pub struct DayCancelled {
+    pub day_index: i64,
+    pub bidder_count: u32,
+    pub refund_pool: u64,
+}
+
+#[event]
+pub struct DaySettled {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub winning_bid: u64,
+    pub bidder_count: u32,
+    pub refund_pool: u64,
+    pub fee_pool: u64,
+    pub display_payload: Vec<u8>,
+    pub settled_at: i64,
+    // The day's final `seq`; every bid event up to it precedes settlement.
+    pub seq: u64,
+}
+
+#[event]
+pub struct StreakBonusPaid {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub streak: u32,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct ReferralFeeClaimed {
+    pub day_index: i64,
+    pub referrer: Pubkey,
+    pub bidder: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct ReferralFeesSwept {
+    pub day_index: i64,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct JackpotRolledOver {
+    pub day_index: i64,
+    pub lamports: u64,
+    pub pot_lamports: u64,
+}
+
+#[event]
+pub struct JackpotAwarded {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct RecipientSplitPaid {
+    pub day_index: i64,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct FeesBurned {
+    pub day_index: i64,
+    pub lamports: u64,
+    pub total_burned_lamports: u64,
+}
+
+#[event]
+pub struct ProtocolRakeTaken {
+    pub day_index: i64,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct TreasuryDeposited {
+    pub day_index: i64,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub authority: Pubkey,
+    pub destination: Pubkey,
+    pub lamports: u64,
+    pub remaining_lamports: u64,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub day_index: i64,
+    pub lamports: u64,
+    pub claimed_lamports: u64,
+    pub total_lamports: u64,
+}
+
+#[event]
+pub struct PrizeDeposited {
+    pub day_index: i64,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PrizeReclaimed {
+    pub day_index: i64,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PrizeClaimed {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TrophyMinted {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub mint: Pubkey,
+    pub winning_bid: u64,
+}
+
+#[event]
+pub struct WinnerMessageSet {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub message: String,
+    pub updated_at: i64,
+    pub uri: String,
+    pub content_hash: [u8; 32],
+}
+
+#[event]
+pub struct WinnerMessageModerated {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub hidden: bool,
+    pub moderated_at: i64,
+}
+
+#[event]
+pub struct DayMetaSet {
+    pub day_index: i64,
+    pub title: String,
+    pub description: String,
+    pub image_uri: String,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct AccessEntrySet {
+    pub wallet: Pubkey,
+    pub status: u8,
+    pub updated_at: i64,
+}
+
+#[event]
+pub struct DayInitialized {
+    pub day_index: i64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub initialized_at: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub previous_amount: u64,
+    pub leading: bool,
+    pub placed_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct Outbid {
+    pub day_index: i64,
+    pub previous_leader: Pubkey,
+    pub previous_bid: u64,
+    pub leader: Pubkey,
+    pub bid: u64,
+    pub outbid_at: i64,
+    pub seq: u64,
+}
 
-            require!(
-                bid_receipt.auction_day == auction_day.key(),
-                ErrorCode::BidReceiptMismatch
-            );
-            require!(bid_receipt.bidder == *bidder_pubkey, ErrorCode::BidderMismatch);
+#[event]
+pub struct RefundIssued {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub refund_lamports: u64,
+    pub loser_fee: u64,
+    pub refunded_at: i64,
+    pub seq: u64,
+}
 
-            if bid_receipt.refunded {
-                continue;
-            }
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub update: ConfigUpdate,
+    pub updated_at: i64,
+}
 
-            if *bidder_pubkey == auction_day.winner {
-                bid_receipt.refunded = true;
-                auction_day.refund_count_completed = auction_day
-                    .refund_count_completed
-                    .checked_add(1)
-                    .ok_or(ErrorCode::MathOverflow)?;
-                write_bid_receipt(bid_receipt_info, &bid_receipt)?;
-                continue;
-            }
+#[event]
+pub struct VaultRentFunded {
+    pub day_index: i64,
+    pub payer: Pubkey,
+    pub lamports: u64,
+}
 
-            require!(
-                bid_receipt.amount > ctx.accounts.config.loser_fee_lamports,
-                ErrorCode::InvalidBidAmount
-            );
-            let refund_amount = bid_receipt
-                .amount
-                .checked_sub(ctx.accounts.config.loser_fee_lamports)
-                .ok_or(ErrorCode::MathOverflow)?;
+#[event]
+pub struct VaultSurplusSwept {
+    pub day_index: i64,
+    pub destination: Pubkey,
+    pub lamports: u64,
+}
 
-            require!(
-                auction_day.refund_pool_remaining >= refund_amount,
-                ErrorCode::InsufficientRefundPool
-            );
-            require!(
-                auction_day.fee_pool_remaining >= ctx.accounts.config.loser_fee_lamports,
-                ErrorCode::InsufficientFeePool
-            );
+#[event]
+pub struct SettleRewardPaid {
+    pub day_index: i64,
+    pub settler: Pubkey,
+    pub lamports: u64,
+}
 
-            let vault_lamports = **ctx.accounts.vault.to_account_info().lamports.borrow();
-            require!(
-                vault_lamports >= refund_amount + ctx.accounts.config.loser_fee_lamports,
-                ErrorCode::InsufficientVaultLamports
-            );
+#[event]
+pub struct WinnersSettled {
+    pub day_index: i64,
+    pub winners: Vec<Pubkey>,
+    pub winning_bids: Vec<u64>,
+    pub winning_total: u64,
+}
 
-            let seeds: &[&[u8]] = &[b"vault", auction_day_key.as_ref(), &[auction_day.vault_bump]];
-            let signer_seeds: &[&[&[u8]]] = &[seeds];
+#[event]
+pub struct WinnerVerified {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub challenge: [u8; 32],
+    pub verified_at: i64,
+}
 
-            let refund_ctx = CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: bidder_info.clone(),
-                },
-                signer_seeds,
-            );
-            transfer(refund_ctx, refund_amount)?;
+#[event]
+pub struct ScheduledBidExecuted {
+    pub owner: Pubkey,
+    pub day_index: i64,
+    pub amount: u64,
+    pub tip_lamports: u64,
+    pub cranker: Pubkey,
+}
 
-            let fee_ctx = CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.cranker.to_account_info(),
-                },
-                signer_seeds,
-            );
-            transfer(fee_ctx, ctx.accounts.config.loser_fee_lamports)?;
+#[event]
+pub struct CounterRaiseSet {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub increment: u64,
+    pub max_bid: u64,
+    pub tip_lamports: u64,
+}
 
-            bid_receipt.refunded = true;
-            auction_day.refund_pool_remaining = auction_day
-                .refund_pool_remaining
-                .checked_sub(refund_amount)
-                .ok_or(ErrorCode::MathOverflow)?;
-            auction_day.fee_pool_remaining = auction_day
-                .fee_pool_remaining
-                .checked_sub(ctx.accounts.config.loser_fee_lamports)
-                .ok_or(ErrorCode::MathOverflow)?;
-            auction_day.refund_count_completed = auction_day
-                .refund_count_completed
-                .checked_add(1)
-                .ok_or(ErrorCode::MathOverflow)?;
+#[event]
+pub struct CounterRaiseExecuted {
+    pub owner: Pubkey,
+    pub day_index: i64,
+    pub amount: u64,
+    pub tip_lamports: u64,
+    pub cranker: Pubkey,
+}
 
-            write_bid_receipt(bid_receipt_info, &bid_receipt)?;
-        }
+#[event]
+pub struct BidNetted {
+    pub bidder: Pubkey,
+    pub refund_day_index: i64,
+    pub day_index: i64,
+    pub refund_lamports: u64,
+    pub bid_delta: u64,
+    pub netted_lamports: u64,
+}
 
-        Ok(())
-    }
+#[event]
+pub struct ReceiptsClosed {
+    pub day_index: i64,
+    pub count: u32,
+    pub rent_lamports: u64,
+    pub policy: ReceiptRentPolicy,
 }
 
-fn current_day_index(clock: &Clock) -> i64 {
-    clock.unix_timestamp / SECONDS_PER_DAY
+#[event]
+pub struct CandleResolved {
+    pub day_index: i64,
+    pub cutoff_ts: i64,
+    pub winner: Pubkey,
+    pub winning_bid: u64,
 }
 
-fn is_uninitialized_auction_day(auction_day: &AuctionDay) -> bool {
-    auction_day.day_index == 0
-        && !auction_day.finalized
-        && auction_day.winner == Pubkey::default()
-        && auction_day.highest_bid == 0
-        && auction_day.bidder_count == 0
-        && auction_day.refund_count_total == 0
-        && auction_day.refund_count_completed == 0
-        && auction_day.total_bid_lamports == 0
-        && auction_day.refund_pool_remaining == 0
-        && auction_day.fee_pool_remaining == 0
+#[event]
+pub struct DayFollowed {
+    pub day_index: i64,
+    pub user: Pubkey,
+    pub follower_count: u32,
 }
 
-fn write_bid_receipt(account_info: &AccountInfo, receipt: &BidReceipt) -> Result<()> {
-    let mut data = account_info.try_borrow_mut_data()?;
-    let mut writer: &mut [u8] = &mut data;
-    receipt.try_serialize(&mut writer)?;
-    Ok(())
+#[event]
+pub struct ProgramInfoUpdated {
+    pub previous_version: [u16; 3],
+    pub version: [u16; 3],
+    pub capabilities: u64,
+    pub capabilities_ext: u64,
 }
 
-#[derive(Accounts)]
-pub struct InitConfig<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init,
-        payer = payer,
-        seeds = [b"config"],
-        bump,
-        space = Config::SPACE
-    )]
-    pub config: Account<'info, Config>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct ContentManagerSet {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub manager: Pubkey,
+    pub authorization_epoch: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(day_index: i64)]
-pub struct InitDay<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        payer = payer,
-        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
-        bump,
-        space = AuctionDay::SPACE
-    )]
-    pub auction_day: Account<'info, AuctionDay>,
-    #[account(
-        init_if_needed,
-        payer = payer,
-        seeds = [b"vault", auction_day.key().as_ref()],
-        bump,
-        space = 0,
-        owner = system_program::ID
-    )]
-    /// CHECK: PDA vault is system-owned (enforced by owner constraint + runtime checks).
-    pub vault: UncheckedAccount<'info>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct PauseSwitchesUpdated {
+    pub paused_instructions: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(day_index: i64)]
-pub struct PlaceBid<'info> {
-    #[account(mut)]
-    pub bidder: Signer<'info>,
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-    #[account(
-        init_if_needed,
-        payer = bidder,
-        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
-        bump,
-        space = AuctionDay::SPACE
-    )]
-    pub auction_day: Account<'info, AuctionDay>,
-    #[account(
-        init_if_needed,
-        payer = bidder,
-        seeds = [b"vault", auction_day.key().as_ref()],
-        bump,
-        space = 0,
-        owner = system_program::ID
-    )]
-    /// CHECK: PDA vault is system-owned (enforced by owner constraint + runtime checks).
-    pub vault: UncheckedAccount<'info>,
-    #[account(
-        init_if_needed,
-        payer = bidder,
-        seeds = [b"bid_receipt", auction_day.key().as_ref(), bidder.key().as_ref()],
-        bump,
-        space = BidReceipt::SPACE
-    )]
-    pub bid_receipt: Account<'info, BidReceipt>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct BundleBidPlaced {
+    pub bidder: Pubkey,
+    pub first_day_index: i64,
+    pub day_count: u8,
+    pub amount: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(day_index: i64)]
-pub struct SettleDay<'info> {
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-    #[account(
-        mut,
-        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub auction_day: Account<'info, AuctionDay>,
-    #[account(
-        mut,
-        seeds = [b"vault", auction_day.key().as_ref()],
-        bump = auction_day.vault_bump
-    )]
-    /// CHECK: PDA vault is system-owned (enforced by runtime check).
-    pub vault: UncheckedAccount<'info>,
-    /// CHECK: recipient is validated against config.
-    #[account(mut)]
-    pub recipient: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct PromoBidPlaced {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub promo_bid_count: u32,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(day_index: i64)]
-pub struct RefundBatch<'info> {
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-    #[account(
-        mut,
-        seeds = [b"auction_day", day_index.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub auction_day: Account<'info, AuctionDay>,
-    #[account(
-        mut,
-        seeds = [b"vault", auction_day.key().as_ref()],
-        bump = auction_day.vault_bump
-    )]
-    /// CHECK: PDA vault is system-owned (enforced by runtime check).
-    pub vault: UncheckedAccount<'info>,
-    #[account(mut)]
-    pub cranker: Signer<'info>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct BidWithdrawn {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+    pub seq: u64,
 }
 
-#[account]
-pub struct Config {
-    pub recipient_pubkey: Pubkey,
-    pub loser_fee_lamports: u64,
-    pub min_increment_lamports: u64,
-    pub bump: u8,
+#[event]
+pub struct RefundTipSet {
+    pub day_index: i64,
+    pub bidder: Pubkey,
+    pub tip_lamports: u64,
 }
 
-impl Config {
-    pub const SPACE: usize = 8 + 32 + 8 + 8 + 1;
+#[event]
+pub struct ContentSubmitted {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub content_hash: [u8; 32],
+    pub bond_lamports: u64,
 }
 
-#[account]
-pub struct AuctionDay {
+#[event]
+pub struct ClaimReminder {
     pub day_index: i64,
-    pub finalized: bool,
     pub winner: Pubkey,
-    pub highest_bid: u64,
-    pub bidder_count: u32,
-    pub refund_count_total: u32,
-    pub refund_count_completed: u32,
-    pub total_bid_lamports: u64,
-    pub refund_pool_remaining: u64,
-    pub fee_pool_remaining: u64,
-    pub vault_bump: u8,
+    pub claim_deadline_ts: i64,
+    pub reminder_level: u8,
 }
 
-impl AuctionDay {
-    pub const SPACE: usize = 8 + 8 + 1 + 32 + 8 + 4 + 4 + 4 + 8 + 8 + 8 + 1;
+#[event]
+pub struct CrankerBonded {
+    pub cranker: Pubkey,
+    pub lamports: u64,
+    pub bonded_lamports: u64,
 }
 
-#[account]
-pub struct BidReceipt {
-    pub auction_day: Pubkey,
-    pub bidder: Pubkey,
-    pub amount: u64,
-    pub refunded: bool,
+#[event]
+pub struct CrankerSlashed {
+    pub day_index: i64,
+    pub cranker: Pubkey,
+    pub lamports: u64,
 }
 
-impl BidReceipt {
-    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+#[event]
+pub struct ContentBondSettled {
+    pub day_index: i64,
+    pub winner: Pubkey,
+    pub bond_lamports: u64,
+    pub slashed: bool,
+}
+
+#[event]
+pub struct VaultMigrated {
+    pub day_index: i64,
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    pub lamports: u64,
+    pub outstanding_lamports: u64,
+    pub from_version: u8,
+    pub to_version: u8,
 }
 
 #[error_code]
@@ -603,4 +11830,294 @@ pub enum ErrorCode {
     InsufficientFeePool,
     #[msg("Vault is not owned by the system program")]
     InvalidVaultOwner,
+    #[msg("Idempotency key must be non-zero")]
+    InvalidIdempotencyKey,
+    #[msg("Signer is not the config authority")]
+    Unauthorized,
+    #[msg("Basis points value out of range")]
+    InvalidBps,
+    #[msg("Vault already migrated")]
+    VaultAlreadyMigrated,
+    #[msg("Destructive action delay is too short")]
+    DestructiveActionDelayTooShort,
+    #[msg("Pending action does not match this operation")]
+    DestructiveActionMismatch,
+    #[msg("Destructive action waiting period has not elapsed")]
+    DestructiveActionNotReady,
+    #[msg("Display payload is too long")]
+    DisplayPayloadTooLong,
+    #[msg("Bidding is closed for this day")]
+    BiddingClosed,
+    #[msg("Buy-now price must exceed the current highest bid")]
+    InvalidBuyNowPrice,
+    #[msg("Soft-close window and extension must be within one day")]
+    InvalidSoftClose,
+    #[msg("Refunds are not complete for this day")]
+    RefundsIncomplete,
+    #[msg("Only current top bidders may bid during the priority lane")]
+    PriorityLaneOnly,
+    #[msg("Priority lane must be shorter than one day")]
+    InvalidPriorityLane,
+    #[msg("Price floor adapter account is required")]
+    PriceFloorMissing,
+    #[msg("Price floor account does not match config")]
+    PriceFloorMismatch,
+    #[msg("Price floor adapter account is invalid")]
+    InvalidPriceFloorAdapter,
+    #[msg("Price floor is stale")]
+    PriceFloorStale,
+    #[msg("Bid is below the external price floor")]
+    BelowPriceFloor,
+    #[msg("Day offset must be less than one day")]
+    InvalidDayOffset,
+    #[msg("Day is closed by the schedule")]
+    DayClosed,
+    #[msg("Day already has bids")]
+    DayHasBids,
+    #[msg("Bid receipt has not been refunded")]
+    ReceiptNotRefunded,
+    #[msg("Rent destination does not match the receipt rent policy")]
+    RentDestinationMismatch,
+    #[msg("Sealed reveal window must be between 0 and one day")]
+    InvalidSealedRevealWindow,
+    #[msg("Day uses sealed bids; use commit_bid")]
+    SealedBidDay,
+    #[msg("Day does not use sealed bids")]
+    NotSealedBidDay,
+    #[msg("Reveal window is not open")]
+    RevealWindowClosed,
+    #[msg("Bid already revealed")]
+    BidAlreadyRevealed,
+    #[msg("Revealed bid does not match commitment")]
+    SealedBidCommitmentMismatch,
+    #[msg("Revealed bid exceeds deposit")]
+    InsufficientDeposit,
+    #[msg("No pending loser refund to net")]
+    NoRefundToNet,
+    #[msg("Invalid scheduled bid")]
+    InvalidScheduledBid,
+    #[msg("Scheduled bid is outside its execution window")]
+    ScheduledBidOutsideWindow,
+    #[msg("Authorization was revoked")]
+    AuthorizationRevoked,
+    #[msg("Owner is already leading")]
+    ScheduledBidNotNeeded,
+    #[msg("Penny auction needs a countdown timer")]
+    InvalidPennyAuction,
+    #[msg("Penny bid must be exactly one tick above the current price")]
+    PennyTickMismatch,
+    #[msg("Candle window must be between 0 and one day")]
+    InvalidCandleWindow,
+    #[msg("Day is not a candle auction")]
+    NotCandleDay,
+    #[msg("Candle already resolved")]
+    CandleAlreadyResolved,
+    #[msg("Candle cutoff must be resolved before settlement")]
+    CandleNotResolved,
+    #[msg("Randomness account is invalid for this day")]
+    RandomnessMismatch,
+    #[msg("Winner does not match the candle cutoff leader")]
+    CandleWinnerMismatch,
+    #[msg("Early-bird window must be between 0 and one day")]
+    InvalidEarlyBirdWindow,
+    #[msg("Bid would exceed the global vault exposure cap")]
+    VaultExposureCapExceeded,
+    #[msg("Winner slots must be between 1 and the leaderboard size, without candle mode")]
+    InvalidWinnerSlots,
+    #[msg("Content display period is over")]
+    DisplayPeriodOver,
+    #[msg("Content display period has not ended")]
+    DisplayPeriodNotOver,
+    #[msg("Content bond was slashed")]
+    ContentBondSlashed,
+    #[msg("Bid receipt already refunded")]
+    ReceiptAlreadyRefunded,
+    #[msg("Missing or malformed Ed25519 signature instruction")]
+    InvalidSignatureInstruction,
+    #[msg("Withdraw cutoff must be between 0 and one day")]
+    InvalidWithdrawCutoff,
+    #[msg("Bid withdrawals are disabled for this day")]
+    WithdrawalsDisabled,
+    #[msg("Withdrawal cutoff has passed")]
+    WithdrawCutoffPassed,
+    #[msg("Winning bidders cannot withdraw")]
+    LeaderCannotWithdraw,
+    #[msg("Unknown pause switch bits")]
+    InvalidPauseSwitches,
+    #[msg("Instruction is paused")]
+    InstructionPaused,
+    #[msg("Promo bids are disabled")]
+    PromoBidsDisabled,
+    #[msg("Promo bid must be positive and within the promo limit")]
+    InvalidPromoBid,
+    #[msg("Promo pool cannot cover this bid")]
+    PromoPoolEmpty,
+    #[msg("Bundle must cover 1-7 days between today and the bundle horizon")]
+    InvalidBundle,
+    #[msg("Unknown category or invalid category rules")]
+    InvalidCategory,
+    #[msg("Categorized day requires the category table account")]
+    CategoryTableRequired,
+    #[msg("Day is already initialized")]
+    DayAlreadyInitialized,
+    #[msg("Day has no outstanding winner claim")]
+    NoClaimObligation,
+    #[msg("No new claim reminder is due")]
+    ReminderNotDue,
+    #[msg("Day has no clearing price")]
+    NoClearingPrice,
+    #[msg("A newer day already occupies this price slot")]
+    StalePricePoint,
+    #[msg("Faucet already claimed today")]
+    FaucetAlreadyClaimed,
+    #[msg("Faucet PDA is not the play-money mint authority")]
+    FaucetMintAuthority,
+    #[msg("Winning receipts have no refund to claim")]
+    NoRefundToClaim,
+    #[msg("Refund window must not be negative")]
+    InvalidRefundWindow,
+    #[msg("Refund window has not passed")]
+    RefundWindowOpen,
+    #[msg("Refund window has closed and unclaimed funds were swept")]
+    RefundWindowClosed,
+    #[msg("Receipt holds no refund voucher")]
+    NoVoucher,
+    #[msg("Receipt still holds an unredeemed refund voucher")]
+    VoucherOutstanding,
+    #[msg("Bid exceeds the cap for this wallet's participation history")]
+    ParticipationCapExceeded,
+    #[msg("Counter-raises are not available on sealed or penny days")]
+    CounterRaiseUnavailable,
+    #[msg("Counter-raise needs a positive increment and a max above the current bid")]
+    InvalidCounterRaise,
+    #[msg("Receipt has no armed counter-raise")]
+    NoCounterRaise,
+    #[msg("Owner is not outbid")]
+    CounterRaiseNotNeeded,
+    #[msg("Counter-raise would exceed the owner's max bid")]
+    CounterRaiseAboveMax,
+    #[msg("Receipt still holds counter-raise escrow")]
+    CounterRaiseOutstanding,
+    #[msg("Day still has open bid receipts or promo bids")]
+    ReceiptsOutstanding,
+    #[msg("Vault holds more than rent")]
+    VaultNotEmpty,
+    #[msg("Winner account is required with the winner's receipt")]
+    WinnerAccountRequired,
+    #[msg("Parameter history is full")]
+    ParamHistoryFull,
+    #[msg("A new bid needs its BidIndex account")]
+    BidIndexRequired,
+    #[msg("BidIndex account does not match the next ordinal")]
+    BidIndexMismatch,
+    #[msg("Refund cursor already reached the end of the bidder list")]
+    RefundCursorExhausted,
+    #[msg("Only the authority may attest a refund root")]
+    RefundRootUnauthorized,
+    #[msg("Day has no refund root")]
+    NoRefundRoot,
+    #[msg("Refund proof does not match the day's root")]
+    InvalidRefundProof,
+    #[msg("Need exactly one receipt bump per receipt")]
+    InvalidReceiptBumps,
+    #[msg("Cranker is not registered for permissioned refunds")]
+    CrankerNotRegistered,
+    #[msg("Cranker registry is full")]
+    CrankerRegistryFull,
+    #[msg("Cranker bond missing, too small, or unbonding")]
+    CrankerBondRequired,
+    #[msg("Unbonding delay has not passed")]
+    UnbondPending,
+    #[msg("Day accounting invariant violated")]
+    DayInvariantViolated,
+    #[msg("Day invariants hold; nothing to slash")]
+    DayInvariantsHold,
+    #[msg("Transfer would dip into the vault's rent reserve")]
+    VaultRentReserved,
+    #[msg("Signer is not this receipt's referrer")]
+    ReferrerMismatch,
+    #[msg("No referral fee to claim")]
+    NoReferralFee,
+    #[msg("Recipient splits must be non-empty entries totalling at most 10,000 bps")]
+    InvalidRecipientSplits,
+    #[msg("The incinerator account is required to burn fees")]
+    IncineratorRequired,
+    #[msg("The protocol treasury account is required to take the rake")]
+    ProtocolTreasuryRequired,
+    #[msg("The treasury account is required while treasury custody is on")]
+    TreasuryRequired,
+    #[msg("Treasury balance is insufficient")]
+    InsufficientTreasuryBalance,
+    #[msg("Nothing has vested since the last claim")]
+    NothingVested,
+    #[msg("Bid currency does not match the day's bid mint")]
+    BidCurrencyMismatch,
+    #[msg("Token days only support the plain single-winner format")]
+    TokenDayFormatUnsupported,
+    #[msg("Token account does not match the bidder or mint")]
+    TokenAccountMismatch,
+    #[msg("The token vault received less than the bid after transfer fees")]
+    TransferFeeShortfall,
+    #[msg("The token program account is required")]
+    TokenProgramRequired,
+    #[msg("The USD price feed account is required for USD minimums")]
+    UsdPriceFeedMissing,
+    #[msg("USD price feed account does not match config")]
+    UsdPriceFeedMismatch,
+    #[msg("USD price feed is not a verified Pyth price update")]
+    InvalidUsdPriceFeed,
+    #[msg("USD price feed is stale")]
+    UsdPriceStale,
+    #[msg("Quote mints must be distinct, fully set, at most 4, and exclusive with bid_mint")]
+    InvalidQuoteMints,
+    #[msg("Prize already claimed")]
+    PrizeAlreadyClaimed,
+    #[msg("Prize amount must be positive")]
+    InvalidPrizeAmount,
+    #[msg("Participation receipt accounts are required while the day has a receipt tree")]
+    ReceiptTreeAccountsRequired,
+    #[msg("Merkle tree does not match the day's receipt tree")]
+    ReceiptTreeMismatch,
+    #[msg("Winner message exceeds the configured maximum length")]
+    WinnerMessageTooLong,
+    #[msg("Winner message URI exceeds 200 bytes")]
+    WinnerMessageUriTooLong,
+    #[msg("The day's trophy is minted; pass the token account holding it")]
+    DayDeedRequired,
+    #[msg("Day metadata field exceeds its maximum length")]
+    DayMetaTooLong,
+    #[msg("The winner's claim window has not lapsed")]
+    ClaimWindowOpen,
+    #[msg("Bidder does not hold the day's gate token")]
+    BidGateNotMet,
+    #[msg("Gate metadata is not the Metaplex metadata of the gate token")]
+    InvalidGateMetadata,
+    #[msg("Invalid access mode or status")]
+    InvalidAccessMode,
+    #[msg("Access list enforcement requires the bidder's access entry account")]
+    AccessEntryRequired,
+    #[msg("Wallet is denylisted")]
+    WalletDenied,
+    #[msg("Wallet is not on the allowlist")]
+    WalletNotAllowlisted,
+    #[msg("Bid exceeds the day's per-wallet cap")]
+    WalletBidCapExceeded,
+    #[msg("Bid cooldown must be non-negative")]
+    InvalidBidCooldown,
+    #[msg("Wait for the bid cooldown before raising again")]
+    BidCooldownActive,
+    #[msg("Streak bonus days require the winner's profile account")]
+    WinnerProfileRequired,
+    #[msg("Jackpot fees or rollovers are configured but the jackpot account is missing")]
+    JackpotRequired,
+    #[msg("The day's referral claim window has closed")]
+    ReferralClaimWindowClosed,
+    #[msg("The day's referral claim window is still open")]
+    ReferralClaimWindowOpen,
+    #[msg("Destructive action waiting period is over; nothing left to warn about")]
+    DestructiveActionReady,
+    #[msg("Next destructive action warning is not due yet")]
+    DestructiveActionWarningNotDue,
+    #[msg("Content bond was not slashed")]
+    ContentBondNotSlashed,
 }
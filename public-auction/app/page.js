@@ -16,6 +16,10 @@ const SHARE_URL = "https://www.thedailyauction.com";
 const GITHUB_URL = "https://github.com/neelsomani/the-daily-auction";
 const X_INTENT = "https://twitter.com/intent/tweet";
 const SECONDS_PER_DAY = 86400;
+// Byte offset of `AuctionDay.bid_index_count` (u32) in the account data.
+const AUCTION_DAY_BID_INDEX_COUNT_OFFSET = 922;
+// Byte offset of `AuctionDay.bid_gate` (pubkey), followed by `bid_gate_collection` (bool).
+const AUCTION_DAY_BID_GATE_OFFSET = 1394;
 
 function secondsUntilNextUtcMidnight(nowMs) {
   const now = new Date(nowMs);
@@ -85,6 +89,19 @@ function decodeAuctionDay(buffer) {
   const feePoolRemaining = readU64(buffer, cursor);
   cursor += 8;
   const vaultBump = buffer[cursor];
+  const bidIndexCount =
+    buffer.length >= AUCTION_DAY_BID_INDEX_COUNT_OFFSET + 4
+      ? readU32(buffer, AUCTION_DAY_BID_INDEX_COUNT_OFFSET)
+      : 0;
+  let bidGate = null;
+  let bidGateCollection = false;
+  if (buffer.length >= AUCTION_DAY_BID_GATE_OFFSET + 33) {
+    const gate = new PublicKey(buffer.subarray(AUCTION_DAY_BID_GATE_OFFSET, AUCTION_DAY_BID_GATE_OFFSET + 32));
+    if (!gate.equals(PublicKey.default)) {
+      bidGate = gate;
+      bidGateCollection = buffer[AUCTION_DAY_BID_GATE_OFFSET + 32] === 1;
+    }
+  }
   return {
     dayIndex,
     finalized,
@@ -97,9 +114,32 @@ function decodeAuctionDay(buffer) {
     refundPoolRemaining,
     feePoolRemaining,
     vaultBump,
+    bidIndexCount,
+    bidGate,
+    bidGateCollection,
   };
 }
 
+// One of the wallet's token accounts holding the gate mint, for members-only days.
+async function findGateTokenAccount(owner, mint, rpcUrl) {
+  const body = {
+    jsonrpc: "2.0",
+    id: 1,
+    method: "getTokenAccountsByOwner",
+    params: [owner.toBase58(), { mint: mint.toBase58() }, { encoding: "jsonParsed" }],
+  };
+  const resp = await fetch(rpcUrl, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(body),
+  });
+  const json = await resp.json();
+  const held = (json?.result?.value || []).find(
+    (entry) => entry?.account?.data?.parsed?.info?.tokenAmount?.amount !== "0"
+  );
+  return held ? new PublicKey(held.pubkey) : null;
+}
+
 function u64ToLeBytes(value) {
   const buf = Buffer.alloc(8);
   let temp = BigInt(value);
@@ -133,7 +173,46 @@ function auctionPdas(programId, dayIndex, bidder) {
     programKey
   );
   const [config] = PublicKey.findProgramAddressSync([Buffer.from("config")], programKey);
-  return { programKey, auctionDay, vault, bidReceipt, config };
+  const [scheduleOverride] = PublicKey.findProgramAddressSync(
+    [Buffer.from("schedule_override"), i64ToLeBytes(dayIndex)],
+    programKey
+  );
+  const [categoryTable] = PublicKey.findProgramAddressSync([Buffer.from("category_table")], programKey);
+  const [bidderStats] = PublicKey.findProgramAddressSync(
+    [Buffer.from("bidder_stats"), bidder.toBuffer()],
+    programKey
+  );
+  const [globalStats] = PublicKey.findProgramAddressSync([Buffer.from("global_stats")], programKey);
+  const [bidderProfile] = PublicKey.findProgramAddressSync(
+    [Buffer.from("bidder_profile"), bidder.toBuffer()],
+    programKey
+  );
+  const [accessEntry] = PublicKey.findProgramAddressSync([Buffer.from("access"), bidder.toBuffer()], programKey);
+  const [eventAuthority] = PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], programKey);
+  return {
+    programKey,
+    auctionDay,
+    vault,
+    bidReceipt,
+    config,
+    scheduleOverride,
+    categoryTable,
+    bidderStats,
+    globalStats,
+    bidderProfile,
+    accessEntry,
+    eventAuthority,
+  };
+}
+
+function bidIndexPda(programKey, auctionDay, ordinal) {
+  const ordinalBytes = Buffer.alloc(4);
+  ordinalBytes.writeUInt32LE(ordinal);
+  const [bidIndex] = PublicKey.findProgramAddressSync(
+    [Buffer.from("bid_index"), auctionDay.toBuffer(), ordinalBytes],
+    programKey
+  );
+  return bidIndex;
 }
 
 function decodeBidReceipt(buffer) {
@@ -176,6 +255,88 @@ async function fetchAuctionDay(programId, dayIndex, rpcUrl) {
   return decodeAuctionDay(buffer);
 }
 
+// WinnerMessage: discriminator, auction_day, winner, a borsh string,
+// updated_at, bump, the moderation flag, moderated_at, then the content URI.
+// Hidden messages are not shown.
+function decodeWinnerMessage(buffer) {
+  let cursor = 8 + 32 + 32;
+  const length = buffer.readUInt32LE(cursor);
+  const text = buffer.subarray(cursor + 4, cursor + 4 + length).toString("utf8");
+  cursor += 4 + length + 8 + 1;
+  const hidden = buffer.readUInt8(cursor) === 1;
+  cursor += 1 + 8;
+  const uriLength = buffer.readUInt32LE(cursor);
+  const uri = buffer.subarray(cursor + 4, cursor + 4 + uriLength).toString("utf8");
+  if (hidden) {
+    return null;
+  }
+  return { text, uri: uri.startsWith("https://") ? uri : "" };
+}
+
+async function fetchWinnerMessage(programId, dayIndex, rpcUrl) {
+  const { auctionDay, programKey } = auctionPdas(programId, dayIndex, PublicKey.default);
+  const [winnerMessage] = PublicKey.findProgramAddressSync(
+    [Buffer.from("winner_message"), auctionDay.toBuffer()],
+    programKey
+  );
+  const body = {
+    jsonrpc: "2.0",
+    id: 1,
+    method: "getAccountInfo",
+    params: [winnerMessage.toBase58(), { encoding: "base64" }],
+  };
+  const resp = await fetch(rpcUrl, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(body),
+  });
+  const json = await resp.json();
+  const data = json?.result?.value?.data?.[0];
+  if (!data) {
+    return null;
+  }
+  return decodeWinnerMessage(Buffer.from(data, "base64"));
+}
+
+// DayMeta: discriminator, day_index, then title, description and image_uri
+// as borsh strings.
+function decodeDayMeta(buffer) {
+  let cursor = 8 + 8;
+  const readString = () => {
+    const length = buffer.readUInt32LE(cursor);
+    const value = buffer.subarray(cursor + 4, cursor + 4 + length).toString("utf8");
+    cursor += 4 + length;
+    return value;
+  };
+  const title = readString();
+  const description = readString();
+  return { title, description };
+}
+
+async function fetchDayMeta(programId, dayIndex, rpcUrl) {
+  const [dayMeta] = PublicKey.findProgramAddressSync(
+    [Buffer.from("day_meta"), i64ToLeBytes(dayIndex)],
+    new PublicKey(programId)
+  );
+  const body = {
+    jsonrpc: "2.0",
+    id: 1,
+    method: "getAccountInfo",
+    params: [dayMeta.toBase58(), { encoding: "base64" }],
+  };
+  const resp = await fetch(rpcUrl, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(body),
+  });
+  const json = await resp.json();
+  const data = json?.result?.value?.data?.[0];
+  if (!data) {
+    return null;
+  }
+  return decodeDayMeta(Buffer.from(data, "base64"));
+}
+
 async function fetchBidReceipt(programId, dayIndex, bidder, rpcUrl) {
   const { bidReceipt } = auctionPdas(programId, dayIndex, bidder);
   const body = {
@@ -208,6 +369,8 @@ export default function Home() {
   const [secondsRemaining, setSecondsRemaining] = useState(null);
   const [winner, setWinner] = useState("Loading...");
   const [yesterdayWinner, setYesterdayWinner] = useState("Loading...");
+  const [winnerMessage, setWinnerMessage] = useState(null);
+  const [dayMeta, setDayMeta] = useState(null);
   const [currentPrice, setCurrentPrice] = useState("--");
   const [winnerPubkey, setWinnerPubkey] = useState(null);
   const [userBidLamports, setUserBidLamports] = useState(0n);
@@ -244,10 +407,14 @@ export default function Home() {
     }
     const dayIndex = Math.floor(Date.now() / 1000 / SECONDS_PER_DAY);
     const yesterdayIndex = Math.max(0, dayIndex - 1);
-    const [data, yesterdayData] = await Promise.all([
+    const [data, yesterdayData, yesterdayMessage, todayMeta] = await Promise.all([
       fetchAuctionDay(programId, dayIndex, rpcUrl),
       fetchAuctionDay(programId, yesterdayIndex, rpcUrl),
+      fetchWinnerMessage(programId, yesterdayIndex, rpcUrl),
+      fetchDayMeta(programId, dayIndex, rpcUrl),
     ]);
+    setWinnerMessage(yesterdayMessage);
+    setDayMeta(todayMeta);
     if (!yesterdayData || yesterdayData.highestBid === 0n) {
       setYesterdayWinner("No bids yesterday");
     } else {
@@ -312,14 +479,55 @@ export default function Home() {
     const dayIndex = Math.floor(Date.now() / 1000 / SECONDS_PER_DAY);
     const lamports = Math.floor(solAmount * 1_000_000_000);
     const pdas = auctionPdas(programId, dayIndex, publicKey);
+    const rpcUrl = process.env.NEXT_PUBLIC_RPC_URL || "https://api.devnet.solana.com";
+    const day = await fetchAuctionDay(programId, dayIndex, rpcUrl);
+    // A first bid opens a receipt and must claim the day's next BidIndex slot.
+    let bidIndex = pdas.programKey;
+    if (userBidLamports === 0n) {
+      bidIndex = bidIndexPda(pdas.programKey, pdas.auctionDay, day ? day.bidIndexCount : 0);
+    }
+    // Mint-gated days need a token account holding the gate. Collection gates
+    // are not looked up here, so those bids fail on-chain.
+    let gateTokenAccount = pdas.programKey;
+    if (day?.bidGate && !day.bidGateCollection) {
+      gateTokenAccount = (await findGateTokenAccount(publicKey, day.bidGate, rpcUrl)) || pdas.programKey;
+    }
 
     const keys = [
       { pubkey: publicKey, isSigner: true, isWritable: true },
-      { pubkey: pdas.config, isSigner: false, isWritable: false },
+      { pubkey: pdas.config, isSigner: false, isWritable: true },
       { pubkey: pdas.auctionDay, isSigner: false, isWritable: true },
       { pubkey: pdas.vault, isSigner: false, isWritable: true },
       { pubkey: pdas.bidReceipt, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: pdas.scheduleOverride, isSigner: false, isWritable: false },
+      // No price floor adapter (the program ID stands in for a missing optional account).
+      { pubkey: pdas.programKey, isSigner: false, isWritable: false },
+      { pubkey: pdas.categoryTable, isSigner: false, isWritable: false },
+      { pubkey: pdas.bidderStats, isSigner: false, isWritable: true },
+      { pubkey: bidIndex, isSigner: false, isWritable: true },
+      { pubkey: pdas.globalStats, isSigner: false, isWritable: true },
+      { pubkey: pdas.bidderProfile, isSigner: false, isWritable: true },
+      // No referrer, wSOL account, or token program.
+      { pubkey: pdas.programKey, isSigner: false, isWritable: false },
+      { pubkey: pdas.programKey, isSigner: false, isWritable: false },
+      { pubkey: pdas.programKey, isSigner: false, isWritable: false },
+      // Pyth SOL/USD feed, needed only when the config sets USD minimums.
+      {
+        pubkey: process.env.NEXT_PUBLIC_USD_PRICE_FEED
+          ? new PublicKey(process.env.NEXT_PUBLIC_USD_PRICE_FEED)
+          : pdas.programKey,
+        isSigner: false,
+        isWritable: false,
+      },
+      // Gate token account, and no gate metadata.
+      { pubkey: gateTokenAccount, isSigner: false, isWritable: false },
+      { pubkey: pdas.programKey, isSigner: false, isWritable: false },
+      // The bidder's access list entry; always passed, usually uninitialized.
+      { pubkey: pdas.accessEntry, isSigner: false, isWritable: false },
+      // Event CPI accounts: the event authority and the program itself.
+      { pubkey: pdas.eventAuthority, isSigner: false, isWritable: false },
+      { pubkey: pdas.programKey, isSigner: false, isWritable: false },
     ];
 
     const data = await buildPlaceBidData(dayIndex, lamports);
@@ -395,8 +603,27 @@ export default function Home() {
           <p className="banner__winner-line">
             <span>Today’s winner:</span> <strong>{yesterdayWinner}</strong>
           </p>
+          {winnerMessage && (
+            <p className="banner__winner-message">
+              {winnerMessage.text}
+              {winnerMessage.uri && (
+                <>
+                  {" "}
+                  <a href={winnerMessage.uri} target="_blank" rel="noopener noreferrer nofollow">
+                    Read more
+                  </a>
+                </>
+              )}
+            </p>
+          )}
         </div>
         <div className="banner__right">
+          {dayMeta?.title && (
+            <p className="banner__day-meta">
+              <strong>{dayMeta.title}</strong>
+              {dayMeta.description && <span>{dayMeta.description}</span>}
+            </p>
+          )}
           <div className="banner__stats">
             <div className="banner__stat">
               <span className="label">Auction ends in</span>